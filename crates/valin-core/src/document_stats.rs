@@ -0,0 +1,58 @@
+use ropey::Rope;
+
+use crate::{fs::TextFormat, indentation::Indentation};
+
+/// Statistics of a text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentStats {
+    pub lines: usize,
+    pub words: usize,
+    pub chars: usize,
+    /// Size of the text once saved, with its encoding and line endings.
+    pub bytes: usize,
+    /// Line and chars of the longest line.
+    pub longest_line: (usize, usize),
+    pub indentation: Indentation,
+}
+
+impl DocumentStats {
+    /// Go through the text once, counting everything at the same time.
+    pub fn new(rope: &Rope, format: TextFormat, indentation: Indentation) -> Self {
+        let mut words = 0;
+        let mut is_in_word = false;
+        let mut longest_line = (0, 0);
+        let mut line = 0;
+        let mut line_len = 0;
+
+        for ch in rope.chars() {
+            if ch == '\n' {
+                if line_len > longest_line.1 {
+                    longest_line = (line, line_len);
+                }
+                line += 1;
+                line_len = 0;
+            } else if ch != '\r' {
+                line_len += 1;
+            }
+
+            if ch.is_whitespace() {
+                is_in_word = false;
+            } else if !is_in_word {
+                is_in_word = true;
+                words += 1;
+            }
+        }
+        if line_len > longest_line.1 {
+            longest_line = (line, line_len);
+        }
+
+        Self {
+            lines: rope.len_lines(),
+            words,
+            chars: rope.len_chars(),
+            bytes: format.encode(&rope.to_string()).len(),
+            longest_line,
+            indentation,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use super::TextFormat;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImageKind {
+    Raster,
+    Svg,
+}
+
+/// The content of a file, classified so it can be opened with the right kind of tab.
+pub enum FileContent {
+    Text(String, TextFormat),
+    Image { bytes: Vec<u8>, kind: ImageKind },
+    Binary(Vec<u8>),
+}
+
+impl FileContent {
+    pub fn detect(path: &Path, bytes: Vec<u8>) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+
+        match extension.as_deref() {
+            Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp" | "ico") => {
+                return Self::Image {
+                    bytes,
+                    kind: ImageKind::Raster,
+                }
+            }
+            Some("svg") => {
+                return Self::Image {
+                    bytes,
+                    kind: ImageKind::Svg,
+                }
+            }
+            _ => {}
+        }
+
+        match TextFormat::decode(&bytes) {
+            Some((text, format)) => Self::Text(text, format),
+            None => Self::Binary(bytes),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    Match,
+};
+use tracing::info;
+
+use super::FSTransport;
+
+/// Decide what files are hidden from the files explorer and the search,
+/// using the exclude globs from the settings and the `.gitignore` files of the folders.
+#[derive(Clone)]
+pub struct ExcludeMatcher {
+    root: PathBuf,
+    globs: Arc<Gitignore>,
+    /// `.gitignore` files loaded so far, from the root folder down.
+    gitignores: Vec<Arc<Gitignore>>,
+}
+
+impl ExcludeMatcher {
+    /// Matcher with only the exclude globs, see [`Self::for_folder`] for the `.gitignore` files.
+    pub fn new(root: &Path, exclude: &[String]) -> Self {
+        let mut builder = GitignoreBuilder::new(root);
+
+        for glob in exclude {
+            if let Err(err) = builder.add_line(None, glob) {
+                info!("Invalid exclude glob '{glob}': {err}");
+            }
+        }
+
+        let globs = builder.build().unwrap_or_else(|err| {
+            info!("Failed to build the exclude globs: {err}");
+            Gitignore::empty()
+        });
+
+        Self {
+            root: root.to_path_buf(),
+            globs: Arc::new(globs),
+            gitignores: Vec::new(),
+        }
+    }
+
+    /// Matcher for the entries of a folder, with the `.gitignore` files
+    /// from the root folder down to the folder.
+    pub async fn for_folder(&self, folder: &Path, transport: &FSTransport) -> Self {
+        let mut matcher = Self {
+            gitignores: Vec::new(),
+            ..self.clone()
+        };
+
+        let mut folders = folder
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(&self.root))
+            .collect::<Vec<&Path>>();
+        folders.reverse();
+        for folder in folders {
+            matcher.load_gitignore(folder, transport).await;
+        }
+
+        matcher
+    }
+
+    /// Add the `.gitignore` of a folder, read through the transport. A missing one is fine.
+    pub async fn load_gitignore(&mut self, folder: &Path, transport: &FSTransport) {
+        let path = folder.join(".gitignore");
+        let Ok(content) = transport.read_to_string(&path).await else {
+            return;
+        };
+
+        let mut builder = GitignoreBuilder::new(folder);
+        for line in content.lines() {
+            if let Err(err) = builder.add_line(Some(path.clone()), line) {
+                info!("Invalid line in {path:?}: {err}");
+            }
+        }
+
+        match builder.build() {
+            Ok(gitignore) => self.gitignores.push(Arc::new(gitignore)),
+            Err(err) => info!("Failed to build {path:?}: {err}"),
+        }
+    }
+
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        if !path.starts_with(&self.root) {
+            return false;
+        }
+
+        if self
+            .globs
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+        {
+            return true;
+        }
+
+        // The deepest `.gitignore` wins, like in git
+        for gitignore in self.gitignores.iter().rev() {
+            if !path.starts_with(gitignore.path()) {
+                continue;
+            }
+            match gitignore.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::FSMemory;
+
+    fn transport(files: impl IntoIterator<Item = (&'static str, &'static str)>) -> FSTransport {
+        let files = files
+            .into_iter()
+            .map(|(path, content)| (PathBuf::from(path), content.as_bytes().to_vec()));
+        Arc::new(Box::new(FSMemory::new(PathBuf::from("/project"), files)))
+    }
+
+    #[tokio::test]
+    async fn nested_gitignores() {
+        let transport = transport([
+            ("/project/.gitignore", "*.log\n/build\n"),
+            ("/project/web/.gitignore", "dist\n!keep.log\n"),
+        ]);
+        let root = Path::new("/project");
+        let matcher = ExcludeMatcher::new(root, &["target".to_string()]);
+
+        let root_matcher = matcher.for_folder(root, &transport).await;
+        assert!(root_matcher.is_excluded(Path::new("/project/target"), true));
+        assert!(root_matcher.is_excluded(Path::new("/project/build"), true));
+        assert!(root_matcher.is_excluded(Path::new("/project/debug.log"), false));
+        assert!(!root_matcher.is_excluded(Path::new("/project/web"), true));
+
+        let web = Path::new("/project/web");
+        let web_matcher = matcher.for_folder(web, &transport).await;
+        assert!(web_matcher.is_excluded(Path::new("/project/web/dist"), true));
+        assert!(web_matcher.is_excluded(Path::new("/project/web/debug.log"), false));
+        assert!(!web_matcher.is_excluded(Path::new("/project/web/keep.log"), false));
+        // Only anchored to the root folder
+        assert!(!web_matcher.is_excluded(Path::new("/project/web/build"), true));
+
+        // Paths outside of the root folder are never excluded
+        assert!(!web_matcher.is_excluded(Path::new("/other/debug.log"), false));
+    }
+}
@@ -0,0 +1,77 @@
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use tokio::sync::mpsc::UnboundedSender;
+
+pub type FSTransport = Arc<Box<dyn FSTransportInterface>>;
+
+/// Keeps a watch alive until dropped.
+pub type FSWatcher = Box<dyn Any + Send + Sync>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FSEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FSMetadata {
+    pub is_dir: bool,
+    pub read_only: bool,
+    /// Last time it was modified, when the transport knows.
+    pub modified: Option<SystemTime>,
+}
+
+#[async_trait]
+pub trait FSTransportInterface: Send + Sync {
+    async fn read_to_string(&self, path: &Path) -> tokio::io::Result<String>;
+
+    async fn read(&self, path: &Path) -> tokio::io::Result<Vec<u8>>;
+
+    /// Create or replace a file with the given content.
+    async fn write(&self, path: &Path, content: &[u8]) -> tokio::io::Result<()>;
+
+    /// Write a file through a temporary file renamed into its place, so it's never left half written.
+    /// Transports that can't do it write the file directly.
+    async fn write_atomic(&self, path: &Path, content: &[u8]) -> tokio::io::Result<()> {
+        self.write(path, content).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> tokio::io::Result<()>;
+
+    /// Delete a file, or a folder with everything inside.
+    async fn remove(&self, path: &Path) -> tokio::io::Result<()>;
+
+    /// Move a file or a folder to the trash of the system.
+    /// Transports without a trash delete it permanently.
+    async fn trash(&self, path: &Path) -> tokio::io::Result<()> {
+        self.remove(path).await
+    }
+
+    /// Whether the entries moved to the trash can be restored with [`Self::restore_from_trash`].
+    fn can_restore_from_trash(&self) -> bool {
+        false
+    }
+
+    /// Restore the last file or folder moved to the trash from the given path.
+    async fn restore_from_trash(&self, _path: &Path) -> tokio::io::Result<()> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> tokio::io::Result<()>;
+
+    /// Files and folders directly inside a folder.
+    async fn list(&self, path: &Path) -> tokio::io::Result<Vec<FSEntry>>;
+
+    async fn metadata(&self, path: &Path) -> tokio::io::Result<FSMetadata>;
+
+    async fn canonicalize(&self, path: &Path) -> tokio::io::Result<PathBuf>;
+
+    /// Send the paths changed inside a folder, for as long as the returned watcher is kept.
+    fn watch(&self, path: &Path, sender: UnboundedSender<PathBuf>) -> tokio::io::Result<FSWatcher>;
+}
@@ -0,0 +1,196 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::UnboundedSender;
+use uuid::Uuid;
+
+use super::{FSEntry, FSMetadata, FSTransportInterface, FSWatcher};
+
+pub struct FSLocal;
+
+/// The file a path points to, following its symlinks even if the file doesn't exist yet.
+async fn resolve_symlinks(path: &Path) -> tokio::io::Result<PathBuf> {
+    let mut path = path.to_path_buf();
+    // Bounded, in case of symlink loops
+    for _ in 0..32 {
+        let is_symlink = match tokio::fs::symlink_metadata(&path).await {
+            Ok(metadata) => metadata.file_type().is_symlink(),
+            Err(err) if err.kind() == ErrorKind::NotFound => false,
+            Err(err) => return Err(err),
+        };
+        if !is_symlink {
+            return Ok(path);
+        }
+
+        // Relative targets are relative to the folder of the symlink
+        let target = tokio::fs::read_link(&path).await?;
+        path = path.parent().unwrap_or(Path::new("")).join(target);
+    }
+    Err(std::io::Error::other(format!(
+        "Too many symlinks in {path:?}"
+    )))
+}
+
+#[async_trait]
+impl FSTransportInterface for FSLocal {
+    async fn read_to_string(&self, path: &Path) -> tokio::io::Result<String> {
+        tokio::fs::read_to_string(path).await
+    }
+
+    async fn read(&self, path: &Path) -> tokio::io::Result<Vec<u8>> {
+        tokio::fs::read(path).await
+    }
+
+    async fn write(&self, path: &Path, content: &[u8]) -> tokio::io::Result<()> {
+        tokio::fs::write(path, content).await
+    }
+
+    async fn write_atomic(&self, path: &Path, content: &[u8]) -> tokio::io::Result<()> {
+        // Symlinks are kept, the file they point to is the one replaced
+        let path = resolve_symlinks(path).await?;
+        let permissions = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => Some(metadata.permissions()),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => return Err(err),
+        };
+
+        // In the same folder, as renaming across filesystems is not possible
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let temp_path =
+            path.with_file_name(format!(".{file_name}.{}.tmp", Uuid::new_v4().simple()));
+
+        let result = async {
+            tokio::fs::write(&temp_path, content).await?;
+            tokio::fs::OpenOptions::new()
+                .write(true)
+                .open(&temp_path)
+                .await?
+                .sync_all()
+                .await?;
+            if let Some(permissions) = permissions {
+                tokio::fs::set_permissions(&temp_path, permissions).await?;
+            }
+            tokio::fs::rename(&temp_path, &path).await
+        }
+        .await;
+
+        if result.is_err() {
+            tokio::fs::remove_file(&temp_path).await.ok();
+        }
+        result
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> tokio::io::Result<()> {
+        tokio::fs::rename(from, to).await
+    }
+
+    async fn remove(&self, path: &Path) -> tokio::io::Result<()> {
+        if tokio::fs::metadata(path).await?.is_dir() {
+            tokio::fs::remove_dir_all(path).await
+        } else {
+            tokio::fs::remove_file(path).await
+        }
+    }
+
+    async fn trash(&self, path: &Path) -> tokio::io::Result<()> {
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || trash::delete(path))
+            .await?
+            .map_err(std::io::Error::other)
+    }
+
+    fn can_restore_from_trash(&self) -> bool {
+        // The trash can only be listed in Windows and the freedesktop.org platforms
+        cfg!(any(
+            target_os = "windows",
+            all(
+                unix,
+                not(target_os = "macos"),
+                not(target_os = "ios"),
+                not(target_os = "android")
+            )
+        ))
+    }
+
+    #[cfg(any(
+        target_os = "windows",
+        all(
+            unix,
+            not(target_os = "macos"),
+            not(target_os = "ios"),
+            not(target_os = "android")
+        )
+    ))]
+    async fn restore_from_trash(&self, path: &Path) -> tokio::io::Result<()> {
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            // The same path might have been trashed more than once
+            let item = trash::os_limited::list()
+                .map_err(std::io::Error::other)?
+                .into_iter()
+                .filter(|item| item.original_path() == path)
+                .max_by_key(|item| item.time_deleted)
+                .ok_or(std::io::ErrorKind::NotFound)?;
+            trash::os_limited::restore_all([item]).map_err(std::io::Error::other)
+        })
+        .await?
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> tokio::io::Result<()> {
+        tokio::fs::create_dir_all(path).await
+    }
+
+    async fn list(&self, path: &Path) -> tokio::io::Result<Vec<FSEntry>> {
+        let mut read_dir = tokio::fs::read_dir(path).await?;
+        let mut entries = Vec::new();
+
+        while let Some(entry) = read_dir.next_entry().await? {
+            entries.push(FSEntry {
+                path: entry.path(),
+                is_dir: entry.file_type().await?.is_dir(),
+            });
+        }
+
+        Ok(entries)
+    }
+
+    async fn metadata(&self, path: &Path) -> tokio::io::Result<FSMetadata> {
+        let metadata = tokio::fs::metadata(path).await?;
+        Ok(FSMetadata {
+            is_dir: metadata.is_dir(),
+            read_only: metadata.permissions().readonly(),
+            modified: metadata.modified().ok(),
+        })
+    }
+
+    async fn canonicalize(&self, path: &Path) -> tokio::io::Result<PathBuf> {
+        tokio::fs::canonicalize(path).await
+    }
+
+    fn watch(&self, path: &Path, sender: UnboundedSender<PathBuf>) -> tokio::io::Result<FSWatcher> {
+        let mut watcher = RecommendedWatcher::new(
+            move |event: notify::Result<Event>| {
+                if let Ok(event) = event {
+                    for path in event.paths {
+                        sender.send(path).ok();
+                    }
+                }
+            },
+            notify::Config::default(),
+        )
+        .map_err(std::io::Error::other)?;
+
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .map_err(std::io::Error::other)?;
+
+        Ok(Box::new(watcher))
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    collections::BTreeMap,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{FSEntry, FSMetadata, FSTransportInterface, FSWatcher};
+
+enum MemoryEntry {
+    File(Vec<u8>),
+    Folder,
+}
+
+/// A filesystem that only lives in memory, with every path under `root`.
+pub struct FSMemory {
+    root: PathBuf,
+    entries: Mutex<BTreeMap<PathBuf, MemoryEntry>>,
+    watchers: Mutex<Vec<(PathBuf, UnboundedSender<PathBuf>)>>,
+}
+
+fn not_found(path: &Path) -> Error {
+    Error::new(ErrorKind::NotFound, format!("{path:?} not found"))
+}
+
+impl FSMemory {
+    pub fn new(root: PathBuf, files: impl IntoIterator<Item = (PathBuf, Vec<u8>)>) -> Self {
+        let mut entries = BTreeMap::new();
+        entries.insert(root.clone(), MemoryEntry::Folder);
+
+        let memory = Self {
+            root,
+            entries: Mutex::new(entries),
+            watchers: Mutex::new(Vec::new()),
+        };
+
+        for (path, content) in files {
+            memory.insert_file(path, content);
+        }
+
+        memory
+    }
+
+    /// Insert a file and the folders above it.
+    fn insert_file(&self, path: PathBuf, content: Vec<u8>) {
+        let mut entries = self.entries.lock().unwrap();
+        for ancestor in path.ancestors().skip(1) {
+            if !ancestor.starts_with(&self.root) {
+                break;
+            }
+            entries
+                .entry(ancestor.to_path_buf())
+                .or_insert(MemoryEntry::Folder);
+        }
+        entries.insert(path, MemoryEntry::File(content));
+    }
+
+    fn check_path(&self, path: &Path) -> tokio::io::Result<()> {
+        if path.starts_with(&self.root) {
+            Ok(())
+        } else {
+            Err(not_found(path))
+        }
+    }
+
+    fn notify(&self, path: &Path) {
+        self.watchers
+            .lock()
+            .unwrap()
+            // Closed watchers are forgotten
+            .retain(|(watched, sender)| {
+                !path.starts_with(watched) || sender.send(path.to_path_buf()).is_ok()
+            });
+    }
+}
+
+#[async_trait]
+impl FSTransportInterface for FSMemory {
+    async fn read_to_string(&self, path: &Path) -> tokio::io::Result<String> {
+        let content = self.read(path).await?;
+        String::from_utf8(content).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    async fn read(&self, path: &Path) -> tokio::io::Result<Vec<u8>> {
+        match self.entries.lock().unwrap().get(path) {
+            Some(MemoryEntry::File(content)) => Ok(content.clone()),
+            Some(MemoryEntry::Folder) => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{path:?} is a folder"),
+            )),
+            None => Err(not_found(path)),
+        }
+    }
+
+    async fn write(&self, path: &Path, content: &[u8]) -> tokio::io::Result<()> {
+        self.check_path(path)?;
+        let parent_exists = path.parent().is_some_and(|parent| {
+            matches!(
+                self.entries.lock().unwrap().get(parent),
+                Some(MemoryEntry::Folder)
+            )
+        });
+        if !parent_exists {
+            return Err(not_found(path));
+        }
+
+        self.insert_file(path.to_path_buf(), content.to_vec());
+        self.notify(path);
+        Ok(())
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> tokio::io::Result<()> {
+        self.check_path(to)?;
+        {
+            let mut entries = self.entries.lock().unwrap();
+            if !entries.contains_key(from) {
+                return Err(not_found(from));
+            }
+
+            let moved = entries
+                .keys()
+                .filter(|path| path.starts_with(from))
+                .cloned()
+                .collect::<Vec<PathBuf>>();
+            for path in moved {
+                let entry = entries.remove(&path).unwrap();
+                let relative_path = path.strip_prefix(from).unwrap();
+                let new_path = if relative_path.as_os_str().is_empty() {
+                    to.to_path_buf()
+                } else {
+                    to.join(relative_path)
+                };
+                entries.insert(new_path, entry);
+            }
+        }
+
+        self.notify(from);
+        self.notify(to);
+        Ok(())
+    }
+
+    async fn remove(&self, path: &Path) -> tokio::io::Result<()> {
+        {
+            let mut entries = self.entries.lock().unwrap();
+            if !entries.contains_key(path) || path == self.root {
+                return Err(not_found(path));
+            }
+            entries.retain(|entry_path, _| !entry_path.starts_with(path));
+        }
+
+        self.notify(path);
+        Ok(())
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> tokio::io::Result<()> {
+        self.check_path(path)?;
+        {
+            let mut entries = self.entries.lock().unwrap();
+            for ancestor in path.ancestors() {
+                if !ancestor.starts_with(&self.root) {
+                    break;
+                }
+                entries
+                    .entry(ancestor.to_path_buf())
+                    .or_insert(MemoryEntry::Folder);
+            }
+        }
+
+        self.notify(path);
+        Ok(())
+    }
+
+    async fn list(&self, path: &Path) -> tokio::io::Result<Vec<FSEntry>> {
+        let entries = self.entries.lock().unwrap();
+        if !matches!(entries.get(path), Some(MemoryEntry::Folder)) {
+            return Err(not_found(path));
+        }
+
+        Ok(entries
+            .iter()
+            .filter(|(entry_path, _)| entry_path.parent() == Some(path))
+            .map(|(entry_path, entry)| FSEntry {
+                path: entry_path.clone(),
+                is_dir: matches!(entry, MemoryEntry::Folder),
+            })
+            .collect())
+    }
+
+    async fn metadata(&self, path: &Path) -> tokio::io::Result<FSMetadata> {
+        match self.entries.lock().unwrap().get(path) {
+            Some(entry) => Ok(FSMetadata {
+                is_dir: matches!(entry, MemoryEntry::Folder),
+                read_only: false,
+                modified: None,
+            }),
+            None => Err(not_found(path)),
+        }
+    }
+
+    async fn canonicalize(&self, path: &Path) -> tokio::io::Result<PathBuf> {
+        if self.entries.lock().unwrap().contains_key(path) {
+            Ok(path.to_path_buf())
+        } else {
+            Err(not_found(path))
+        }
+    }
+
+    fn watch(&self, path: &Path, sender: UnboundedSender<PathBuf>) -> tokio::io::Result<FSWatcher> {
+        self.watchers
+            .lock()
+            .unwrap()
+            .push((path.to_path_buf(), sender));
+        Ok(Box::new(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+
+    fn memory(files: impl IntoIterator<Item = (&'static str, &'static str)>) -> FSMemory {
+        let files = files
+            .into_iter()
+            .map(|(path, content)| (PathBuf::from(path), content.as_bytes().to_vec()));
+        FSMemory::new(PathBuf::from("/project"), files)
+    }
+
+    fn paths(entries: Vec<FSEntry>) -> Vec<PathBuf> {
+        entries.into_iter().map(|entry| entry.path).collect()
+    }
+
+    #[tokio::test]
+    async fn write_and_read() {
+        let memory = memory([("/project/src/main.rs", "fn main() {}")]);
+        assert_eq!(
+            memory
+                .read_to_string(Path::new("/project/src/main.rs"))
+                .await
+                .unwrap(),
+            "fn main() {}"
+        );
+
+        memory
+            .write(Path::new("/project/src/main.rs"), b"fn main() {\n}")
+            .await
+            .unwrap();
+        assert_eq!(
+            memory
+                .read(Path::new("/project/src/main.rs"))
+                .await
+                .unwrap(),
+            b"fn main() {\n}"
+        );
+
+        // Folders are not created by writing
+        let err = memory
+            .write(Path::new("/project/tests/main.rs"), b"")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        memory
+            .create_dir_all(Path::new("/project/tests"))
+            .await
+            .unwrap();
+        memory
+            .write(Path::new("/project/tests/main.rs"), b"")
+            .await
+            .unwrap();
+
+        let err = memory.read(Path::new("/project/src")).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn paths_outside_of_the_root() {
+        let memory = memory([]);
+        let err = memory
+            .write(Path::new("/other/main.rs"), b"")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(memory.create_dir_all(Path::new("/other")).await.is_err());
+        assert!(memory.remove(Path::new("/project")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn list_and_metadata() {
+        let memory = memory([
+            ("/project/readme.md", ""),
+            ("/project/src/main.rs", ""),
+            ("/project/src/utils/mod.rs", ""),
+        ]);
+
+        let entries = memory.list(Path::new("/project/src")).await.unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                FSEntry {
+                    path: PathBuf::from("/project/src/main.rs"),
+                    is_dir: false,
+                },
+                FSEntry {
+                    path: PathBuf::from("/project/src/utils"),
+                    is_dir: true,
+                },
+            ]
+        );
+        assert!(memory.list(Path::new("/project/readme.md")).await.is_err());
+
+        let metadata = memory.metadata(Path::new("/project/src")).await.unwrap();
+        assert!(metadata.is_dir);
+        assert!(!metadata.read_only);
+        assert!(memory.metadata(Path::new("/project/lib.rs")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rename_and_remove_folders() {
+        let memory = memory([
+            ("/project/src/main.rs", "main"),
+            ("/project/src/utils/mod.rs", "utils"),
+        ]);
+
+        memory
+            .rename(Path::new("/project/src"), Path::new("/project/lib"))
+            .await
+            .unwrap();
+        assert_eq!(
+            paths(memory.list(Path::new("/project")).await.unwrap()),
+            vec![PathBuf::from("/project/lib")]
+        );
+        assert_eq!(
+            memory
+                .read_to_string(Path::new("/project/lib/utils/mod.rs"))
+                .await
+                .unwrap(),
+            "utils"
+        );
+
+        memory.remove(Path::new("/project/lib")).await.unwrap();
+        assert!(memory.list(Path::new("/project")).await.unwrap().is_empty());
+        assert!(memory
+            .read(Path::new("/project/lib/main.rs"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn watch_changes() {
+        let memory = memory([("/project/src/main.rs", "")]);
+        let (sender, mut receiver) = unbounded_channel();
+        let _watcher = memory.watch(Path::new("/project/src"), sender).unwrap();
+
+        memory
+            .write(Path::new("/project/src/main.rs"), b"fn main() {}")
+            .await
+            .unwrap();
+        memory
+            .write(Path::new("/project/readme.md"), b"")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            receiver.try_recv().ok(),
+            Some(PathBuf::from("/project/src/main.rs"))
+        );
+        // Changes outside of the watched folder are not sent
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,13 @@
+mod content;
+mod exclude;
+mod interface;
+mod local;
+mod memory;
+mod text_format;
+
+pub use content::*;
+pub use exclude::*;
+pub use interface::*;
+pub use local::*;
+pub use memory::*;
+pub use text_format::*;
@@ -0,0 +1,263 @@
+use std::{borrow::Cow, fmt::Display};
+
+/// How many bytes are inspected when guessing if a file is binary.
+const BINARY_SNIFF_LEN: usize = 8000;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Character encoding of a text file.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Utf8 => f.write_str("UTF-8"),
+            Self::Utf8Bom => f.write_str("UTF-8 with BOM"),
+            Self::Utf16Le => f.write_str("UTF-16 LE"),
+            Self::Utf16Be => f.write_str("UTF-16 BE"),
+            Self::Latin1 => f.write_str("Latin-1"),
+        }
+    }
+}
+
+impl Encoding {
+    pub const ALL: [Self; 5] = [
+        Self::Utf8,
+        Self::Utf8Bom,
+        Self::Utf16Le,
+        Self::Utf16Be,
+        Self::Latin1,
+    ];
+
+    /// Guess the encoding of some bytes and decode them, `None` if they don't look like text.
+    fn decode(bytes: &[u8]) -> Option<(String, Self)> {
+        if let Some(content) = bytes.strip_prefix(UTF8_BOM) {
+            let text = String::from_utf8(content.to_vec()).ok()?;
+            return Some((text, Self::Utf8Bom));
+        }
+        if let Some(content) = bytes.strip_prefix(UTF16_LE_BOM) {
+            return Some((decode_utf16(content, u16::from_le_bytes)?, Self::Utf16Le));
+        }
+        if let Some(content) = bytes.strip_prefix(UTF16_BE_BOM) {
+            return Some((decode_utf16(content, u16::from_be_bytes)?, Self::Utf16Be));
+        }
+
+        // Text files practically never contain NUL bytes
+        let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_LEN)];
+        if sniff.contains(&0) {
+            return None;
+        }
+
+        if let Ok(text) = std::str::from_utf8(bytes) {
+            return Some((text.to_string(), Self::Utf8));
+        }
+
+        // Anything else is Latin-1 unless it has control characters
+        let is_text = bytes
+            .iter()
+            .all(|byte| *byte >= 0x20 || matches!(byte, b'\t' | b'\n' | b'\r' | 0x0C | 0x1B));
+        if is_text {
+            return Some((
+                bytes.iter().map(|byte| *byte as char).collect(),
+                Self::Latin1,
+            ));
+        }
+
+        None
+    }
+
+    /// Encode a text, chars that don't exist in Latin-1 are replaced by `?`.
+    fn encode(&self, text: &str) -> Vec<u8> {
+        match self {
+            Self::Utf8 => text.as_bytes().to_vec(),
+            Self::Utf8Bom => [UTF8_BOM, text.as_bytes()].concat(),
+            Self::Utf16Le => {
+                let mut bytes = UTF16_LE_BOM.to_vec();
+                bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+                bytes
+            }
+            Self::Utf16Be => {
+                let mut bytes = UTF16_BE_BOM.to_vec();
+                bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+                bytes
+            }
+            Self::Latin1 => text
+                .chars()
+                .map(|ch| u8::try_from(ch).unwrap_or(b'?'))
+                .collect(),
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Option<String> {
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+
+    let units = bytes
+        .chunks_exact(2)
+        .map(|unit| from_bytes([unit[0], unit[1]]))
+        .collect::<Vec<u16>>();
+
+    String::from_utf16(&units).ok()
+}
+
+/// Line break used by a text file.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl Display for LineEnding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lf => f.write_str("LF"),
+            Self::Crlf => f.write_str("CRLF"),
+        }
+    }
+}
+
+impl LineEnding {
+    /// The most used line ending of a text.
+    fn detect(text: &str) -> Self {
+        let crlf = text.matches("\r\n").count();
+        let lf = text.matches('\n').count() - crlf;
+
+        if crlf > lf {
+            Self::Crlf
+        } else {
+            Self::Lf
+        }
+    }
+
+    /// A text with `\n` line endings, using this line ending instead.
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Lf => Cow::Borrowed(text),
+            Self::Crlf => Cow::Owned(text.replace('\n', "\r\n")),
+        }
+    }
+}
+
+/// Encoding and line ending of a text file, kept so it's saved the same way it was read.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct TextFormat {
+    pub encoding: Encoding,
+    pub line_ending: LineEnding,
+}
+
+impl TextFormat {
+    /// Decode the content of a text file, with its line endings normalized to `\n`.
+    pub fn decode(bytes: &[u8]) -> Option<(String, Self)> {
+        let (text, encoding) = Encoding::decode(bytes)?;
+        let line_ending = LineEnding::detect(&text);
+        let text = if text.contains('\r') {
+            text.replace("\r\n", "\n")
+        } else {
+            text
+        };
+
+        Some((
+            text,
+            Self {
+                encoding,
+                line_ending,
+            },
+        ))
+    }
+
+    /// Encode a text with `\n` line endings in this format.
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        self.encoding.encode(&self.line_ending.apply(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(bytes: &[u8]) -> (String, TextFormat) {
+        let (text, format) = TextFormat::decode(bytes).unwrap();
+        assert_eq!(format.encode(&text), bytes);
+        (text, format)
+    }
+
+    #[test]
+    fn encodings_round_trip() {
+        let text = "héllo\nwörld\n";
+        for encoding in Encoding::ALL {
+            let format = TextFormat {
+                encoding,
+                line_ending: LineEnding::Lf,
+            };
+            let bytes = format.encode(text);
+            assert_eq!(round_trip(&bytes), (text.to_string(), format), "{encoding}");
+        }
+    }
+
+    #[test]
+    fn detect_encodings() {
+        assert_eq!(round_trip(b"plain").1.encoding, Encoding::Utf8);
+        assert_eq!(round_trip(b"\xEF\xBB\xBFbom").1.encoding, Encoding::Utf8Bom);
+        assert_eq!(round_trip(b"\xFF\xFEa\0").1.encoding, Encoding::Utf16Le);
+        assert_eq!(round_trip(b"\xFE\xFF\0a").1.encoding, Encoding::Utf16Be);
+
+        let (text, format) = round_trip(b"caf\xE9");
+        assert_eq!(text, "café");
+        assert_eq!(format.encoding, Encoding::Latin1);
+    }
+
+    #[test]
+    fn detect_binary_files() {
+        assert_eq!(TextFormat::decode(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), None);
+        assert_eq!(TextFormat::decode(b"\xFF\xFEa"), None);
+        assert_eq!(TextFormat::decode(b"\x01\x02\xE9"), None);
+    }
+
+    #[test]
+    fn line_endings_round_trip() {
+        let (text, format) = round_trip(b"one\r\ntwo\r\n");
+        assert_eq!(text, "one\ntwo\n");
+        assert_eq!(format.line_ending, LineEnding::Crlf);
+
+        let (text, format) = round_trip(b"one\ntwo\n");
+        assert_eq!(text, "one\ntwo\n");
+        assert_eq!(format.line_ending, LineEnding::Lf);
+
+        let crlf_utf16 = TextFormat {
+            encoding: Encoding::Utf16Le,
+            line_ending: LineEnding::Crlf,
+        };
+        assert_eq!(round_trip(&crlf_utf16.encode("a\nb")).1, crlf_utf16);
+    }
+
+    #[test]
+    fn mixed_line_endings() {
+        // The most used one is kept, and every line is saved with it
+        let (text, format) = TextFormat::decode(b"one\r\ntwo\r\nthree\nfour").unwrap();
+        assert_eq!(text, "one\ntwo\nthree\nfour");
+        assert_eq!(format.line_ending, LineEnding::Crlf);
+        assert_eq!(format.encode(&text), b"one\r\ntwo\r\nthree\r\nfour");
+    }
+
+    #[test]
+    fn unsupported_latin1_chars() {
+        let format = TextFormat {
+            encoding: Encoding::Latin1,
+            line_ending: LineEnding::Lf,
+        };
+        assert_eq!(format.encode("ñ → ü"), b"\xF1 ? \xFC");
+    }
+}
@@ -0,0 +1,326 @@
+use std::{mem::size_of, ops::Range};
+
+use ropey::Rope;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HistoryChange {
+    InsertChar { idx: usize, char: char },
+    InsertText { idx: usize, text: String },
+    Remove { idx: usize, text: String },
+}
+
+impl HistoryChange {
+    /// Revert the change, returning where the cursor should be placed.
+    fn undo(&self, rope: &mut Rope, on_edit: &mut impl FnMut(&Rope, Range<usize>, &str)) -> usize {
+        match self {
+            Self::InsertChar { idx, .. } => {
+                on_edit(rope, *idx..*idx + 1, "");
+                rope.remove(*idx..*idx + 1);
+                *idx
+            }
+            Self::InsertText { idx, text } => {
+                on_edit(rope, *idx..*idx + text.chars().count(), "");
+                rope.remove(*idx..*idx + text.chars().count());
+                *idx
+            }
+            Self::Remove { idx, text } => {
+                on_edit(rope, *idx..*idx, text);
+                rope.insert(*idx, text);
+                *idx + text.chars().count()
+            }
+        }
+    }
+
+    /// Apply the change again, returning where the cursor should be placed.
+    fn redo(&self, rope: &mut Rope, on_edit: &mut impl FnMut(&Rope, Range<usize>, &str)) -> usize {
+        match self {
+            Self::InsertChar { idx, char } => {
+                on_edit(rope, *idx..*idx, char.encode_utf8(&mut [0; 4]));
+                rope.insert_char(*idx, *char);
+                *idx + 1
+            }
+            Self::InsertText { idx, text } => {
+                on_edit(rope, *idx..*idx, text);
+                rope.insert(*idx, text);
+                *idx + text.chars().count()
+            }
+            Self::Remove { idx, text } => {
+                on_edit(rope, *idx..*idx + text.chars().count(), "");
+                rope.remove(*idx..*idx + text.chars().count());
+                *idx
+            }
+        }
+    }
+}
+
+/// Undo history of an editor, where every entry is a group of changes
+/// that are undone and redone at once.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct EditorHistory {
+    entries: Vec<Vec<HistoryChange>>,
+    /// How many entries are currently applied.
+    current_change: usize,
+    /// Nesting level of the open transactions.
+    #[serde(skip)]
+    transaction_depth: usize,
+}
+
+impl EditorHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn current_change(&self) -> usize {
+        self.current_change
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.current_change > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.current_change < self.entries.len()
+    }
+
+    /// Bytes allocated for the list of changes, roughly.
+    pub fn heap_size(&self) -> usize {
+        self.entries.capacity() * size_of::<Vec<HistoryChange>>()
+            + self
+                .entries
+                .iter()
+                .map(|entry| entry.capacity() * size_of::<HistoryChange>())
+                .sum::<usize>()
+    }
+
+    /// Free the space left by undone changes and grown entries.
+    pub fn shrink_to_fit(&mut self) {
+        for entry in &mut self.entries {
+            entry.shrink_to_fit();
+        }
+        self.entries.shrink_to_fit();
+    }
+
+    pub fn push_change(&mut self, change: HistoryChange) {
+        if self.transaction_depth > 0 {
+            if let Some(entry) = self.entries.last_mut() {
+                entry.push(change);
+                return;
+            }
+        }
+
+        // Changes that were undone can't be redone anymore
+        self.entries.truncate(self.current_change);
+        self.entries.push(vec![change]);
+        self.current_change += 1;
+    }
+
+    /// Group all the changes until [`Self::end_transaction`] into a single entry.
+    pub fn begin_transaction(&mut self) {
+        if self.transaction_depth == 0 {
+            self.entries.truncate(self.current_change);
+            self.entries.push(Vec::new());
+            self.current_change += 1;
+        }
+        self.transaction_depth += 1;
+    }
+
+    pub fn end_transaction(&mut self) {
+        self.transaction_depth = self.transaction_depth.saturating_sub(1);
+
+        // Don't keep transactions that changed nothing
+        if self.transaction_depth == 0 && self.entries.last().map(Vec::is_empty) == Some(true) {
+            self.entries.pop();
+            self.current_change -= 1;
+        }
+    }
+
+    pub fn undo(&mut self, rope: &mut Rope) -> Option<usize> {
+        self.undo_with(rope, |_, _, _| {})
+    }
+
+    /// Like [`Self::undo`], calling `on_edit` with every range of the rope about to be replaced
+    /// and the text replacing it.
+    pub fn undo_with(
+        &mut self,
+        rope: &mut Rope,
+        mut on_edit: impl FnMut(&Rope, Range<usize>, &str),
+    ) -> Option<usize> {
+        if !self.can_undo() {
+            return None;
+        }
+
+        self.current_change -= 1;
+        let entry = &self.entries[self.current_change];
+
+        entry
+            .iter()
+            .rev()
+            .map(|change| change.undo(rope, &mut on_edit))
+            .last()
+    }
+
+    pub fn redo(&mut self, rope: &mut Rope) -> Option<usize> {
+        self.redo_with(rope, |_, _, _| {})
+    }
+
+    /// Like [`Self::redo`], calling `on_edit` with every range of the rope about to be replaced
+    /// and the text replacing it.
+    pub fn redo_with(
+        &mut self,
+        rope: &mut Rope,
+        mut on_edit: impl FnMut(&Rope, Range<usize>, &str),
+    ) -> Option<usize> {
+        if !self.can_redo() {
+            return None;
+        }
+
+        let entry = &self.entries[self.current_change];
+        self.current_change += 1;
+
+        entry
+            .iter()
+            .map(|change| change.redo(rope, &mut on_edit))
+            .last()
+    }
+
+    /// Undo or redo until the given amount of entries are applied.
+    pub fn rewind_to(&mut self, rope: &mut Rope, change: usize) {
+        while self.current_change > change && self.undo(rope).is_some() {}
+        while self.current_change < change && self.redo(rope).is_some() {}
+    }
+
+    /// Drop the oldest entries so there are at most `limit`.
+    pub fn truncate_oldest(&mut self, limit: usize) {
+        let dropped = self
+            .entries
+            .len()
+            .saturating_sub(limit)
+            .min(self.current_change);
+        self.entries.drain(..dropped);
+        self.current_change -= dropped;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert(history: &mut EditorHistory, rope: &mut Rope, idx: usize, text: &str) {
+        history.push_change(HistoryChange::InsertText {
+            idx,
+            text: text.to_string(),
+        });
+        rope.insert(idx, text);
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut rope = Rope::from_str("ac");
+        let mut history = EditorHistory::new();
+        history.push_change(HistoryChange::InsertChar { idx: 1, char: 'b' });
+        rope.insert_char(1, 'b');
+        history.push_change(HistoryChange::Remove {
+            idx: 0,
+            text: "a".to_string(),
+        });
+        rope.remove(0..1);
+        assert_eq!(rope, "bc");
+
+        assert_eq!(history.undo(&mut rope), Some(1));
+        assert_eq!(rope, "abc");
+        assert_eq!(history.undo(&mut rope), Some(1));
+        assert_eq!(rope, "ac");
+        assert_eq!(history.undo(&mut rope), None);
+
+        assert_eq!(history.redo(&mut rope), Some(2));
+        assert_eq!(history.redo(&mut rope), Some(0));
+        assert_eq!(history.redo(&mut rope), None);
+        assert_eq!(rope, "bc");
+    }
+
+    #[test]
+    fn new_changes_drop_the_undone_ones() {
+        let mut rope = Rope::new();
+        let mut history = EditorHistory::new();
+        insert(&mut history, &mut rope, 0, "one");
+        insert(&mut history, &mut rope, 3, " two");
+        history.undo(&mut rope);
+        insert(&mut history, &mut rope, 3, " three");
+
+        assert_eq!(history.current_change(), 2);
+        assert!(!history.can_redo());
+        history.undo(&mut rope);
+        history.undo(&mut rope);
+        assert_eq!(rope, "");
+    }
+
+    #[test]
+    fn transactions() {
+        let mut rope = Rope::new();
+        let mut history = EditorHistory::new();
+        history.begin_transaction();
+        insert(&mut history, &mut rope, 0, "b");
+        // Nested transactions are part of the outer one
+        history.begin_transaction();
+        insert(&mut history, &mut rope, 0, "a");
+        history.end_transaction();
+        insert(&mut history, &mut rope, 2, "c");
+        history.end_transaction();
+        assert_eq!(history.current_change(), 1);
+
+        // Transactions that changed nothing are dropped
+        history.begin_transaction();
+        history.end_transaction();
+        assert_eq!(history.current_change(), 1);
+
+        assert_eq!(rope, "abc");
+        assert_eq!(history.undo(&mut rope), Some(0));
+        assert_eq!(rope, "");
+        assert_eq!(history.redo(&mut rope), Some(3));
+        assert_eq!(rope, "abc");
+    }
+
+    #[test]
+    fn undo_with_edits() {
+        let mut rope = Rope::new();
+        let mut history = EditorHistory::new();
+        insert(&mut history, &mut rope, 0, "hi");
+
+        let mut edits = Vec::new();
+        history.undo_with(&mut rope, |_, range, text| {
+            edits.push((range, text.to_string()))
+        });
+        history.redo_with(&mut rope, |_, range, text| {
+            edits.push((range, text.to_string()))
+        });
+        assert_eq!(edits, [(0..2, String::new()), (0..0, "hi".to_string())]);
+    }
+
+    #[test]
+    fn rewind_and_truncate() {
+        let mut rope = Rope::new();
+        let mut history = EditorHistory::new();
+        for (i, text) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            insert(&mut history, &mut rope, i, text);
+        }
+
+        history.rewind_to(&mut rope, 1);
+        assert_eq!(rope, "a");
+        history.rewind_to(&mut rope, 3);
+        assert_eq!(rope, "abc");
+
+        history.truncate_oldest(2);
+        assert_eq!(history.current_change(), 1);
+        assert_eq!(history.undo(&mut rope), Some(2));
+        assert_eq!(history.undo(&mut rope), None);
+        assert_eq!(rope, "ab");
+
+        // Undone entries are kept even if there are too many
+        history.truncate_oldest(1);
+        assert_eq!(history.current_change(), 0);
+        assert!(history.can_redo());
+        history.rewind_to(&mut rope, 2);
+        assert_eq!(rope, "abcd");
+    }
+}
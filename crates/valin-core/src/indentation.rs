@@ -0,0 +1,108 @@
+use std::fmt::Display;
+
+use ropey::Rope;
+
+/// How many lines are looked at when detecting the indentation of a file.
+const DETECTION_LINES: usize = 1000;
+
+/// Indentation used by an editor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Indentation {
+    pub with_tabs: bool,
+    /// Columns of an indentation level.
+    pub width: u8,
+}
+
+impl Default for Indentation {
+    fn default() -> Self {
+        Self {
+            with_tabs: false,
+            width: 4,
+        }
+    }
+}
+
+impl Display for Indentation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.with_tabs {
+            write!(f, "Tab Size: {}", self.width)
+        } else {
+            write!(f, "Spaces: {}", self.width)
+        }
+    }
+}
+
+impl Indentation {
+    /// Text of one indentation level.
+    pub fn unit(&self) -> String {
+        if self.with_tabs {
+            "\t".to_string()
+        } else {
+            " ".repeat(self.width as usize)
+        }
+    }
+
+    /// Columns taken by the given leading whitespace.
+    pub fn columns(&self, whitespace: &str) -> usize {
+        whitespace.chars().fold(0, |columns, ch| {
+            if ch == '\t' {
+                columns + self.width as usize - columns % self.width as usize
+            } else {
+                columns + 1
+            }
+        })
+    }
+
+    /// Guess the indentation of a text by the most used difference between the
+    /// indentation of consecutive lines, or use `fallback` if nothing is indented.
+    pub fn detect(rope: &Rope, fallback: Self) -> Self {
+        let mut tab_lines = 0;
+        let mut space_lines = 0;
+        let mut widths = [0usize; 9];
+        let mut previous_spaces = 0;
+
+        for line in rope.lines().take(DETECTION_LINES) {
+            // Blank lines say nothing about the indentation
+            if line.chars().all(char::is_whitespace) {
+                continue;
+            }
+
+            if line.chars().next() == Some('\t') {
+                tab_lines += 1;
+                continue;
+            }
+
+            let spaces = line.chars().take_while(|ch| *ch == ' ').count();
+            if spaces > 0 {
+                space_lines += 1;
+            }
+
+            let width = spaces.abs_diff(previous_spaces);
+            if (2..widths.len()).contains(&width) {
+                widths[width] += 1;
+            }
+            previous_spaces = spaces;
+        }
+
+        if tab_lines > space_lines {
+            return Self {
+                with_tabs: true,
+                width: fallback.width,
+            };
+        }
+
+        // Ties go to the narrowest width
+        let width = (2..widths.len())
+            .rev()
+            .max_by_key(|width| widths[*width])
+            .filter(|width| widths[*width] > 0);
+
+        match width {
+            Some(width) if space_lines > 0 => Self {
+                with_tabs: false,
+                width: width as u8,
+            },
+            _ => fallback,
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::{fmt::Display, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Debug, PartialEq, Copy)]
+pub enum LanguageId {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Markdown,
+    Http,
+    #[default]
+    Unknown,
+}
+
+impl Display for LanguageId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rust => f.write_str("Rust"),
+            Self::Python => f.write_str("Python"),
+            Self::JavaScript => f.write_str("JavaScript"),
+            Self::TypeScript => f.write_str("TypeScript"),
+            Self::Markdown => f.write_str("Markdown"),
+            Self::Http => f.write_str("HTTP"),
+            Self::Unknown => f.write_str("Unknown"),
+        }
+    }
+}
+
+impl LanguageId {
+    pub fn parse(id: &str) -> Self {
+        match id {
+            "rs" => LanguageId::Rust,
+            "py" => LanguageId::Python,
+            "js" => LanguageId::JavaScript,
+            "ts" => LanguageId::TypeScript,
+            "md" => LanguageId::Markdown,
+            "http" | "rest" => LanguageId::Http,
+            _ => LanguageId::Unknown,
+        }
+    }
+
+    pub fn language_server(&self) -> Option<&str> {
+        match self {
+            LanguageId::Rust => Some("rust-analyzer"),
+            _ => None,
+        }
+    }
+}
+
+/// Syntax of a language the editor needs to know about: how its code is commented,
+/// which brackets and string delimiters it pairs and which characters open an indented block.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LanguageConfig {
+    #[serde(default)]
+    pub line_comment: Option<String>,
+    /// Opening and closing tokens of block comments.
+    #[serde(default)]
+    pub block_comment: Option<(String, String)>,
+    /// Opening and closing brackets.
+    #[serde(default = "default_brackets")]
+    pub brackets: Vec<(char, char)>,
+    #[serde(default = "default_string_delimiters")]
+    pub string_delimiters: Vec<char>,
+    /// Lines ending with one of these are followed by a more indented line.
+    #[serde(default = "default_indent_triggers")]
+    pub indent_triggers: Vec<char>,
+}
+
+fn default_brackets() -> Vec<(char, char)> {
+    vec![('(', ')'), ('[', ']'), ('{', '}')]
+}
+
+fn default_string_delimiters() -> Vec<char> {
+    vec!['"']
+}
+
+fn default_indent_triggers() -> Vec<char> {
+    vec!['{', '[', '(']
+}
+
+impl Default for LanguageConfig {
+    fn default() -> Self {
+        Self {
+            line_comment: None,
+            block_comment: None,
+            brackets: default_brackets(),
+            string_delimiters: default_string_delimiters(),
+            indent_triggers: default_indent_triggers(),
+        }
+    }
+}
+
+impl LanguageConfig {
+    /// Built-in definition of a language.
+    pub fn for_language(language_id: LanguageId) -> Self {
+        let c_like_comments = Self {
+            line_comment: Some("//".to_string()),
+            block_comment: Some(("/*".to_string(), "*/".to_string())),
+            ..Self::default()
+        };
+
+        match language_id {
+            LanguageId::Rust => c_like_comments,
+            LanguageId::JavaScript | LanguageId::TypeScript => Self {
+                string_delimiters: vec!['"', '\'', '`'],
+                ..c_like_comments
+            },
+            LanguageId::Python => Self {
+                line_comment: Some("#".to_string()),
+                string_delimiters: vec!['"', '\''],
+                indent_triggers: vec!['{', '[', '(', ':'],
+                ..Self::default()
+            },
+            LanguageId::Http => Self {
+                line_comment: Some("#".to_string()),
+                ..Self::default()
+            },
+            LanguageId::Markdown => Self {
+                block_comment: Some(("<!--".to_string(), "-->".to_string())),
+                ..Self::default()
+            },
+            LanguageId::Unknown => Self::default(),
+        }
+    }
+
+    /// Tokens to comment a line out with, and to close it for languages
+    /// that only have block comments.
+    pub fn line_comment_tokens(&self) -> Option<(&str, Option<&str>)> {
+        match (&self.line_comment, &self.block_comment) {
+            (Some(line), _) => Some((line, None)),
+            (None, Some((open, close))) => Some((open, Some(close))),
+            (None, None) => None,
+        }
+    }
+
+    pub fn is_opening_bracket(&self, ch: char) -> bool {
+        self.brackets.iter().any(|(open, _)| *open == ch)
+    }
+
+    pub fn is_closing_bracket(&self, ch: char) -> bool {
+        self.brackets.iter().any(|(_, close)| *close == ch)
+    }
+
+    /// Whether a line ending like the given text is followed by a more indented line.
+    pub fn triggers_indent(&self, line: &str) -> bool {
+        line.trim_end()
+            .chars()
+            .last()
+            .is_some_and(|ch| self.indent_triggers.contains(&ch))
+    }
+}
+
+/// Language added in the settings, for the files with any of its extensions.
+/// They take precedence over the built-in definitions.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LanguageSettings {
+    pub extensions: Vec<String>,
+    #[serde(flatten)]
+    pub config: LanguageConfig,
+}
+
+/// Definition of the language of a file, from the settings or the built-in ones.
+pub fn language_config(path: &Path, languages: &[LanguageSettings]) -> LanguageConfig {
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return LanguageConfig::default();
+    };
+
+    languages
+        .iter()
+        .find(|language| language.extensions.iter().any(|ext| ext == extension))
+        .map(|language| language.config.clone())
+        .unwrap_or_else(|| LanguageConfig::for_language(LanguageId::parse(extension)))
+}
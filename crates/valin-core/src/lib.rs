@@ -0,0 +1,9 @@
+//! Editing logic of Valin without any UI: file transports, undo history, syntax blocks
+//! and language definitions. The editor is built on top of it, and so can other frontends.
+
+pub mod document_stats;
+pub mod fs;
+pub mod history;
+pub mod indentation;
+pub mod language;
+pub mod parser;
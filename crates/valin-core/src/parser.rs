@@ -1,8 +1,10 @@
-use std::{borrow::Cow, ops::Range};
+use std::{borrow::Cow, mem::size_of, ops::Range};
 
 use ropey::Rope;
 use smallvec::SmallVec;
 
+use crate::language::LanguageConfig;
+
 const LARGE_FILE: usize = 45_000_000;
 
 #[derive(Clone, Debug)]
@@ -17,8 +19,17 @@ pub enum SyntaxType {
     Module,
     Comment,
     SpaceMark,
+    /// Opening or closing bracket, with how many brackets enclose it.
+    Bracket(usize),
 }
 
+/// Colors of the nested brackets, reused in order.
+const BRACKET_COLORS: [&str; 3] = [
+    "rgb(252, 188, 61)",
+    "rgb(211, 134, 155)",
+    "rgb(90, 160, 215)",
+];
+
 impl SyntaxType {
     pub fn color(&self) -> &str {
         match self {
@@ -32,6 +43,15 @@ impl SyntaxType {
             SyntaxType::SpecialKeyword => "rgb(211, 134, 155)",
             SyntaxType::Comment => "gray",
             SyntaxType::SpaceMark => "rgb(223, 191, 142, 0.2)",
+            SyntaxType::Bracket(_) => SyntaxType::Punctuation2.color(),
+        }
+    }
+
+    /// Like [`SyntaxType::color`], but brackets are colored by how nested they are.
+    pub fn rainbow_color(&self) -> &str {
+        match self {
+            SyntaxType::Bracket(depth) => BRACKET_COLORS[depth % BRACKET_COLORS.len()],
+            _ => self.color(),
         }
     }
 }
@@ -61,6 +81,8 @@ pub type SyntaxLine = SmallVec<[(SyntaxType, TextNode); 4]>;
 #[derive(Default)]
 pub struct SyntaxBlocks {
     blocks: Vec<SyntaxLine>,
+    /// Leading whitespace characters of every line, blank lines take the one of the block they are in.
+    indents: Vec<usize>,
 }
 
 impl SyntaxBlocks {
@@ -72,12 +94,73 @@ impl SyntaxBlocks {
         &self.blocks[line]
     }
 
+    /// Leading whitespace characters of a line, used to draw its indent guides.
+    pub fn get_indent(&self, line: usize) -> usize {
+        self.indents.get(line).copied().unwrap_or_default()
+    }
+
     pub fn len(&self) -> usize {
         self.blocks.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
     pub fn clear(&mut self) {
         self.blocks.clear();
+        self.indents.clear();
+    }
+
+    /// Bytes allocated for the lines, roughly.
+    pub fn heap_size(&self) -> usize {
+        let spilled_lines = self
+            .blocks
+            .iter()
+            .filter(|line| line.spilled())
+            .map(|line| line.capacity() * size_of::<(SyntaxType, TextNode)>())
+            .sum::<usize>();
+        self.blocks.capacity() * size_of::<SyntaxLine>()
+            + spilled_lines
+            + self.indents.capacity() * size_of::<usize>()
+    }
+
+    /// Free the space left by longer versions of the text.
+    pub fn shrink_to_fit(&mut self) {
+        for line in &mut self.blocks {
+            line.shrink_to_fit();
+        }
+        self.blocks.shrink_to_fit();
+        self.indents.shrink_to_fit();
+    }
+
+    /// Measure the indentation of every line.
+    fn measure_indents(&mut self, rope: &Rope) {
+        let mut previous_indent = 0;
+        let mut blank_lines = 0;
+
+        for line in rope.lines().take(self.blocks.len()) {
+            let indent = line
+                .chars()
+                .take_while(|ch| *ch == ' ' || *ch == '\t')
+                .count();
+            let is_blank = line.chars().all(char::is_whitespace);
+
+            if is_blank {
+                blank_lines += 1;
+                continue;
+            }
+
+            // Blank lines stay inside the block surrounding them
+            let blank_indent = previous_indent.min(indent);
+            self.indents
+                .resize(self.indents.len() + blank_lines, blank_indent);
+            self.indents.push(indent);
+            previous_indent = indent;
+            blank_lines = 0;
+        }
+
+        self.indents.resize(self.blocks.len(), 0);
     }
 }
 
@@ -166,7 +249,7 @@ fn flush_spaces_stack(
     }
 }
 
-pub fn parse(rope: &Rope, syntax_blocks: &mut SyntaxBlocks) {
+pub fn parse(rope: &Rope, syntax_blocks: &mut SyntaxBlocks, language: &LanguageConfig) {
     // Clear any blocks from before
     syntax_blocks.clear();
 
@@ -184,11 +267,15 @@ pub fn parse(rope: &Rope, syntax_blocks: &mut SyntaxBlocks) {
     // Track comments
     let mut tracking_comment = CommentTracking::None;
     let mut comment_stack: Option<Range<usize>> = None;
+    let mut block_comment_start = 0;
 
-    // Track strings
-    let mut tracking_string = false;
+    // Track strings, by the delimiter they started with
+    let mut tracking_string: Option<char> = None;
     let mut string_stack: Option<Range<usize>> = None;
 
+    // Track how nested the brackets are
+    let mut bracket_depth = 0;
+
     // Track anything else
     let mut generic_stack: Option<Range<usize>> = None;
     let mut last_semantic = SyntaxSemantic::Unknown;
@@ -219,7 +306,7 @@ pub fn parse(rope: &Rope, syntax_blocks: &mut SyntaxBlocks) {
         }
 
         // Stop tracking a string
-        if tracking_string && ch == '"' {
+        if tracking_string == Some(ch) {
             flush_generic_stack(rope, &mut generic_stack, &mut line, &mut last_semantic, ch);
 
             let mut st = string_stack.take().unwrap_or_default();
@@ -227,37 +314,47 @@ pub fn parse(rope: &Rope, syntax_blocks: &mut SyntaxBlocks) {
 
             // Strings
             line.push((SyntaxType::String, TextNode::Range(st)));
-            tracking_string = false;
-        }
-        // Start tracking a string
-        else if tracking_comment == CommentTracking::None && ch == '"' {
-            string_stack = Some(i..i + 1);
-            tracking_string = true;
+            tracking_string = None;
         }
         // While tracking a comment
         else if tracking_comment != CommentTracking::None {
-            if let Some(ct) = comment_stack.as_mut() {
-                ct.end = i + 1;
-
-                let current_comment: Cow<str> = rope.slice(ct.clone()).into();
-
-                // Stop a multi line comment
-                if ch == '/' && current_comment.ends_with("*/") {
-                    generic_stack.take();
-                    line.push((
-                        SyntaxType::Comment,
-                        TextNode::Range(comment_stack.take().unwrap()),
-                    ));
-                    tracking_comment = CommentTracking::None;
+            let ct = comment_stack.get_or_insert(i..i);
+            ct.end = i + 1;
+
+            // Stop a multi line comment, its closing token can't overlap the opening one
+            if tracking_comment == CommentTracking::MultiLine {
+                if let Some((open, close)) = &language.block_comment {
+                    let close_len = close.chars().count();
+                    if i + 1 >= block_comment_start + open.chars().count() + close_len
+                        && starts_with_at(rope, i + 1 - close_len, close)
+                    {
+                        generic_stack.take();
+                        line.push((
+                            SyntaxType::Comment,
+                            TextNode::Range(comment_stack.take().unwrap()),
+                        ));
+                        tracking_comment = CommentTracking::None;
+                    }
                 }
-            } else {
-                comment_stack = Some(i..i + 1);
             }
         }
         // While tracking a string
-        else if tracking_string {
+        else if tracking_string.is_some() {
             push_to_stack(&mut string_stack, i);
         }
+        // Start tracking a string
+        else if language.string_delimiters.contains(&ch) {
+            string_stack = Some(i..i + 1);
+            tracking_string = Some(ch);
+        }
+        // Start tracking a comment (both one line and multine)
+        else if let Some(comment_tracking) = comment_start(rope, i, ch, language) {
+            flush_generic_stack(rope, &mut generic_stack, &mut line, &mut last_semantic, ch);
+
+            comment_stack = Some(i..i + 1);
+            block_comment_start = i;
+            tracking_comment = comment_tracking;
+        }
         // If is a special character
         else if SPECIAL_CHARACTER.contains(&ch) {
             flush_generic_stack(rope, &mut generic_stack, &mut line, &mut last_semantic, ch);
@@ -277,29 +374,27 @@ pub fn parse(rope: &Rope, syntax_blocks: &mut SyntaxBlocks) {
                 last_semantic = SyntaxSemantic::PropertyAccess;
             }
 
+            // Brackets
+            if language.is_opening_bracket(ch) {
+                line.push((
+                    SyntaxType::Bracket(bracket_depth),
+                    TextNode::Range(i..i + 1),
+                ));
+                bracket_depth += 1;
+            } else if language.is_closing_bracket(ch) {
+                bracket_depth = bracket_depth.saturating_sub(1);
+                line.push((
+                    SyntaxType::Bracket(bracket_depth),
+                    TextNode::Range(i..i + 1),
+                ));
+            }
             // Punctuation
-            line.push((SyntaxType::Punctuation2, TextNode::Range(i..i + 1)));
+            else {
+                line.push((SyntaxType::Punctuation2, TextNode::Range(i..i + 1)));
+            }
         }
         // Unknown (for now at least) characters
         else {
-            // Start tracking a comment (both one line and multine)
-            if tracking_comment == CommentTracking::None && (ch == '*' || ch == '/') {
-                if let Some(us) = generic_stack.as_mut() {
-                    let generic_stack_text: Cow<str> = rope.slice(us.clone()).into();
-                    if generic_stack_text == "/" {
-                        comment_stack = generic_stack.take();
-
-                        push_to_stack(&mut comment_stack, i);
-
-                        if ch == '*' {
-                            tracking_comment = CommentTracking::MultiLine
-                        } else if ch == '/' {
-                            tracking_comment = CommentTracking::OneLine
-                        }
-                    }
-                }
-            }
-
             // Flush the generic stack before adding the space
             if ch.is_whitespace() {
                 flush_generic_stack(rope, &mut generic_stack, &mut line, &mut last_semantic, ch);
@@ -338,6 +433,35 @@ pub fn parse(rope: &Rope, syntax_blocks: &mut SyntaxBlocks) {
             begining_of_line = true;
         }
     }
+
+    syntax_blocks.measure_indents(rope);
+}
+
+/// Kind of comment starting at the given position, if any.
+fn comment_start(
+    rope: &Rope,
+    pos: usize,
+    ch: char,
+    language: &LanguageConfig,
+) -> Option<CommentTracking> {
+    // Block comments first, their opening token might start like the line one
+    if let Some((open, _)) = &language.block_comment {
+        if open.starts_with(ch) && starts_with_at(rope, pos, open) {
+            return Some(CommentTracking::MultiLine);
+        }
+    }
+    if let Some(line_comment) = &language.line_comment {
+        if line_comment.starts_with(ch) && starts_with_at(rope, pos, line_comment) {
+            return Some(CommentTracking::OneLine);
+        }
+    }
+    None
+}
+
+/// Whether the text at the given position starts with the token.
+fn starts_with_at(rope: &Rope, pos: usize, token: &str) -> bool {
+    let mut chars = rope.chars_at(pos);
+    !token.is_empty() && token.chars().all(|ch| chars.next() == Some(ch))
 }
 
 // Push if exists otherwise create the stack
@@ -6,17 +6,27 @@ use crate::{
     tabs::welcome::WelcomeTab,
 };
 use crate::{global_defaults::GlobalDefaults, state::KeyboardShortcuts};
-use crate::{hooks::*, settings::watch_settings};
+use crate::{
+    hooks::*,
+    settings::{notify_invalid_settings, notify_unavoidable_ligatures, watch_settings},
+    symbols::index_folder,
+    tasks::load_tasks,
+};
 use crate::{tabs::editor::EditorTab, utils::*};
 use dioxus_radio::prelude::*;
 use dioxus_sdk::clipboard::use_clipboard;
 use freya::prelude::*;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::info;
 
-use crate::state::{AppState, Channel};
+use crate::notifications::notify;
+use crate::state::{AppState, Channel, NotificationLevel};
 use crate::state::{EditorSidePanel, EditorView};
 
+/// Width of the focused panel in zen mode, as a percentage of the window.
+const ZEN_MODE_WIDTH: f32 = 70.0;
+
 #[allow(non_snake_case)]
 pub fn App() -> Element {
     // Initialize the Language Server Status reporters
@@ -30,7 +40,7 @@ pub fn App() -> Element {
         let args = consume_context::<Arc<Args>>();
         let default_transport: FSTransport = Arc::new(Box::new(FSLocal));
 
-        let mut app_state = AppState::new(lsp_sender, default_transport, clipboard);
+        let mut app_state = AppState::new(lsp_sender, default_transport, clipboard, args.clean);
 
         if args.paths.is_empty() {
             // Default tab
@@ -43,6 +53,18 @@ pub fn App() -> Element {
     // Subscribe to the State Manager
     let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
 
+    // Save the editors when they lose the focus, if enabled
+    use_autosave(radio_app_state);
+
+    // Back up the unsaved changes, and offer to restore the ones of a previous session
+    use_backups(radio_app_state);
+
+    // Reveal the focused file in the files explorer, if enabled
+    use_follow_mode(radio_app_state);
+
+    // Compact the editors while idle
+    let mut last_activity = use_idle_maintenance(radio_app_state);
+
     // Load specified files and folders asynchronously
     use_hook(move || {
         let args = consume_context::<Arc<Args>>();
@@ -53,11 +75,12 @@ pub fn App() -> Element {
                     let root_path = path.parent().unwrap_or(path).to_path_buf();
                     let transport = radio_app_state.read().default_transport.clone();
 
-                    let content = transport.read_to_string(path).await;
+                    let content = transport.read(path).await;
                     if let Ok(content) = content {
+                        let read_only = is_read_only(&transport, path).await;
                         let mut app_state = radio_app_state.write();
 
-                        EditorTab::open_with(&mut app_state, path.clone(), root_path, content);
+                        open_file(&mut app_state, path.clone(), root_path, content, read_only);
                     }
                 }
                 // Folders
@@ -69,13 +92,21 @@ pub fn App() -> Element {
                         .await
                         .unwrap();
 
-                    let items =
-                        read_folder_as_items(&folder_path, &app_state.default_transport).await;
+                    let exclude_matcher = app_state.exclude_matcher(&folder_path);
+                    let items = read_folder_as_items(
+                        &folder_path,
+                        &app_state.default_transport,
+                        &exclude_matcher,
+                    )
+                    .await;
                     if let Ok(items) = items {
                         app_state.open_folder(ExplorerItem::Folder {
-                            path: folder_path,
+                            path: folder_path.clone(),
                             state: FolderState::Opened(items),
                         });
+                        spawn(index_folder(radio_app_state, folder_path.clone()));
+                        spawn(load_tasks(radio_app_state, folder_path.clone()));
+                        spawn(watch_folder(radio_app_state, folder_path));
                     }
                 }
             }
@@ -83,12 +114,47 @@ pub fn App() -> Element {
     });
 
     use_hook(|| {
+        // Clean sessions keep the default settings
+        if radio_app_state.read().clean_session {
+            spawn(async move {
+                notify(
+                    radio_app_state,
+                    NotificationLevel::Info,
+                    "Clean session: default settings and layout, nothing restored from previous sessions",
+                    None,
+                );
+            });
+            return;
+        }
+
+        // Boot with the default settings if they are invalid, but tell where the error is
+        let invalid_settings = radio_app_state.read().invalid_settings.clone();
+        if let Some(invalid_settings) = invalid_settings {
+            spawn(async move {
+                notify_invalid_settings(radio_app_state, &invalid_settings);
+            });
+        }
+
+        // Ligatures were turned off for a font that can't render without them
+        let font_family = {
+            let app_state = radio_app_state.read();
+            let editor_settings = &app_state.settings.editor;
+            editor_settings
+                .has_unavoidable_ligatures()
+                .then(|| editor_settings.font_family.clone())
+        };
+        if let Some(font_family) = font_family {
+            spawn(async move {
+                notify_unavoidable_ligatures(radio_app_state, &font_family);
+            });
+        }
+
         spawn(async move {
             let res = watch_settings(radio_app_state).await;
             if res.is_none() {
                 info!("Failed to watch the settings in background.");
             }
-        })
+        });
     });
 
     // Initialize the Commands
@@ -98,14 +164,15 @@ pub fn App() -> Element {
     let mut keyboard_shorcuts = use_hook(|| Signal::new(KeyboardShortcuts::default()));
 
     // Register Commands and Shortcuts
+    // The EditorTab shortcuts go first so they can take over global keys (e.g `Esc`) while in a special mode
     #[allow(clippy::explicit_auto_deref)]
     use_hook(|| {
-        GlobalDefaults::init(
+        EditorTab::init(
             &mut *keyboard_shorcuts.write(),
             &mut *editor_commands.write(),
             radio_app_state,
         );
-        EditorTab::init(
+        GlobalDefaults::init(
             &mut *keyboard_shorcuts.write(),
             &mut *editor_commands.write(),
             radio_app_state,
@@ -115,14 +182,29 @@ pub fn App() -> Element {
     // Trigger Shortcuts
     #[allow(clippy::explicit_auto_deref)]
     let onkeydown = move |e: KeyboardEvent| {
+        last_activity.set(Instant::now());
         keyboard_shorcuts
             .write()
             .run(&e.data, &mut *editor_commands.write(), radio_app_state);
     };
 
     let focused_view = radio_app_state.read().focused_view;
-    let panels_len = radio_app_state.read().panels().len();
-    let panes_width = 100.0 / panels_len as f32;
+    let commander_pinned = radio_app_state.read().commander_pinned;
+    let panel_columns = radio_app_state.read().panel_columns();
+    let panes_width = 100.0 / panel_columns.len() as f32;
+    let layout = radio_app_state.read().layout;
+    let focused_panel = radio_app_state.read().focused_panel();
+    let toasts = radio_app_state.read().notifications.toasts();
+    let is_notification_history_open = radio_app_state.read().notifications.is_history_open();
+    // Space taken by the output panel and the status bar, dividers included
+    let mut reserved_height = 0.0;
+    if layout.is_output_panel_visible() {
+        reserved_height += OUTPUT_PANEL_HEIGHT + 1.0;
+    }
+    if layout.is_status_bar_visible() {
+        reserved_height += 35.0;
+    }
+    let panels_height = format!("calc(100% - {reserved_height})");
 
     rsx!(
         rect {
@@ -132,15 +214,15 @@ pub fn App() -> Element {
             width: "100%",
             height: "100%",
             onkeydown: onkeydown,
-            if focused_view == EditorView::Commander {
+            if focused_view == EditorView::Commander && !commander_pinned {
                 Commander {
                     editor_commands
                 }
             }
             rect {
-                height: "calc(100% - 35)",
+                height: "{panels_height}",
                 direction: "horizontal",
-                if let Some(side_panel) = radio_app_state.read().side_panel {
+                if let Some(side_panel) = layout.visible_side_panel() {
                     Sidepanel {
                         match side_panel {
                             EditorSidePanel::FileExplorer => {
@@ -148,6 +230,26 @@ pub fn App() -> Element {
                                     FileExplorer {  }
                                 )
                             }
+                            EditorSidePanel::Search => {
+                                rsx!(
+                                    SearchPanel {  }
+                                )
+                            }
+                            EditorSidePanel::WorkingSets => {
+                                rsx!(
+                                    WorkingSetsPanel {  }
+                                )
+                            }
+                            EditorSidePanel::Processes => {
+                                rsx!(
+                                    ProcessesPanel {  }
+                                )
+                            }
+                            EditorSidePanel::Bookmarks => {
+                                rsx!(
+                                    BookmarksPanel {  }
+                                )
+                            }
                         }
                     }
                     Divider {}
@@ -155,22 +257,74 @@ pub fn App() -> Element {
                 rect {
                     width: "fill",
                     height: "fill",
-                    direction: "horizontal",
-                    {radio_app_state.read().panels().iter().enumerate().map(|(panel_index, _)| {
-                        rsx!(
-                            EditorPanel {
-                                key: "{panel_index}",
-                                panel_index: panel_index,
-                                width: format!("{panes_width}%")
+                    if commander_pinned {
+                        Commander {
+                            editor_commands,
+                            pinned: true
+                        }
+                        VerticalDivider {}
+                    }
+                    rect {
+                        width: "100%",
+                        height: "fill",
+                        direction: "horizontal",
+                        if layout.zen_mode {
+                            // Only the focused panel, centered
+                            rect {
+                                width: "100%",
+                                height: "100%",
+                                main_align: "center",
+                                direction: "horizontal",
+                                EditorPanel {
+                                    key: "{focused_panel}",
+                                    panel_index: focused_panel,
+                                    width: "{ZEN_MODE_WIDTH}%",
+                                    height: "100%"
+                                }
                             }
-                        )
-                    })}
+                        } else {
+                            {panel_columns.into_iter().map(|column| {
+                                let panes_height = 100.0 / column.len() as f32;
+                                rsx!(
+                                    rect {
+                                        key: "{column.start}",
+                                        width: "{panes_width}%",
+                                        height: "100%",
+                                        {column.map(|panel_index| {
+                                            rsx!(
+                                                EditorPanel {
+                                                    key: "{panel_index}",
+                                                    panel_index: panel_index,
+                                                    width: "100%",
+                                                    height: format!("{panes_height}%")
+                                                }
+                                            )
+                                        })}
+                                    }
+                                )
+                            })}
+                        }
+                    }
                 }
             }
-            VerticalDivider {}
-            StatusBar {
-                lsp_statuses,
-                focused_view
+            if !toasts.is_empty() {
+                ToastsView {
+                    toasts
+                }
+            }
+            if is_notification_history_open {
+                NotificationHistory {}
+            }
+            if layout.is_output_panel_visible() {
+                VerticalDivider {}
+                OutputPanel {}
+            }
+            if layout.is_status_bar_visible() {
+                VerticalDivider {}
+                StatusBar {
+                    lsp_statuses,
+                    focused_view
+                }
             }
         }
     )
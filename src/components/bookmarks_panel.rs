@@ -0,0 +1,138 @@
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::{
+    state::{AppState, Bookmark, Channel},
+    tabs::editor::AppStateEditorUtils,
+    utils::open_file_at_position,
+};
+
+#[allow(non_snake_case)]
+pub fn BookmarksPanel() -> Element {
+    // Edits of the focused editor move its bookmarks
+    let radio_app_state = use_radio::<AppState, Channel>(Channel::ActiveTab);
+
+    let app_state = radio_app_state.read();
+    let bookmarks = app_state.bookmarks();
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            padding: "6",
+            if bookmarks.is_empty() {
+                label {
+                    padding: "4",
+                    color: "rgb(135, 135, 135)",
+                    "Bookmark a line with `Ctrl F2`"
+                }
+            }
+            ScrollView {
+                theme: theme_with!(ScrollViewTheme {
+                    width: "100%".into(),
+                    height: "fill".into(),
+                }),
+                {bookmarks.into_iter().map(|bookmark| {
+                    // Only the opened files have their content at hand
+                    let preview = app_state
+                        .find_editor_tab(&bookmark.path)
+                        .and_then(|(panel, tab)| {
+                            let rope = &app_state.editor_tab(panel, tab).editor.rope;
+                            let line = rope.get_line(bookmark.line)?.to_string();
+                            Some(line.trim().to_string())
+                        });
+                    let key = format!("{}:{}", bookmark.path.display(), bookmark.line);
+                    rsx!(
+                        BookmarkItem {
+                            key: "{key}",
+                            bookmark,
+                            preview
+                        }
+                    )
+                })}
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn BookmarkItem(bookmark: Bookmark, preview: Option<String>) -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::ActiveTab);
+    let mut status = use_signal(|| ButtonStatus::Idle);
+
+    let onclick = {
+        let bookmark = bookmark.clone();
+        move |_| {
+            spawn(open_file_at_position(
+                radio_app_state,
+                bookmark.path.clone(),
+                bookmark.root_path.clone(),
+                bookmark.line,
+                0,
+            ));
+        }
+    };
+
+    let onremove = {
+        let bookmark = bookmark.clone();
+        move |_| {
+            radio_app_state
+                .write_channel(Channel::AllTabs)
+                .remove_bookmark(&bookmark);
+        }
+    };
+
+    let background = match *status.read() {
+        ButtonStatus::Hovering => "rgb(35, 35, 35)",
+        ButtonStatus::Idle => "transparent",
+    };
+
+    let file_name = bookmark
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    rsx!(
+        rect {
+            onmouseenter: move |_| status.set(ButtonStatus::Hovering),
+            onmouseleave: move |_| status.set(ButtonStatus::Idle),
+            background,
+            width: "100%",
+            padding: "4",
+            direction: "horizontal",
+            cross_align: "center",
+            rect {
+                width: "fill",
+                onclick,
+                label {
+                    max_lines: "1",
+                    text_overflow: "ellipsis",
+                    color: "rgb(235, 235, 235)",
+                    "🔖 {file_name}:{bookmark.line + 1}"
+                }
+                if let Some(preview) = preview {
+                    label {
+                        max_lines: "1",
+                        text_overflow: "ellipsis",
+                        font_family: "Jetbrains Mono",
+                        color: "rgb(135, 135, 135)",
+                        "{preview}"
+                    }
+                }
+            }
+            Button {
+                onpress: onremove,
+                theme: theme_with!(ButtonTheme {
+                    padding: "2 6".into(),
+                    background: "none".into(),
+                    border_fill: "none".into(),
+                }),
+                label {
+                    "✕"
+                }
+            }
+        }
+    )
+}
@@ -1,6 +1,10 @@
 use crate::{
     keyboard_navigation::use_keyboard_navigation,
-    state::{Channel, EditorCommands, EditorView},
+    state::{AppState, Channel, CommandOutput, EditorCommands, EditorView},
+    symbols::{parse_symbols, search_symbols, SymbolLocation},
+    tabs::editor::TabEditorUtils,
+    tasks::{run_task, WorkspaceTask},
+    utils::{fuzzy_match, open_file_at},
     TextArea,
 };
 use dioxus_radio::prelude::use_radio;
@@ -9,16 +13,28 @@ use freya::prelude::*;
 #[derive(Props, Clone, PartialEq)]
 pub struct CommanderProps {
     editor_commands: Signal<EditorCommands>,
+    /// Docked above the panels instead of floating over them.
+    #[props(default = false)]
+    pinned: bool,
 }
 
 #[allow(non_snake_case)]
-pub fn Commander(CommanderProps { editor_commands }: CommanderProps) -> Element {
+pub fn Commander(
+    CommanderProps {
+        editor_commands,
+        pinned,
+    }: CommanderProps,
+) -> Element {
     let mut radio_app_state = use_radio(Channel::Global);
     let mut value = use_signal(String::new);
     let mut selected = use_signal(|| 0);
+    // Result of the last command, with the text of the command
+    let mut output = use_signal(|| None::<(String, CommandOutput)>);
     let mut keyboard_navigation = use_keyboard_navigation();
     let mut focus = use_focus();
 
+    let is_focused = *radio_app_state.read().focused_view() == EditorView::Commander;
+
     let commands = editor_commands.read();
     let filtered_commands = commands
         .commands
@@ -32,43 +48,119 @@ pub fn Commander(CommanderProps { editor_commands }: CommanderProps) -> Element
             .then_some(id.clone())
         })
         .collect::<Vec<String>>();
-    let filtered_commands_len = filtered_commands.len();
-    let options_height = ((filtered_commands_len.max(1)) * 30).min(200);
+    // `@` lists the symbols of the focused file and `#` the ones of the workspace
+    let symbols = {
+        let value = value.read();
+        if let Some(query) = value.strip_prefix('@') {
+            Some(file_symbols(&radio_app_state.read(), query))
+        } else if let Some(query) = value.strip_prefix('#') {
+            Some(radio_app_state.read().symbol_index.search(query))
+        } else {
+            None
+        }
+    };
+    // `!` lists the tasks of the opened folders
+    let tasks = value
+        .read()
+        .strip_prefix('!')
+        .map(|query| matching_tasks(&radio_app_state.read(), query));
+    let options_len = symbols
+        .as_ref()
+        .map(Vec::len)
+        .or(tasks.as_ref().map(Vec::len))
+        .unwrap_or(filtered_commands.len());
+    let max_options_height = if pinned { 120 } else { 200 };
+    let options_height = ((options_len.max(1)) * 30).min(max_options_height);
 
     let onchange = move |v| {
         if *value.read() != v {
             selected.set(0);
             value.set(v);
+            output.set(None);
         }
     };
 
     let command_id = filtered_commands.get(selected()).cloned();
+    let is_listing_symbols = symbols.is_some();
+    let symbol = symbols
+        .as_ref()
+        .and_then(|symbols| symbols.get(selected()).cloned());
+    let is_listing_tasks = tasks.is_some();
+    let task = tasks
+        .as_ref()
+        .and_then(|tasks| tasks.get(selected()).cloned());
 
-    let onsubmit = move |_: String| {
-        to_owned![command_id];
+    // Run a command and focus the previous view, unless it has a result to show
+    let mut run_command = move |command_id: Option<String>| {
+        let editor_commands = editor_commands.read();
+        let command = command_id
+            .as_ref()
+            .and_then(|command_i| editor_commands.commands.get(command_i));
+        if let Some(command) = command {
+            // Run the command
+            let command_output = command.run_with_input(&value.peek());
+            if let Some(command_output) = command_output {
+                output.set(Some((command.text().to_string(), command_output)));
+                return;
+            }
 
-        // Focus the previous view
-        keyboard_navigation.callback(true, move || {
-            let editor_commands = editor_commands.read();
-            let command = command_id
-                .as_ref()
-                .and_then(|command_i| editor_commands.commands.get(command_i));
-            if let Some(command) = command {
-                // Run the command
-                command.run();
-
-                let mut app_state = radio_app_state.write();
+            let mut app_state = radio_app_state.write();
+            if *app_state.focused_view() == EditorView::Commander {
                 app_state.set_focused_view_to_previous();
             }
-        });
+        }
+    };
+
+    // Focus the previous view and jump to a symbol
+    let mut open_symbol = move |location: SymbolLocation| {
+        {
+            let mut app_state = radio_app_state.write();
+            if *app_state.focused_view() == EditorView::Commander {
+                app_state.set_focused_view_to_previous();
+            }
+        }
+        spawn(open_file_at(
+            radio_app_state,
+            location.path,
+            location.root_path,
+            location.symbol.range,
+        ));
+    };
+
+    // Focus the previous view and run a task
+    let mut start_task = move |task: WorkspaceTask| {
+        {
+            let mut app_state = radio_app_state.write();
+            if *app_state.focused_view() == EditorView::Commander {
+                app_state.set_focused_view_to_previous();
+            }
+        }
+        spawn(run_task(radio_app_state, task));
+    };
+
+    let onsubmit = move |_: String| {
+        to_owned![command_id, symbol, task];
+
+        // Focus the previous view
+        if let Some(symbol) = symbol {
+            keyboard_navigation.callback(true, move || open_symbol(symbol));
+        } else if let Some(task) = task {
+            keyboard_navigation.callback(true, move || start_task(task));
+        } else if !is_listing_symbols && !is_listing_tasks {
+            keyboard_navigation.callback(true, move || run_command(command_id));
+        }
     };
 
     let onkeydown = move |e: KeyboardEvent| {
+        // The pinned Commander shares the keyboard with the other views
+        if !is_focused {
+            return;
+        }
         focus.prevent_navigation();
         match e.code {
             Code::ArrowDown => {
-                if filtered_commands_len > 0 {
-                    if *selected.read() < filtered_commands_len - 1 {
+                if options_len > 0 {
+                    if *selected.read() < options_len - 1 {
                         *selected.write() += 1;
                     } else {
                         selected.set(0);
@@ -76,10 +168,10 @@ pub fn Commander(CommanderProps { editor_commands }: CommanderProps) -> Element
                 }
             }
             Code::ArrowUp => {
-                if selected() > 0 && filtered_commands_len > 0 {
+                if selected() > 0 && options_len > 0 {
                     *selected.write() -= 1;
                 } else {
-                    selected.set(filtered_commands_len - 1);
+                    selected.set(options_len.saturating_sub(1));
                 }
             }
             _ => {}
@@ -87,58 +179,263 @@ pub fn Commander(CommanderProps { editor_commands }: CommanderProps) -> Element
     };
 
     let onglobalmousedown = move |_| {
-        if *radio_app_state.read().focused_view() == EditorView::Commander {
+        if !pinned && *radio_app_state.read().focused_view() == EditorView::Commander {
             let mut app_state = radio_app_state.write_channel(Channel::Global);
             app_state.set_focused_view_to_previous();
         }
     };
 
-    rsx!(
-        rect {
-            width: "100%",
-            height: "0",
-            layer: "-100",
-            onglobalmousedown,
-            onkeydown,
+    // Clicking the pinned Commander gives it the keyboard focus
+    let onmousedown = move |_| {
+        if pinned && *radio_app_state.read().focused_view() != EditorView::Commander {
+            let mut app_state = radio_app_state.write_channel(Channel::Global);
+            app_state.set_focused_view(EditorView::Commander);
+        }
+    };
+
+    let options_list = if let Some(symbols) = symbols {
+        rsx!(
+            if symbols.is_empty() {
+                {commander_option("not-found", "Symbol Not Found", true, None)}
+            }
+            for (n, location) in symbols.into_iter().enumerate() {
+                {
+                    let key = format!("{}:{}", location.path.display(), location.symbol.range.start);
+                    let file_name = location.path.file_name().unwrap_or_default().to_string_lossy();
+                    let text = format!(
+                        "{} {}  {file_name}:{}",
+                        location.symbol.kind,
+                        location.symbol.name,
+                        location.symbol.line + 1
+                    );
+                    let is_selected = n == selected() && is_focused;
+                    let onclick = EventHandler::new(move |_| open_symbol(location.clone()));
+                    commander_option(&key, &text, is_selected, Some(onclick))
+                }
+            }
+        )
+    } else if let Some(tasks) = tasks {
+        rsx!(
+            if tasks.is_empty() {
+                {commander_option("not-found", "Task Not Found", true, None)}
+            }
+            for (n, task) in tasks.into_iter().enumerate() {
+                {
+                    let key = format!("{}:{}", task.root_path.display(), task.task.name);
+                    let folder_name = task.root_path.file_name().unwrap_or_default().to_string_lossy();
+                    let text = format!("{}  {folder_name}", task.task.name);
+                    let is_selected = n == selected() && is_focused;
+                    let onclick = EventHandler::new(move |_| start_task(task.clone()));
+                    commander_option(&key, &text, is_selected, Some(onclick))
+                }
+            }
+        )
+    } else {
+        rsx!(
+            if filtered_commands.is_empty() {
+                {commander_option("not-found", "Command Not Found", true, None)}
+            }
+            for (n, command_id) in filtered_commands.into_iter().enumerate() {
+                {
+                    let command = commands.commands.get(&command_id).unwrap();
+                    let is_selected = n == selected() && is_focused;
+                    let onclick = EventHandler::new(move |_| run_command(Some(command_id.clone())));
+                    commander_option(command.id(), command.text(), is_selected, Some(onclick))
+                }
+            }
+        )
+    };
+
+    let width = if output.read().is_some() {
+        "500"
+    } else {
+        "300"
+    };
+
+    let options = rsx!(
+        TextArea {
+            placeholder: "Run a command...",
+            value: "{value}",
+            onchange,
+            onsubmit,
+            autofocus: !pinned,
+            enabled: is_focused,
+        }
+        if let Some((title, output)) = output() {
+            CommandOutputView {
+                title,
+                output,
+                max_height: max_options_height
+            }
+        } else {
+            ScrollView {
+                theme: theme_with!(ScrollViewTheme {
+                    height: options_height.to_string().into(),
+                }),
+                {options_list}
+            }
+        }
+    );
+
+    if pinned {
+        rsx!(
             rect {
                 width: "100%",
-                main_align: "center",
-                cross_align: "center",
-                padding: "10",
+                padding: "5",
+                background: "rgb(30, 30, 30)",
+                onkeydown,
+                onmousedown,
+                {options}
+            }
+        )
+    } else {
+        rsx!(
+            rect {
+                width: "100%",
+                height: "0",
+                layer: "-100",
+                onglobalmousedown,
+                onkeydown,
                 rect {
-                    background: "rgb(45, 45, 45)",
-                    shadow: "0 4 15 8 rgb(0, 0, 0, 0.3)",
-                    corner_radius: "10",
-                    onmousedown: |_| {},
-                    width: "300",
-                    padding: "5",
-                    TextArea {
-                        placeholder: "Run a command...",
-                        value: "{value}",
-                        onchange,
-                        onsubmit,
-                    }
-                    ScrollView {
-                        theme: theme_with!(ScrollViewTheme {
-                            height: options_height.to_string().into(),
-                        }),
-                        if filtered_commands.is_empty() {
-                            {commander_option("not-found", "Command Not Found", true)}
-                        }
-                        for (n, command_id) in filtered_commands.into_iter().enumerate() {
-                            {
-                                let command = commands.commands.get(&command_id).unwrap();
-                                commander_option(&command_id, command.text(), n == selected())
-                            }
-                        }
+                    width: "100%",
+                    main_align: "center",
+                    cross_align: "center",
+                    padding: "10",
+                    rect {
+                        background: "rgb(45, 45, 45)",
+                        shadow: "0 4 15 8 rgb(0, 0, 0, 0.3)",
+                        corner_radius: "10",
+                        onmousedown: |_| {},
+                        width,
+                        padding: "5",
+                        {options}
                     }
                 }
             }
+        )
+    }
+}
+
+/// Tasks of the opened folders that match the query, best matches first.
+fn matching_tasks(app_state: &AppState, query: &str) -> Vec<WorkspaceTask> {
+    let mut tasks = app_state
+        .tasks
+        .iter()
+        .filter_map(|task| Some((fuzzy_match(&task.task.name, query)?, task.clone())))
+        .collect::<Vec<(usize, WorkspaceTask)>>();
+    tasks.sort_by_key(|(gaps, _)| *gaps);
+    tasks.into_iter().map(|(_, task)| task).collect()
+}
+
+/// Symbols of the file in the focused editor that match the query.
+fn file_symbols(app_state: &AppState, query: &str) -> Vec<SymbolLocation> {
+    let panel = app_state.panel(app_state.focused_panel);
+    let Some(editor_tab) = panel
+        .active_tab()
+        .and_then(|active_tab| panel.tab(active_tab).as_text_editor())
+    else {
+        return Vec::new();
+    };
+    let Some((path, root_path)) = editor_tab.editor.editor_type.paths() else {
+        return Vec::new();
+    };
+
+    let locations = parse_symbols(&editor_tab.editor.rope)
+        .into_iter()
+        .map(|symbol| SymbolLocation {
+            path: path.clone(),
+            root_path: root_path.clone(),
+            symbol,
+        });
+    search_symbols(locations, query)
+}
+
+/// Result of a command, below the Commander input.
+#[allow(non_snake_case)]
+#[component]
+fn CommandOutputView(title: String, output: CommandOutput, max_height: usize) -> Element {
+    let lines_len = match &output {
+        CommandOutput::Text(text) => text.lines().count(),
+        CommandOutput::List(items) => items.len(),
+        CommandOutput::Table { rows, .. } => rows.len() + 1,
+    };
+    let height = ((lines_len.max(1) * 24) + 30).min(max_height);
+
+    let content = match output {
+        CommandOutput::Text(text) => rsx!(
+            label {
+                width: "100%",
+                padding: "4 6",
+                "{text}"
+            }
+        ),
+        CommandOutput::List(items) => rsx!(
+            for (n, item) in items.into_iter().enumerate() {
+                label {
+                    key: "{n}",
+                    width: "100%",
+                    height: "24",
+                    padding: "4 6",
+                    max_lines: "1",
+                    text_overflow: "ellipsis",
+                    "{item}"
+                }
+            }
+        ),
+        CommandOutput::Table { headers, rows } => {
+            let column_width = format!("{}%", 100.0 / headers.len().max(1) as f32);
+            rsx!(
+                {output_row("headers", &headers, &column_width, "rgb(135, 135, 135)")}
+                for (n, row) in rows.into_iter().enumerate() {
+                    {output_row(&n.to_string(), &row, &column_width, "rgb(235, 235, 235)")}
+                }
+            )
+        }
+    };
+
+    rsx!(
+        label {
+            padding: "8 6 4 6",
+            color: "rgb(135, 135, 135)",
+            "{title}"
+        }
+        ScrollView {
+            theme: theme_with!(ScrollViewTheme {
+                height: height.to_string().into(),
+            }),
+            {content}
+        }
+    )
+}
+
+fn output_row(key: &str, cells: &[String], column_width: &str, color: &str) -> Element {
+    rsx!(
+        rect {
+            key: "{key}",
+            width: "100%",
+            height: "24",
+            padding: "4 6",
+            direction: "horizontal",
+            color,
+            for (n, cell) in cells.iter().enumerate() {
+                label {
+                    key: "{n}",
+                    width: column_width,
+                    max_lines: "1",
+                    text_overflow: "ellipsis",
+                    "{cell}"
+                }
+            }
         }
     )
 }
 
-fn commander_option(command_id: &str, command_text: &str, is_selected: bool) -> Element {
+fn commander_option(
+    command_id: &str,
+    command_text: &str,
+    is_selected: bool,
+    onclick: Option<EventHandler<MouseEvent>>,
+) -> Element {
     let background = if is_selected { "rgb(65, 65, 65)" } else { "" };
 
     rsx!(
@@ -150,6 +447,11 @@ fn commander_option(command_id: &str, command_text: &str, is_selected: bool) ->
             height: "30",
             corner_radius: "10",
             main_align: "center",
+            onclick: move |e| {
+                if let Some(onclick) = onclick {
+                    onclick.call(e)
+                }
+            },
             label {
                 "{command_text}"
             }
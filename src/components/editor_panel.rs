@@ -1,6 +1,7 @@
 use super::icons::*;
 use super::tab::*;
-use crate::state::{AppState, Channel, Panel};
+use crate::notifications::notify;
+use crate::state::{AppState, Channel, NotificationLevel, Panel, TabDrag, TabDropZone};
 use crate::utils::*;
 use dioxus_radio::prelude::use_radio;
 use freya::prelude::*;
@@ -10,15 +11,29 @@ pub struct EditorPanelProps {
     panel_index: usize,
     #[props(into)]
     width: String,
+    #[props(into)]
+    height: String,
 }
 
 #[allow(non_snake_case)]
-pub fn EditorPanel(EditorPanelProps { panel_index, width }: EditorPanelProps) -> Element {
+pub fn EditorPanel(
+    EditorPanelProps {
+        panel_index,
+        width,
+        height,
+    }: EditorPanelProps,
+) -> Element {
     let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
 
     let app_state = radio_app_state.read();
     let panels_len = app_state.panels().len();
-    let is_last_panel = app_state.panels().len() - 1 == panel_index;
+    // Zen mode only shows the focused panel, without its tabs
+    let zen_mode = app_state.layout.zen_mode;
+    let column = app_state.panel_column(panel_index);
+    let is_last_column = column.end == panels_len;
+    let is_last_in_column = column.end - 1 == panel_index;
+    let show_divider = !is_last_column && !zen_mode;
+    let show_bottom_divider = !is_last_in_column && !zen_mode;
     let is_focused = app_state.focused_panel() == panel_index;
     let panel = app_state.panel(panel_index);
     let active_tab_index = panel.active_tab();
@@ -45,108 +60,206 @@ pub fn EditorPanel(EditorPanelProps { panel_index, width }: EditorPanelProps) ->
         }
     };
 
+    // Size of the content of the panel, to find the drop zone of a dragged tab
+    let (content_ref, content_size) = use_node();
+
+    let onmouseover = move |e: MouseEvent| {
+        let Some(tab_drag) = radio_app_state.read().tab_drag else {
+            return;
+        };
+        let coordinates = e.get_screen_coordinates();
+        let zone = TabDropZone::from_position(
+            coordinates.x as f32 - content_size.area.min_x(),
+            coordinates.y as f32 - content_size.area.min_y(),
+            content_size.area.width(),
+            content_size.area.height(),
+        );
+        if tab_drag.target != Some((panel_index, zone)) {
+            let mut app_state = radio_app_state.write_channel(Channel::Global);
+            if let Some(tab_drag) = &mut app_state.tab_drag {
+                tab_drag.target = Some((panel_index, zone));
+            }
+        }
+    };
+
+    let onmouseleave = move |_| {
+        let is_target = radio_app_state
+            .read()
+            .tab_drag
+            .is_some_and(|tab_drag| tab_drag.target.map(|(panel, _)| panel) == Some(panel_index));
+        if is_target {
+            let mut app_state = radio_app_state.write_channel(Channel::Global);
+            if let Some(tab_drag) = &mut app_state.tab_drag {
+                tab_drag.target = None;
+            }
+        }
+    };
+
+    // Releasing the mouse drops the dragged tab in the panel under it, if any
+    let onglobalclick = move |_| {
+        let Some(tab_drag) = radio_app_state.read().tab_drag else {
+            return;
+        };
+        match tab_drag.target {
+            Some((target_panel, zone)) if target_panel == panel_index => {
+                let mut app_state = radio_app_state.write_channel(Channel::Global);
+                app_state.tab_drag = None;
+                app_state.drop_tab(tab_drag.panel, tab_drag.tab, target_panel, zone);
+            }
+            // Another panel takes care of it
+            Some(_) => {}
+            None => {
+                radio_app_state.write_channel(Channel::Global).tab_drag = None;
+            }
+        }
+    };
+
+    let drop_zone = app_state
+        .tab_drag
+        .and_then(|tab_drag| tab_drag.target)
+        .filter(|(target_panel, _)| *target_panel == panel_index)
+        .map(|(_, zone)| zone);
+    let content_height = content_size.area.height();
+    let (drop_zone_align, drop_zone_width, drop_zone_top, drop_zone_height) = match drop_zone {
+        Some(TabDropZone::Left) => ("start", "50%", 0.0, content_height),
+        Some(TabDropZone::Right) => ("end", "50%", 0.0, content_height),
+        Some(TabDropZone::Top) => ("start", "100%", 0.0, content_height / 2.0),
+        Some(TabDropZone::Bottom) => ("start", "100%", content_height / 2.0, content_height / 2.0),
+        _ => ("center", "100%", 0.0, content_height),
+    };
+
     let show_close_panel = panels_len > 1;
     let tabsbar_tools_width = if show_close_panel { 115 } else { 60 };
-    let extra_container_width = if is_last_panel { 0 } else { 1 };
+    let extra_container_width = if show_divider { 1 } else { 0 };
+    let extra_container_height = if show_bottom_divider { 1 } else { 0 };
 
     rsx!(
         rect {
-            direction: "horizontal",
-            height: "100%",
+            height: "{height}",
             width: "{width}",
             rect {
-                width: "calc(100% - {extra_container_width})",
-                height: "100%",
-                overflow: "clip",
+                direction: "horizontal",
+                height: "calc(100% - {extra_container_height})",
+                width: "100%",
                 rect {
-                    direction: "horizontal",
-                    height: "34",
-                    width: "100%",
-                    cross_align: "center",
-                    ScrollView {
-                        direction: "horizontal",
-                        theme: theme_with!(ScrollViewTheme {
-                            width: format!("calc(100% - {tabsbar_tools_width})").into(),
-                        }),
-                        show_scrollbar: false,
-                        {panel.tabs().iter().enumerate().map(|(tab_index, _)| {
-                            let is_selected = active_tab_index == Some(tab_index);
-                            rsx!(
-                                PanelTab {
-                                    panel_index,
-                                    tab_index,
-                                    is_selected,
-                                }
-                            )
-                        })}
-                    }
-                    rect {
-                        width: "{tabsbar_tools_width}",
-                        direction: "horizontal",
-                        cross_align: "center",
-                        main_align: "end",
-                        height: "100%",
-                        if show_close_panel {
-                            Button {
-                                theme: theme_with!(ButtonTheme {
-                                    height: "100%".into(),
-                                    padding: "10 8".into(),
+                    width: "calc(100% - {extra_container_width})",
+                    height: "100%",
+                    overflow: "clip",
+                    if !zen_mode {
+                        rect {
+                            direction: "horizontal",
+                            height: "34",
+                            width: "100%",
+                            cross_align: "center",
+                            ScrollView {
+                                direction: "horizontal",
+                                theme: theme_with!(ScrollViewTheme {
+                                    width: format!("calc(100% - {tabsbar_tools_width})").into(),
                                 }),
-                                onpress: close_panel,
-                                label {
-                                    "Close"
-                                }
+                                show_scrollbar: false,
+                                {panel.tabs().iter().enumerate().map(|(tab_index, _)| {
+                                    let is_selected = active_tab_index == Some(tab_index);
+                                    rsx!(
+                                        PanelTab {
+                                            panel_index,
+                                            tab_index,
+                                            is_selected,
+                                        }
+                                    )
+                                })}
                             }
-                        }
-                        Button {
-                            theme: theme_with!(ButtonTheme {
-                                height: "100%".into(),
-                                padding: "10 8".into(),
-                            }),
-                            onpress: split_panel,
-                            label {
-                                "Split"
+                            rect {
+                                width: "{tabsbar_tools_width}",
+                                direction: "horizontal",
+                                cross_align: "center",
+                                main_align: "end",
+                                height: "100%",
+                                if show_close_panel {
+                                    Button {
+                                        theme: theme_with!(ButtonTheme {
+                                            height: "100%".into(),
+                                            padding: "10 8".into(),
+                                        }),
+                                        onpress: close_panel,
+                                        label {
+                                            "Close"
+                                        }
+                                    }
+                                }
+                                Button {
+                                    theme: theme_with!(ButtonTheme {
+                                        height: "100%".into(),
+                                        padding: "10 8".into(),
+                                    }),
+                                    onpress: split_panel,
+                                    label {
+                                        "Split"
+                                    }
+                                }
                             }
                         }
                     }
-                }
-                rect {
-                    height: "fill",
-                    width: "100%",
-                    onclick: onclickpanel,
-                    if let Some(active_tab_index) = active_tab_index {
-                        {
-                            let active_tab = panel.tab(active_tab_index);
-                            let tab_data = active_tab.get_data();
-                            let Render = active_tab.as_ref().render();
-                            rsx!(
-                                Render {
-                                    key: "{tab_data.id}",
-                                    panel_index,
-                                    tab_index: active_tab_index,
+                    rect {
+                        height: "fill",
+                        width: "100%",
+                        reference: content_ref,
+                        onclick: onclickpanel,
+                        onmouseover,
+                        onmouseleave,
+                        onglobalclick,
+                        if drop_zone.is_some() {
+                            rect {
+                                width: "100%",
+                                height: "0",
+                                layer: "-100",
+                                direction: "horizontal",
+                                main_align: "{drop_zone_align}",
+                                rect {
+                                    width: "{drop_zone_width}",
+                                    height: "{drop_zone_height}",
+                                    margin: "{drop_zone_top} 0 0 0",
+                                    background: "rgb(90, 140, 220, 0.2)",
+                                    border: "2 solid rgb(90, 140, 220, 0.6)",
                                 }
-                            )
+                            }
                         }
-                    } else {
-                        rect {
-                            main_align: "center",
-                            cross_align: "center",
-                            width: "100%",
-                            height: "100%",
-                            background: "rgb(20, 20, 20)",
-                            ExpandedIcon {
-                                Logo {
-                                    enabled: is_focused,
-                                    width: "200",
-                                    height: "200"
+                        if let Some(active_tab_index) = active_tab_index {
+                            {
+                                let active_tab = panel.tab(active_tab_index);
+                                let tab_data = active_tab.get_data();
+                                let Render = active_tab.as_ref().render();
+                                rsx!(
+                                    Render {
+                                        key: "{tab_data.id}",
+                                        panel_index,
+                                        tab_index: active_tab_index,
+                                    }
+                                )
+                            }
+                        } else {
+                            rect {
+                                main_align: "center",
+                                cross_align: "center",
+                                width: "100%",
+                                height: "100%",
+                                background: "rgb(20, 20, 20)",
+                                ExpandedIcon {
+                                    Logo {
+                                        enabled: is_focused,
+                                        width: "200",
+                                        height: "200"
+                                    }
                                 }
                             }
                         }
                     }
                 }
+                if show_divider {
+                    Divider { }
+                }
             }
-            if !is_last_panel {
-                Divider { }
+            if show_bottom_divider {
+                VerticalDivider { }
             }
         }
     )
@@ -186,7 +299,12 @@ fn PanelTab(
 
     let onclickaction = move |_| {
         if tab_data.edited {
-            println!("save...")
+            notify(
+                radio_app_state,
+                NotificationLevel::Warning,
+                format!("Save {} before closing it", tab_data.title),
+                None,
+            );
         } else {
             radio_app_state
                 .write_channel(Channel::Global)
@@ -194,12 +312,23 @@ fn PanelTab(
         }
     };
 
+    let ondragstart = move |_| {
+        radio_app_state.write_channel(Channel::Global).tab_drag = Some(TabDrag {
+            panel: panel_index,
+            tab: tab_index,
+            target: None,
+        });
+    };
+
     rsx!(EditorTab {
         key: "{tab_data.id}",
         onclick,
         onclickaction,
+        ondragstart,
         value: "{tab_data.title}",
         is_edited: tab_data.edited,
+        is_preview: tab_data.preview,
+        is_read_only: tab_data.read_only,
         is_selected
     })
 }
@@ -60,6 +60,8 @@ pub struct EditorScrollViewProps<
     pub offset_y: i32,
     pub offset_x: i32,
     pub onscroll: Option<EventHandler<(Axis, i32)>>,
+    /// Called with the wheel movement when scrolling while holding `Ctrl`.
+    pub onzoom: Option<EventHandler<f32>>,
 
     builder_args: BuilderArgs,
     builder: Builder,
@@ -107,10 +109,12 @@ pub fn EditorScrollView<
 ) -> Element {
     let mut clicking_shift = use_signal(|| false);
     let mut clicking_alt = use_signal(|| false);
+    let mut clicking_ctrl = use_signal(|| false);
     let mut clicking_scrollbar = use_signal::<Option<(Axis, f64)>>(|| None);
     let scrolled_y = props.offset_y;
     let scrolled_x = props.offset_x;
     let onscroll = props.onscroll.unwrap();
+    let onzoom = props.onzoom;
     let mut focus = use_focus();
     let (node_ref, size) = use_node();
     let scrollbar_theme = use_applied_theme!(&None, scroll_bar);
@@ -153,6 +157,11 @@ pub fn EditorScrollView<
 
     // Moves the Y axis when the user scrolls in the container
     let onwheel = move |e: WheelEvent| {
+        if let Some(onzoom) = onzoom.filter(|_| *clicking_ctrl.read()) {
+            onzoom.call(e.get_delta_y() as f32);
+            return;
+        }
+
         let speed_multiplier = if *clicking_alt.read() {
             SCROLL_SPEED_MULTIPLIER
         } else {
@@ -228,6 +237,9 @@ pub fn EditorScrollView<
             Key::Alt => {
                 clicking_alt.set(true);
             }
+            Key::Control => {
+                clicking_ctrl.set(true);
+            }
             _ => {
                 // TODO: Support other keys with `manage_key_event`
             }
@@ -239,6 +251,8 @@ pub fn EditorScrollView<
             clicking_shift.set(false);
         } else if e.key == Key::Alt {
             clicking_alt.set(false);
+        } else if e.key == Key::Control {
+            clicking_ctrl.set(false);
         }
     };
 
@@ -1,18 +1,34 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use dioxus_radio::hooks::use_radio;
 use freya::elements as dioxus_elements;
-use freya::prelude::keyboard::Code;
+use freya::prelude::keyboard::{Code, Key};
 use freya::prelude::*;
 use futures::StreamExt;
-use tokio::io;
+use tokio::{io, sync::mpsc::unbounded_channel};
+use tracing::info;
 
 use crate::{
-    fs::FSTransport,
-    state::{AppState, Channel, EditorView, RadioAppState},
-    tabs::editor::EditorTab,
+    fs::{ExcludeMatcher, FSEntry, FSTransport},
+    notifications::notify,
+    search::collect_files,
+    state::{AppState, Channel, EditorView, NotificationAction, NotificationLevel, RadioAppState},
+    symbols::index_folder,
+    tabs::{
+        diff::DiffTab,
+        editor::{AppStateEditorUtils, TabEditorUtils},
+    },
+    tasks::{is_tasks_file, load_tasks},
+    utils::{fuzzy_match, is_read_only, open_file, open_file_as_preview},
 };
 
+/// Files opened twice within this time are kept open instead of previewed.
+const DOUBLE_CLICK_DELAY: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum FolderState {
     Opened(Vec<ExplorerItem>),
@@ -47,6 +63,25 @@ impl ExplorerItem {
         }
     }
 
+    /// Items of an opened folder of this tree.
+    fn opened_items(&self, folder_path: &Path) -> Option<&[ExplorerItem]> {
+        match self {
+            ExplorerItem::Folder {
+                path,
+                state: FolderState::Opened(items),
+            } => {
+                if path == folder_path {
+                    Some(items)
+                } else if folder_path.starts_with(path) {
+                    items.iter().find_map(|item| item.opened_items(folder_path))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
     pub fn flat(&self, depth: usize, root_path: &PathBuf) -> Vec<FlatItem> {
         let mut flat_items = vec![self.clone().into_flat(depth, root_path.clone())];
         if let ExplorerItem::Folder {
@@ -94,23 +129,26 @@ pub struct FlatItem {
 pub async fn read_folder_as_items(
     dir: &Path,
     transport: &FSTransport,
+    exclude_matcher: &ExcludeMatcher,
 ) -> io::Result<Vec<ExplorerItem>> {
-    let mut paths = transport.read_dir(dir).await?;
     let mut folder_items = Vec::default();
     let mut files_items = Vec::default();
 
-    while let Ok(Some(entry)) = paths.next_entry().await {
-        let file_type = entry.file_type().await?;
-        let is_file = file_type.is_file();
-        let path = entry.path();
+    let exclude_matcher = exclude_matcher.for_folder(dir, transport).await;
+    for entry in transport.list(dir).await? {
+        let FSEntry { path, is_dir } = entry;
 
-        if is_file {
-            files_items.push(ExplorerItem::File { path })
-        } else {
+        if exclude_matcher.is_excluded(&path, is_dir) {
+            continue;
+        }
+
+        if is_dir {
             folder_items.push(ExplorerItem::Folder {
                 path,
                 state: FolderState::Closed,
             })
+        } else {
+            files_items.push(ExplorerItem::File { path })
         }
     }
 
@@ -119,6 +157,334 @@ pub async fn read_folder_as_items(
     Ok(folder_items)
 }
 
+/// Open the folders containing a file and focus it in the files explorer.
+pub async fn reveal_file(mut radio_app_state: RadioAppState, file_path: PathBuf) {
+    let root = {
+        let app_state = radio_app_state.read();
+        app_state
+            .file_explorer_folders
+            .iter()
+            .map(|folder| folder.path())
+            .find(|root_path| file_path.starts_with(root_path))
+            .map(|root_path| {
+                (
+                    root_path.clone(),
+                    app_state.transport(root_path),
+                    app_state.exclude_matcher(root_path),
+                )
+            })
+    };
+    let Some((root_path, transport, exclude_matcher)) = root else {
+        return;
+    };
+
+    // From the root folder down to the folder of the file
+    let mut folders = file_path
+        .ancestors()
+        .skip(1)
+        .take_while(|folder_path| folder_path.starts_with(&root_path))
+        .map(Path::to_path_buf)
+        .collect::<Vec<PathBuf>>();
+    folders.reverse();
+
+    for folder_path in folders {
+        let is_opened = radio_app_state
+            .read()
+            .file_explorer_folders
+            .iter()
+            .find(|folder| folder.path() == &root_path)
+            .and_then(|folder| folder.opened_items(&folder_path))
+            .is_some();
+        if is_opened {
+            continue;
+        }
+
+        let Ok(items) = read_folder_as_items(&folder_path, &transport, &exclude_matcher).await
+        else {
+            return;
+        };
+
+        let mut app_state = radio_app_state.write_channel(Channel::FileExplorer);
+        let folder = app_state
+            .file_explorer_folders
+            .iter_mut()
+            .find(|folder| folder.path() == &root_path);
+        if let Some(folder) = folder {
+            folder.set_folder_state(&folder_path, &FolderState::Opened(items));
+        }
+    }
+
+    radio_app_state
+        .write_channel(Channel::FileExplorer)
+        .file_explorer_revealed_file = Some(file_path);
+}
+
+/// Move a file or a folder to the trash, closing the files opened from it.
+/// Files with unsaved changes are kept open.
+pub async fn trash_item(mut radio_app_state: RadioAppState, path: PathBuf, root_path: PathBuf) {
+    let transport = radio_app_state.read().transport(&path);
+    let name = file_name(&path);
+
+    if let Err(err) = transport.trash(&path).await {
+        notify(
+            radio_app_state,
+            NotificationLevel::Error,
+            format!("Failed to move {name} to the trash: {err}"),
+            None,
+        );
+        return;
+    }
+
+    let mut closed_files = Vec::new();
+    {
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        for panel in (0..app_state.panels().len()).rev() {
+            for tab in (0..app_state.panel(panel).tabs().len()).rev() {
+                let closed_file = app_state
+                    .panel(panel)
+                    .tab(tab)
+                    .as_text_editor()
+                    .filter(|editor_tab| !editor_tab.editor.is_edited())
+                    .and_then(|editor_tab| editor_tab.editor.path())
+                    .filter(|file_path| file_path.starts_with(&path))
+                    .cloned();
+
+                if let Some(closed_file) = closed_file {
+                    app_state.close_tab(panel, tab);
+                    closed_files.push(closed_file);
+                }
+            }
+        }
+    }
+
+    let action =
+        transport
+            .can_restore_from_trash()
+            .then_some(NotificationAction::RestoreFromTrash {
+                path,
+                root_path,
+                closed_files,
+            });
+    notify(
+        radio_app_state,
+        NotificationLevel::Info,
+        format!("Moved {name} to the trash"),
+        action,
+    );
+}
+
+/// Bring back an entry moved to the trash with [`trash_item`] and reopen its closed files.
+pub async fn restore_trashed_item(
+    mut radio_app_state: RadioAppState,
+    path: PathBuf,
+    root_path: PathBuf,
+    closed_files: Vec<PathBuf>,
+) {
+    let transport = radio_app_state.read().transport(&path);
+
+    if let Err(err) = transport.restore_from_trash(&path).await {
+        notify(
+            radio_app_state,
+            NotificationLevel::Error,
+            format!("Failed to restore {}: {err}", file_name(&path)),
+            None,
+        );
+        return;
+    }
+
+    for file_path in closed_files {
+        match transport.read(&file_path).await {
+            Ok(content) => {
+                let read_only = is_read_only(&transport, &file_path).await;
+                let mut app_state = radio_app_state.write_channel(Channel::Global);
+                open_file(
+                    &mut app_state,
+                    file_path,
+                    root_path.clone(),
+                    content,
+                    read_only,
+                );
+            }
+            Err(err) => notify(
+                radio_app_state,
+                NotificationLevel::Error,
+                format!("Failed to reopen {}: {err}", file_name(&file_path)),
+                None,
+            ),
+        }
+    }
+}
+
+/// Open a folder whose files are accessed through the given transport, e.g not the local filesystem.
+pub async fn open_mounted_folder(
+    mut radio_app_state: RadioAppState,
+    root_path: PathBuf,
+    transport: FSTransport,
+) {
+    let exclude_matcher = {
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.mount_transport(root_path.clone(), transport.clone());
+        app_state.exclude_matcher(&root_path)
+    };
+
+    let items = read_folder_as_items(&root_path, &transport, &exclude_matcher)
+        .await
+        .unwrap_or_default();
+
+    let mut app_state = radio_app_state.write_channel(Channel::FileExplorer);
+    app_state.open_folder(ExplorerItem::Folder {
+        path: root_path.clone(),
+        state: FolderState::Opened(items),
+    });
+    app_state.set_focused_view(EditorView::FilesExplorer);
+    spawn(index_folder(radio_app_state, root_path.clone()));
+    spawn(load_tasks(radio_app_state, root_path.clone()));
+    spawn(watch_folder(radio_app_state, root_path));
+}
+
+/// Read again the opened folders of a tree when their files change, keeping the opened subfolders.
+pub async fn watch_folder(mut radio_app_state: RadioAppState, root_path: PathBuf) {
+    let (sender, mut receiver) = unbounded_channel();
+    let transport = radio_app_state.read().transport(&root_path);
+    let _watcher = match transport.watch(&root_path, sender) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            info!("Failed to watch {root_path:?}: {err:?}");
+            return;
+        }
+    };
+
+    while let Some(path) = receiver.recv().await {
+        // Changes usually come in bursts
+        let mut changed_folders = BTreeSet::new();
+        let mut tasks_changed = is_tasks_file(&path, &root_path);
+        changed_folders.extend(path.parent().map(Path::to_path_buf));
+        while let Ok(path) = receiver.try_recv() {
+            tasks_changed |= is_tasks_file(&path, &root_path);
+            changed_folders.extend(path.parent().map(Path::to_path_buf));
+        }
+
+        if tasks_changed {
+            spawn(load_tasks(radio_app_state, root_path.clone()));
+        }
+
+        for folder_path in changed_folders {
+            let (old_items, exclude_matcher) = {
+                let app_state = radio_app_state.read();
+                // Stop watching once the folder is not in the explorer anymore
+                let Some(root) = app_state
+                    .file_explorer_folders
+                    .iter()
+                    .find(|folder| folder.path() == &root_path)
+                else {
+                    return;
+                };
+                let Some(old_items) = root.opened_items(&folder_path) else {
+                    continue;
+                };
+                (old_items.to_vec(), app_state.exclude_matcher(&root_path))
+            };
+
+            let Ok(items) = read_folder_as_items(&folder_path, &transport, &exclude_matcher).await
+            else {
+                continue;
+            };
+            let items = items
+                .into_iter()
+                .map(|item| {
+                    old_items
+                        .iter()
+                        .find(|old_item| old_item.path() == item.path())
+                        .cloned()
+                        .unwrap_or(item)
+                })
+                .collect();
+
+            let mut app_state = radio_app_state.write_channel(Channel::FileExplorer);
+            if let Some(root) = app_state
+                .file_explorer_folders
+                .iter_mut()
+                .find(|folder| folder.path() == &root_path)
+            {
+                root.set_folder_state(&folder_path, &FolderState::Opened(items));
+            }
+        }
+    }
+}
+
+/// Files of every opened folder, used to filter the explorer.
+type FilesIndex = Vec<(PathBuf, Vec<PathBuf>)>;
+
+/// Build a tree with only the files whose names match the query and their ancestor folders.
+/// Also returns the position of the best match.
+fn filter_items(files_index: &FilesIndex, query: &str) -> (Vec<FlatItem>, Option<usize>) {
+    let mut flat_items = Vec::new();
+    let mut best_match: Option<((usize, usize), usize)> = None;
+
+    for (root_path, files) in files_index {
+        let matches = files
+            .iter()
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?;
+                let gaps = fuzzy_match(name, query)?;
+                Some((path.as_path(), (gaps, name.len())))
+            })
+            .collect::<HashMap<&Path, (usize, usize)>>();
+
+        if matches.is_empty() {
+            continue;
+        }
+
+        // Folders and files (in this order) inside every folder
+        let mut children = HashMap::<&Path, (BTreeSet<&Path>, BTreeSet<&Path>)>::new();
+        for path in matches.keys() {
+            let mut child = *path;
+            let mut is_file = true;
+            while let Some(parent) = child.parent() {
+                let (folders, files) = children.entry(parent).or_default();
+                let is_new = if is_file {
+                    files.insert(child)
+                } else {
+                    folders.insert(child)
+                };
+                if !is_new || parent == root_path {
+                    break;
+                }
+                child = parent;
+                is_file = false;
+            }
+        }
+
+        let mut pending = vec![(root_path.as_path(), false, 0)];
+        while let Some((path, is_file, depth)) = pending.pop() {
+            if let Some(score) = matches.get(path) {
+                if best_match.map(|(best, _)| *score < best).unwrap_or(true) {
+                    best_match = Some((*score, flat_items.len()));
+                }
+            }
+
+            flat_items.push(FlatItem {
+                path: path.to_path_buf(),
+                is_opened: !is_file,
+                is_file,
+                depth,
+                root_path: root_path.clone(),
+            });
+
+            if let Some((folders, files)) = children.get(path) {
+                let files = files.iter().rev().map(|file| (*file, true, depth + 1));
+                let folders = folders
+                    .iter()
+                    .rev()
+                    .map(|folder| (*folder, false, depth + 1));
+                pending.extend(files.chain(folders));
+            }
+        }
+    }
+
+    (flat_items, best_match.map(|(_, index)| index))
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum TreeTask {
     OpenFolder {
@@ -133,6 +499,9 @@ enum TreeTask {
         file_path: PathBuf,
         root_path: PathBuf,
     },
+    CompareFile {
+        file_path: PathBuf,
+    },
 }
 
 #[allow(non_snake_case)]
@@ -140,16 +509,103 @@ pub fn FileExplorer() -> Element {
     let mut radio_app_state = use_radio::<AppState, Channel>(Channel::FileExplorer);
     let app_state = radio_app_state.read();
     let mut focused_item = use_signal(|| 0);
+    let mut pressing_ctrl = use_signal(|| false);
+    // File selected to be compared with the next selected file
+    let mut compare_selection = use_signal::<Option<PathBuf>>(|| None);
+    let mut filter_query = use_signal(String::new);
+    let mut files_index = use_signal::<Option<FilesIndex>>(|| None);
+    let mut is_indexing = use_signal(|| false);
+    let mut filtered_items = use_signal(Vec::<FlatItem>::new);
 
-    let items = app_state
-        .file_explorer_folders
-        .iter()
-        .flat_map(|tree| tree.flat(0, tree.path()))
-        .collect::<Vec<FlatItem>>();
+    let is_filtering = !filter_query.read().is_empty();
+
+    let items = if is_filtering {
+        filtered_items.read().clone()
+    } else {
+        app_state
+            .file_explorer_folders
+            .iter()
+            .flat_map(|tree| tree.flat(0, tree.path()))
+            .collect::<Vec<FlatItem>>()
+    };
     let items_len = items.len();
+    // The opened folders themselves are closed rather than trashed
+    let focused_entry = items
+        .get(*focused_item.read())
+        .filter(|item| item.path != item.root_path)
+        .map(|item| (item.path.clone(), item.root_path.clone()));
+
+    // Focus the file revealed by the follow mode, only once
+    let revealed_item = app_state
+        .file_explorer_revealed_file
+        .as_ref()
+        .filter(|_| !is_filtering)
+        .and_then(|path| items.iter().position(|item| &item.path == path));
+    use_effect(use_reactive(&revealed_item, move |revealed_item| {
+        if let Some(revealed_item) = revealed_item {
+            focused_item.set(revealed_item);
+            radio_app_state
+                .write_channel(Channel::FileExplorer)
+                .file_explorer_revealed_file = None;
+        }
+    }));
+
+    // Filter the indexed files and focus the best match, so it can be opened with `Enter`
+    let mut apply_filter = move || {
+        if let Some(files_index) = files_index.peek().as_ref() {
+            let (items, best_match) = filter_items(files_index, &filter_query.peek());
+            filtered_items.set(items);
+            focused_item.set(best_match.unwrap_or_default());
+        }
+    };
+
+    let onchange_filter = move |query: String| {
+        let is_empty = query.is_empty();
+        filter_query.set(query);
+
+        if is_empty {
+            // Index again next time so new files are found
+            files_index.set(None);
+            filtered_items.set(Vec::new());
+            focused_item.set(0);
+        } else if files_index.peek().is_some() {
+            apply_filter();
+        } else if !*is_indexing.peek() {
+            is_indexing.set(true);
+            spawn(async move {
+                let roots = {
+                    let app_state = radio_app_state.read();
+                    app_state
+                        .file_explorer_folders
+                        .iter()
+                        .map(|folder| {
+                            let root = folder.path().clone();
+                            let exclude_matcher = app_state.exclude_matcher(&root);
+                            let transport = app_state.transport(&root);
+                            (root, exclude_matcher, transport)
+                        })
+                        .collect::<Vec<(PathBuf, ExcludeMatcher, FSTransport)>>()
+                };
+
+                let mut index = Vec::new();
+                for (root, exclude_matcher, transport) in roots {
+                    let files = collect_files(&root, &transport, &exclude_matcher).await;
+                    index.push((root, files));
+                }
+
+                is_indexing.set(false);
+                if !filter_query.peek().is_empty() {
+                    files_index.set(Some(index));
+                    apply_filter();
+                }
+            });
+        }
+    };
 
     let channel = use_coroutine(move |mut rx| {
         async move {
+            let mut last_opened_file: Option<(PathBuf, Instant)> = None;
+
             while let Some((task, item_index)) = rx.next().await {
                 // Focus the FilesExplorer view if it wasn't focused already
                 let focused_view = *radio_app_state.read().focused_view();
@@ -164,8 +620,16 @@ pub fn FileExplorer() -> Element {
                         folder_path,
                         root_path,
                     } => {
-                        let transport = radio_app_state.read().default_transport.clone();
-                        if let Ok(items) = read_folder_as_items(&folder_path, &transport).await {
+                        let (transport, exclude_matcher) = {
+                            let app_state = radio_app_state.read();
+                            (
+                                app_state.transport(&root_path),
+                                app_state.exclude_matcher(&root_path),
+                            )
+                        };
+                        if let Ok(items) =
+                            read_folder_as_items(&folder_path, &transport, &exclude_matcher).await
+                        {
                             let mut app_state = radio_app_state.write();
                             let folder = app_state
                                 .file_explorer_folders
@@ -191,13 +655,98 @@ pub fn FileExplorer() -> Element {
                         file_path,
                         root_path,
                     } => {
-                        let transport = radio_app_state.read().default_transport.clone();
-                        let content = transport.read_to_string(&file_path).await;
+                        // Opening a file twice in a row keeps its tab open
+                        let is_double_click = last_opened_file
+                            .take()
+                            .filter(|(path, opened_at)| {
+                                *path == file_path && opened_at.elapsed() < DOUBLE_CLICK_DELAY
+                            })
+                            .is_some();
+
+                        if is_double_click {
+                            let mut app_state = radio_app_state.write_channel(Channel::Global);
+                            if let Some((panel, tab)) = app_state.find_editor_tab(&file_path) {
+                                app_state.editor_tab_mut(panel, tab).preview = false;
+                            }
+                            continue;
+                        }
+
+                        last_opened_file = Some((file_path.clone(), Instant::now()));
+
+                        let transport = radio_app_state.read().transport(&file_path);
+                        let content = transport.read(&file_path).await;
                         if let Ok(content) = content {
+                            let read_only = is_read_only(&transport, &file_path).await;
                             let mut app_state = radio_app_state.write_channel(Channel::Global);
-                            EditorTab::open_with(&mut app_state, file_path, root_path, content);
+                            open_file_as_preview(
+                                &mut app_state,
+                                file_path,
+                                root_path,
+                                content,
+                                read_only,
+                            );
                         } else if let Err(err) = content {
-                            println!("Error reading file: {err:?}");
+                            notify(
+                                radio_app_state,
+                                NotificationLevel::Error,
+                                format!("Failed to open {}: {err}", file_name(&file_path)),
+                                None,
+                            );
+                        }
+                    }
+                    TreeTask::CompareFile { file_path } => {
+                        let selection = compare_selection.write().take();
+                        match selection {
+                            Some(selected_path) if selected_path != file_path => {
+                                let (left_transport, right_transport) = {
+                                    let app_state = radio_app_state.read();
+                                    (
+                                        app_state.transport(&selected_path),
+                                        app_state.transport(&file_path),
+                                    )
+                                };
+                                let left = left_transport.read(&selected_path).await;
+                                let right = right_transport.read(&file_path).await;
+                                if let (Ok(left), Ok(right)) = (left, right) {
+                                    let mut app_state =
+                                        radio_app_state.write_channel(Channel::Global);
+                                    DiffTab::open_with(
+                                        &mut app_state,
+                                        format!(
+                                            "diff:{}:{}",
+                                            selected_path.display(),
+                                            file_path.display()
+                                        ),
+                                        format!(
+                                            "{} ↔ {}",
+                                            file_name(&selected_path),
+                                            file_name(&file_path)
+                                        ),
+                                        (
+                                            selected_path.display().to_string(),
+                                            &String::from_utf8_lossy(&left),
+                                        ),
+                                        (
+                                            file_path.display().to_string(),
+                                            &String::from_utf8_lossy(&right),
+                                        ),
+                                    );
+                                } else {
+                                    notify(
+                                        radio_app_state,
+                                        NotificationLevel::Error,
+                                        format!(
+                                            "Failed to read {} or {} to compare them",
+                                            file_name(&selected_path),
+                                            file_name(&file_path)
+                                        ),
+                                        None,
+                                    );
+                                }
+                            }
+                            // Selecting the same file again unselects it
+                            Some(_) => {}
+                            None => compare_selection.set(Some(file_path)),
                         }
                     }
                 }
@@ -211,33 +760,50 @@ pub fn FileExplorer() -> Element {
             let folder = rfd::AsyncFileDialog::new().pick_folder().await;
 
             if let Some(folder) = folder {
-                let transport = radio_app_state.read().default_transport.clone();
-
                 let path = folder.path().to_owned();
-                let items = read_folder_as_items(&path, &transport)
+
+                let (transport, exclude_matcher) = {
+                    let app_state = radio_app_state.read();
+                    (app_state.transport(&path), app_state.exclude_matcher(&path))
+                };
+
+                let items = read_folder_as_items(&path, &transport, &exclude_matcher)
                     .await
                     .unwrap_or_default();
 
                 let mut app_state = radio_app_state.write();
 
                 app_state.open_folder(ExplorerItem::Folder {
-                    path,
+                    path: path.clone(),
                     state: FolderState::Opened(items),
                 });
 
                 app_state.set_focused_view(EditorView::FilesExplorer);
+                spawn(index_folder(radio_app_state, path.clone()));
+                spawn(load_tasks(radio_app_state, path.clone()));
+                spawn(watch_folder(radio_app_state, path));
             }
         });
     };
 
+    let onkeyup = move |ev: KeyboardEvent| {
+        if ev.key == Key::Control {
+            pressing_ctrl.set(false);
+        }
+    };
+
     let onkeydown = move |ev: KeyboardEvent| {
+        if ev.key == Key::Control {
+            pressing_ctrl.set(true);
+        }
+
         let is_focused_files_explorer =
             *radio_app_state.read().focused_view() == EditorView::FilesExplorer;
         if is_focused_files_explorer {
             match ev.code {
                 Code::ArrowDown => {
                     focused_item.with_mut(|i| {
-                        if *i < items_len - 1 {
+                        if *i + 1 < items_len {
                             *i += 1
                         }
                     });
@@ -249,12 +815,18 @@ pub fn FileExplorer() -> Element {
                         }
                     });
                 }
+                // While filtering it deletes text of the filter instead
+                Code::Delete if !is_filtering => {
+                    if let Some((path, root_path)) = focused_entry.clone() {
+                        spawn(trash_item(radio_app_state, path, root_path));
+                    }
+                }
                 _ => {}
             }
         }
     };
 
-    if items.is_empty() {
+    if items.is_empty() && !is_filtering {
         rsx!(
             rect {
                 width: "100%",
@@ -270,18 +842,58 @@ pub fn FileExplorer() -> Element {
             }
         )
     } else {
+        let is_focused_files_explorer = *app_state.focused_view() == EditorView::FilesExplorer;
+
         rsx!(rect {
             width: "100%",
             height: "100%",
             onkeydown,
+            onkeyup,
+            rect {
+                padding: "4",
+                onclick: move |_| {
+                    if *radio_app_state.read().focused_view() != EditorView::FilesExplorer {
+                        radio_app_state
+                            .write_channel(Channel::Global)
+                            .set_focused_view(EditorView::FilesExplorer);
+                    }
+                },
+                TextArea {
+                    placeholder: "Filter files...",
+                    value: "{filter_query}",
+                    onchange: onchange_filter,
+                    // `Enter` opens the focused item, which is the best match while filtering
+                    onsubmit: move |_| {},
+                    autofocus: false,
+                    enabled: is_focused_files_explorer,
+                }
+            }
+            if is_filtering && items.is_empty() {
+                label {
+                    padding: "4 10",
+                    color: "rgb(210, 210, 210)",
+                    if *is_indexing.read() {
+                        "Indexing files..."
+                    } else {
+                        "No matches"
+                    }
+                }
+            }
             VirtualScrollView {
                 theme: theme_with!(ScrollViewTheme {
                     width: "100%".into(),
-                    height: "100%".into(),
+                    height: "fill".into(),
                 }),
                 length: items.len(),
                 item_size: 27.0,
-                builder_args: (items, channel, focused_item, radio_app_state),
+                builder_args: (
+                    items,
+                    channel,
+                    focused_item,
+                    radio_app_state,
+                    pressing_ctrl,
+                    compare_selection.read().clone(),
+                ),
                 direction: "vertical",
                 scroll_with_arrows: false,
                 builder: file_explorer_item_builder
@@ -295,10 +907,19 @@ type TreeBuilderOptions = (
     Coroutine<(TreeTask, usize)>,
     Signal<usize>,
     RadioAppState,
+    Signal<bool>,
+    Option<PathBuf>,
 );
 
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
 fn file_explorer_item_builder(index: usize, values: &Option<TreeBuilderOptions>) -> Element {
-    let (items, channel, focused_item, radio_app_state) = values.as_ref().unwrap();
+    let (items, channel, focused_item, radio_app_state, pressing_ctrl, compare_selection) =
+        values.as_ref().unwrap();
     let item: &FlatItem = &items[index];
 
     let path = item.path.to_str().unwrap().to_owned();
@@ -313,15 +934,22 @@ fn file_explorer_item_builder(index: usize, values: &Option<TreeBuilderOptions>)
     let is_focused = *focused_item.read() == index;
 
     if item.is_file {
-        to_owned![channel, item];
+        let is_compare_selected = compare_selection.as_ref() == Some(&item.path);
+        let compare_icon = if is_compare_selected { "⇄ " } else { "" };
+        to_owned![channel, item, pressing_ctrl];
         let onclick = move |_| {
-            channel.send((
+            // `Ctrl` + click selects files to compare
+            let task = if *pressing_ctrl.peek() {
+                TreeTask::CompareFile {
+                    file_path: item.path.clone(),
+                }
+            } else {
                 TreeTask::OpenFile {
                     file_path: item.path.clone(),
                     root_path: item.root_path.clone(),
-                },
-                index,
-            ));
+                }
+            };
+            channel.send((task, index));
         };
         rsx!(
             FileExplorerItem {
@@ -333,7 +961,7 @@ fn file_explorer_item_builder(index: usize, values: &Option<TreeBuilderOptions>)
                 label {
                     max_lines: "1",
                     text_overflow: "ellipsis",
-                    "📃 {name}"
+                    "{compare_icon}📃 {name}"
                 }
             }
         )
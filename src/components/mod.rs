@@ -1,17 +1,29 @@
+mod bookmarks_panel;
 mod commander;
 mod editor_panel;
 mod editor_scroll_view;
 mod file_explorer;
 mod icons;
+mod notifications;
+mod output_panel;
+mod processes_panel;
+mod search_panel;
 mod sidepanel;
 mod status_bar;
 mod tab;
 mod text_area;
+mod working_sets_panel;
 
+pub use bookmarks_panel::*;
 pub use commander::*;
 pub use editor_panel::*;
 pub use editor_scroll_view::*;
 pub use file_explorer::*;
+pub use notifications::*;
+pub use output_panel::*;
+pub use processes_panel::*;
+pub use search_panel::*;
 pub use sidepanel::*;
 pub use status_bar::*;
 pub use text_area::*;
+pub use working_sets_panel::*;
@@ -0,0 +1,224 @@
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::{
+    notifications::run_notification_action,
+    state::{AppState, Channel, Notification},
+};
+
+/// Height of a toast, and the space left between them.
+const TOAST_HEIGHT: f32 = 40.0;
+const TOAST_GAP: f32 = 6.0;
+
+/// Toasts shown at the bottom right corner of the editors, above the status bar.
+#[allow(non_snake_case)]
+#[component]
+pub fn ToastsView(toasts: Vec<Notification>) -> Element {
+    let offset_y = 20.0 + toasts.len() as f32 * (TOAST_HEIGHT + TOAST_GAP);
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "0",
+            layer: "-100",
+            direction: "horizontal",
+            main_align: "end",
+            rect {
+                offset_y: "-{offset_y}",
+                margin: "0 20 0 0",
+                cross_align: "end",
+                for toast in toasts {
+                    ToastView {
+                        key: "{toast.id}",
+                        toast
+                    }
+                }
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn ToastView(toast: Notification) -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+    let id = toast.id;
+
+    let onaction = move |_| {
+        run_notification_action(radio_app_state, id);
+    };
+
+    let onclose = move |_| {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .notifications
+            .hide_toast(id);
+    };
+
+    rsx!(
+        rect {
+            height: "{TOAST_HEIGHT}",
+            margin: "0 0 {TOAST_GAP} 0",
+            padding: "0 6 0 10",
+            direction: "horizontal",
+            cross_align: "center",
+            corner_radius: "6",
+            background: "rgb(45, 45, 45)",
+            border: "1 solid {toast.level.color()}",
+            shadow: "0 4 15 8 rgb(0, 0, 0, 0.3)",
+            label {
+                margin: "0 8 0 0",
+                "{toast.level.icon()}"
+            }
+            label {
+                max_lines: "1",
+                margin: "0 8 0 0",
+                color: "rgb(235, 235, 235)",
+                "{toast.message}"
+            }
+            if let Some(action) = &toast.action {
+                Button {
+                    onpress: onaction,
+                    label {
+                        "{action.text()}"
+                    }
+                }
+            }
+            Button {
+                onpress: onclose,
+                label {
+                    "✕"
+                }
+            }
+        }
+    )
+}
+
+/// Notifications received so far, opened from the status bar.
+#[allow(non_snake_case)]
+pub fn NotificationHistory() -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+
+    let onclear = move |_| {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .notifications
+            .clear();
+    };
+
+    let onclose = move |_| {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .notifications
+            .toggle_history();
+    };
+
+    let app_state = radio_app_state.read();
+    let notifications = app_state
+        .notifications
+        .history()
+        .cloned()
+        .collect::<Vec<Notification>>();
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "0",
+            layer: "-100",
+            direction: "horizontal",
+            main_align: "end",
+            rect {
+                offset_y: "-330",
+                margin: "0 20 0 0",
+                width: "420",
+                height: "320",
+                padding: "4",
+                corner_radius: "6",
+                background: "rgb(45, 45, 45)",
+                border: "1 solid rgb(60, 60, 60)",
+                shadow: "0 4 15 8 rgb(0, 0, 0, 0.3)",
+                rect {
+                    width: "100%",
+                    direction: "horizontal",
+                    cross_align: "center",
+                    label {
+                        width: "fill",
+                        padding: "4 6",
+                        color: "rgb(135, 135, 135)",
+                        "🔔 Notifications"
+                    }
+                    Button {
+                        onpress: onclear,
+                        label {
+                            "Clear"
+                        }
+                    }
+                    Button {
+                        onpress: onclose,
+                        label {
+                            "✕"
+                        }
+                    }
+                }
+                if notifications.is_empty() {
+                    label {
+                        padding: "4 6",
+                        color: "rgb(135, 135, 135)",
+                        "No notifications"
+                    }
+                }
+                ScrollView {
+                    theme: theme_with!(ScrollViewTheme {
+                        width: "100%".into(),
+                        height: "fill".into(),
+                    }),
+                    for notification in notifications {
+                        NotificationItem {
+                            key: "{notification.id}",
+                            notification
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn NotificationItem(notification: Notification) -> Element {
+    let radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+    let id = notification.id;
+
+    let onaction = move |_| {
+        run_notification_action(radio_app_state, id);
+    };
+
+    rsx!(
+        rect {
+            width: "100%",
+            padding: "4 6",
+            direction: "horizontal",
+            cross_align: "center",
+            label {
+                margin: "0 8 0 0",
+                "{notification.level.icon()}"
+            }
+            label {
+                width: "fill",
+                max_lines: "2",
+                text_overflow: "ellipsis",
+                color: "rgb(235, 235, 235)",
+                "{notification.message}"
+            }
+            if let Some(action) = &notification.action {
+                Button {
+                    onpress: onaction,
+                    label {
+                        "{action.text()}"
+                    }
+                }
+            }
+        }
+    )
+}
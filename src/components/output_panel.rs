@@ -0,0 +1,164 @@
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::{
+    output::OutputLine,
+    state::{AppState, Channel, RadioAppState},
+    utils::open_file_at_position,
+};
+
+/// Height of the output panel, including its header.
+pub const OUTPUT_PANEL_HEIGHT: f32 = 200.0;
+
+const OUTPUT_LINE_HEIGHT: f32 = 20.0;
+
+#[allow(non_snake_case)]
+pub fn OutputPanel() -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Output);
+
+    let onclear = move |_| {
+        radio_app_state
+            .write_channel(Channel::Output)
+            .output
+            .clear();
+    };
+
+    let onclose = move |_| {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .toggle_output_panel();
+    };
+
+    let lines_len = radio_app_state.read().output.lines().len();
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "{OUTPUT_PANEL_HEIGHT}",
+            background: "rgb(25, 25, 25)",
+            rect {
+                width: "100%",
+                height: "30",
+                padding: "0 10",
+                direction: "horizontal",
+                cross_align: "center",
+                label {
+                    width: "fill",
+                    color: "rgb(210, 210, 210)",
+                    "Output"
+                }
+                Button {
+                    onpress: onclear,
+                    label {
+                        "Clear"
+                    }
+                }
+                Button {
+                    onpress: onclose,
+                    label {
+                        "Close"
+                    }
+                }
+            }
+            if lines_len == 0 {
+                label {
+                    padding: "4 10",
+                    color: "rgb(135, 135, 135)",
+                    "Run a task from the Commander with `!`"
+                }
+            }
+            VirtualScrollView {
+                theme: theme_with!(ScrollViewTheme {
+                    width: "100%".into(),
+                    height: "fill".into(),
+                }),
+                length: lines_len,
+                item_size: OUTPUT_LINE_HEIGHT,
+                builder_args: radio_app_state,
+                direction: "vertical",
+                builder: output_line_builder
+            }
+        }
+    )
+}
+
+fn output_line_builder(index: usize, radio_app_state: &Option<RadioAppState>) -> Element {
+    let radio_app_state = radio_app_state.unwrap();
+    let line = radio_app_state.read().output.lines().get(index)?.clone();
+
+    rsx!(OutputLineItem {
+        key: "{index}",
+        line,
+        radio_app_state
+    })
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn OutputLineItem(line: OutputLine, radio_app_state: RadioAppState) -> Element {
+    let mut status = use_signal(|| ButtonStatus::Idle);
+    let location = line.location.clone();
+    let is_clickable = location.is_some();
+
+    // Jump to the location mentioned in the line
+    let onclick = move |_| {
+        if let Some(location) = location.clone() {
+            spawn(open_file_at_position(
+                radio_app_state,
+                location.path,
+                location.root_path,
+                location.line,
+                location.column,
+            ));
+        }
+    };
+
+    let onmouseenter = move |_| {
+        if is_clickable {
+            status.set(ButtonStatus::Hovering);
+        }
+    };
+
+    let onmouseleave = move |_| {
+        status.set(ButtonStatus::Idle);
+    };
+
+    let background = match *status.read() {
+        ButtonStatus::Hovering => "rgb(40, 40, 40)",
+        ButtonStatus::Idle => "transparent",
+    };
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "{OUTPUT_LINE_HEIGHT}",
+            padding: "0 10",
+            main_align: "center",
+            background,
+            onclick,
+            onmouseenter,
+            onmouseleave,
+            paragraph {
+                width: "100%",
+                max_lines: "1",
+                font_size: "13",
+                font_family: "Jetbrains Mono",
+                {line.spans.iter().enumerate().map(|(i, span)| {
+                    let color = span
+                        .color
+                        .map(|(r, g, b)| format!("rgb({r}, {g}, {b})"))
+                        .unwrap_or_else(|| "rgb(215, 215, 215)".to_string());
+                    let font_weight = if span.bold { "bold" } else { "normal" };
+                    rsx!(
+                        text {
+                            key: "{i}",
+                            color: "{color}",
+                            font_weight: "{font_weight}",
+                            "{span.text}"
+                        }
+                    )
+                })}
+            }
+        }
+    )
+}
@@ -0,0 +1,125 @@
+use std::{collections::HashMap, time::Duration};
+
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+use sysinfo::System;
+
+use crate::{
+    processes::{format_bytes, process_usages, ProcessUsage, SpawnedProcess},
+    state::{AppState, Channel},
+};
+
+/// How often the usage of the processes is updated.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[allow(non_snake_case)]
+pub fn ProcessesPanel() -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+    let mut usages = use_signal(HashMap::<u32, ProcessUsage>::new);
+
+    use_hook(move || {
+        spawn(async move {
+            // CPU usage is measured between refreshes of the same system
+            let mut system = System::new();
+            loop {
+                let processes = radio_app_state.read().processes.processes().to_vec();
+                let new_usages = process_usages(&processes, &mut system);
+
+                // Processes without usage already exited
+                let exited = processes
+                    .iter()
+                    .map(|process| process.pid)
+                    .filter(|pid| !new_usages.contains_key(pid))
+                    .collect::<Vec<u32>>();
+                if !exited.is_empty() {
+                    radio_app_state
+                        .write_channel(Channel::Global)
+                        .processes
+                        .unregister_all(&exited);
+                }
+
+                usages.set(new_usages);
+                tokio::time::sleep(REFRESH_INTERVAL).await;
+            }
+        })
+    });
+
+    let app_state = radio_app_state.read();
+    let processes = app_state.processes.processes();
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            padding: "6",
+            if processes.is_empty() {
+                label {
+                    padding: "4",
+                    color: "rgb(210, 210, 210)",
+                    "No processes running"
+                }
+            }
+            ScrollView {
+                theme: theme_with!(ScrollViewTheme {
+                    width: "100%".into(),
+                    height: "fill".into(),
+                }),
+                for process in processes {
+                    ProcessItem {
+                        key: "{process.pid}",
+                        process: process.clone(),
+                        usage: usages.read().get(&process.pid).copied()
+                    }
+                }
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn ProcessItem(process: SpawnedProcess, usage: Option<ProcessUsage>) -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+
+    let onkill = move |_| {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .kill_process(process.pid);
+    };
+
+    let usage = usage
+        .map(|usage| format!("{:.1}% CPU · {}", usage.cpu, format_bytes(usage.memory)))
+        .unwrap_or_else(|| "...".to_string());
+
+    rsx!(
+        rect {
+            width: "100%",
+            padding: "4",
+            direction: "horizontal",
+            cross_align: "center",
+            rect {
+                width: "fill",
+                label {
+                    max_lines: "1",
+                    text_overflow: "ellipsis",
+                    color: "rgb(235, 235, 235)",
+                    "{process.name}"
+                }
+                label {
+                    color: "rgb(135, 135, 135)",
+                    "{process.kind} · PID {process.pid}"
+                }
+                label {
+                    color: "rgb(135, 135, 135)",
+                    "{usage}"
+                }
+            }
+            Button {
+                onpress: onkill,
+                label {
+                    "Kill"
+                }
+            }
+        }
+    )
+}
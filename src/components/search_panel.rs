@@ -0,0 +1,271 @@
+use std::{ops::Range, path::PathBuf};
+
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::{
+    search::{replace_in_workspace, search_workspace, FileMatches, SearchOptions, SearchScope},
+    state::{AppState, Channel, EditorView, RadioAppState},
+    utils::open_file_at,
+    TextArea,
+};
+
+#[allow(non_snake_case)]
+pub fn SearchPanel() -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+    let mut query = use_signal(String::new);
+    let mut replacement = use_signal(String::new);
+    let mut options = use_signal(SearchOptions::default);
+    let mut scope = use_signal(SearchScope::default);
+    let mut results = use_signal(Vec::<FileMatches>::new);
+
+    let is_focused = *radio_app_state.read().focused_view() == EditorView::Search;
+
+    let run_search = move || {
+        let query = query.peek().clone();
+        let options = *options.peek();
+        let scope = *scope.peek();
+        spawn(async move {
+            let found = search_workspace(radio_app_state, &query, options, scope).await;
+            results.set(found);
+        });
+    };
+
+    let onchange_query = move |value: String| {
+        if *query.peek() != value {
+            query.set(value);
+        }
+    };
+
+    let onchange_replacement = move |value: String| {
+        if *replacement.peek() != value {
+            replacement.set(value);
+        }
+    };
+
+    let onsubmit_query = move |_| run_search();
+
+    let replace = move || {
+        let query = query.peek().clone();
+        let replacement = replacement.peek().clone();
+        let options = *options.peek();
+        let scope = *scope.peek();
+        spawn(async move {
+            replace_in_workspace(radio_app_state, &query, &replacement, options, scope).await;
+            run_search();
+        });
+    };
+
+    let replace_all = move |_| replace();
+
+    let onsubmit_replacement = move |_| replace();
+
+    let toggle_case_sensitive = move |_| {
+        options.with_mut(|options| options.case_sensitive = !options.case_sensitive);
+        run_search();
+    };
+
+    let toggle_whole_word = move |_| {
+        options.with_mut(|options| options.whole_word = !options.whole_word);
+        run_search();
+    };
+
+    let toggle_preserve_case = move |_| {
+        options.with_mut(|options| options.preserve_case = !options.preserve_case);
+    };
+
+    let toggle_scope = move |_| {
+        scope.with_mut(|scope| {
+            *scope = match scope {
+                SearchScope::Workspace => SearchScope::ActiveFile,
+                SearchScope::ActiveFile => SearchScope::Workspace,
+            }
+        });
+        run_search();
+    };
+
+    let onclick = move |_| {
+        if *radio_app_state.read().focused_view() != EditorView::Search {
+            radio_app_state
+                .write_channel(Channel::Global)
+                .set_focused_view(EditorView::Search);
+        }
+    };
+
+    let current_options = *options.read();
+    let current_scope = *scope.read();
+    let results = results.read();
+    let matches_count = results.iter().map(|file| file.matches.len()).sum::<usize>();
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            padding: "6",
+            onclick,
+            TextArea {
+                placeholder: "Search...",
+                value: "{query}",
+                onchange: onchange_query,
+                onsubmit: onsubmit_query,
+                enabled: is_focused,
+            }
+            TextArea {
+                placeholder: "Replace...",
+                value: "{replacement}",
+                onchange: onchange_replacement,
+                onsubmit: onsubmit_replacement,
+                autofocus: false,
+                enabled: is_focused,
+            }
+            rect {
+                direction: "horizontal",
+                width: "100%",
+                SearchOption {
+                    enabled: current_options.case_sensitive,
+                    onclick: toggle_case_sensitive,
+                    text: "Aa"
+                }
+                SearchOption {
+                    enabled: current_options.whole_word,
+                    onclick: toggle_whole_word,
+                    text: "Word"
+                }
+                SearchOption {
+                    enabled: current_options.preserve_case,
+                    onclick: toggle_preserve_case,
+                    text: "AB→ab"
+                }
+                SearchOption {
+                    enabled: current_scope == SearchScope::ActiveFile,
+                    onclick: toggle_scope,
+                    text: "File"
+                }
+            }
+            rect {
+                direction: "horizontal",
+                width: "100%",
+                cross_align: "center",
+                padding: "4 2",
+                label {
+                    width: "fill",
+                    color: "rgb(180, 180, 180)",
+                    "{matches_count} results in {results.len()} files"
+                }
+                Button {
+                    onpress: replace_all,
+                    label {
+                        "Replace All"
+                    }
+                }
+            }
+            ScrollView {
+                theme: theme_with!(ScrollViewTheme {
+                    width: "100%".into(),
+                    height: "fill".into(),
+                }),
+                {results.iter().map(|file| {
+                    let path = file.path.to_str().unwrap().to_owned();
+                    let relative_path = file.path.strip_prefix(&file.root_path).unwrap_or(&file.path);
+                    let relative_path = relative_path.to_str().unwrap().to_owned();
+                    rsx!(
+                        rect {
+                            key: "{path}",
+                            width: "100%",
+                            label {
+                                padding: "6 4",
+                                max_lines: "1",
+                                text_overflow: "ellipsis",
+                                color: "rgb(235, 235, 235)",
+                                "📃 {relative_path}"
+                            }
+                            {file.matches.iter().map(|line_match| rsx!(
+                                SearchResult {
+                                    key: "{line_match.range.start}",
+                                    radio_app_state,
+                                    path: file.path.clone(),
+                                    root_path: file.root_path.clone(),
+                                    line: line_match.line,
+                                    text: line_match.line_text.clone(),
+                                    range: line_match.range.clone(),
+                                }
+                            ))}
+                        }
+                    )
+                })}
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn SearchOption(enabled: bool, onclick: EventHandler<()>, text: &'static str) -> Element {
+    let background = if enabled {
+        "rgb(70, 70, 70)"
+    } else {
+        "transparent"
+    };
+
+    rsx!(
+        Button {
+            onpress: move |_| onclick.call(()),
+            theme: theme_with!(ButtonTheme {
+                margin: "2".into(),
+                padding: "4 6".into(),
+                background: background.into(),
+            }),
+            label {
+                "{text}"
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn SearchResult(
+    radio_app_state: RadioAppState,
+    path: PathBuf,
+    root_path: PathBuf,
+    line: usize,
+    text: String,
+    range: Range<usize>,
+) -> Element {
+    let mut status = use_signal(|| ButtonStatus::Idle);
+
+    let onclick = move |_| {
+        to_owned![path, root_path, range];
+        spawn(async move {
+            open_file_at(radio_app_state, path, root_path, range).await;
+        });
+    };
+
+    let background = match *status.read() {
+        ButtonStatus::Hovering => "rgb(35, 35, 35)",
+        ButtonStatus::Idle => "transparent",
+    };
+
+    rsx!(
+        rect {
+            onmouseenter: move |_| status.set(ButtonStatus::Hovering),
+            onmouseleave: move |_| status.set(ButtonStatus::Idle),
+            onclick,
+            background,
+            width: "100%",
+            padding: "4 4 4 16",
+            direction: "horizontal",
+            label {
+                color: "rgb(135, 135, 135)",
+                margin: "0 6 0 0",
+                "{line + 1}"
+            }
+            label {
+                max_lines: "1",
+                text_overflow: "ellipsis",
+                color: "rgb(210, 210, 210)",
+                "{text}"
+            }
+        }
+    )
+}
@@ -2,8 +2,12 @@ use dioxus_radio::prelude::use_radio;
 use freya::prelude::*;
 
 use crate::{
-    state::{Channel, EditorSidePanel, EditorView},
-    tabs::{editor::TabEditorUtils, settings::Settings},
+    fs::LineEnding,
+    state::{AppStateUtils, Channel, EditorSidePanel, EditorView},
+    tabs::{
+        editor::{toggle_editor_tab_lsp, zoom_editor_tab, AppStateEditorUtils, TabEditorUtils},
+        settings::Settings,
+    },
     LspStatuses,
 };
 
@@ -18,8 +22,7 @@ pub fn StatusBar(props: StatusBarProps) -> Element {
     let mut radio_app_state = use_radio(Channel::ActiveTab);
 
     let open_settings = move |_| {
-        let mut app_state = radio_app_state.write_channel(Channel::Global);
-        Settings::open_with(&mut app_state);
+        spawn(Settings::open(radio_app_state));
     };
 
     let toggle_file_explorer = move |_| {
@@ -27,7 +30,78 @@ pub fn StatusBar(props: StatusBarProps) -> Element {
         app_state.toggle_side_panel(EditorSidePanel::FileExplorer);
     };
 
+    let toggle_search = move |_| {
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.toggle_side_panel(EditorSidePanel::Search);
+    };
+
+    let toggle_working_sets = move |_| {
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.toggle_side_panel(EditorSidePanel::WorkingSets);
+    };
+
+    let toggle_processes = move |_| {
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.toggle_side_panel(EditorSidePanel::Processes);
+    };
+
+    let toggle_bookmarks = move |_| {
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.toggle_side_panel(EditorSidePanel::Bookmarks);
+    };
+
+    let toggle_indent_with_tabs = move |_| {
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+        if let Some(active_tab) = active_tab {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
+            if let Some(editor_tab) = app_state.try_editor_tab_mut(panel, active_tab) {
+                let indentation = &mut editor_tab.editor.indentation;
+                indentation.with_tabs = !indentation.with_tabs;
+            }
+        }
+    };
+
+    let toggle_line_ending = move |_| {
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+        if let Some(active_tab) = active_tab {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
+            if let Some(editor_tab) = app_state.try_editor_tab_mut(panel, active_tab) {
+                if !editor_tab.editor.read_only {
+                    let format = &mut editor_tab.editor.format;
+                    format.line_ending = match format.line_ending {
+                        LineEnding::Lf => LineEnding::Crlf,
+                        LineEnding::Crlf => LineEnding::Lf,
+                    };
+                }
+            }
+        }
+    };
+
+    let toggle_lsp = move |_| {
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+        if let Some(active_tab) = active_tab {
+            toggle_editor_tab_lsp(radio_app_state, panel, active_tab);
+        }
+    };
+
+    let reset_zoom = move |_| {
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+        if let Some(active_tab) = active_tab {
+            zoom_editor_tab(radio_app_state, panel, active_tab, |_| 1.0);
+        }
+    };
+
+    let toggle_notifications = move |_| {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .notifications
+            .toggle_history();
+    };
+
     let app_state = radio_app_state.read();
+    let unread_notifications = app_state.notifications.unread();
     let panel = app_state.panel(app_state.focused_panel);
     let tab_data = {
         if let Some(active_tab) = panel.active_tab() {
@@ -35,6 +109,16 @@ pub fn StatusBar(props: StatusBarProps) -> Element {
                 (
                     editor_tab.editor.cursor_row_and_col(),
                     editor_tab.editor.editor_type(),
+                    editor_tab.editor.indentation,
+                    editor_tab.editor.format,
+                    // Only for the languages with a language server
+                    editor_tab
+                        .editor
+                        .editor_type
+                        .language_id()
+                        .language_server()
+                        .map(|_| editor_tab.is_lsp_disabled(&app_state)),
+                    (editor_tab.zoom * 100.0).round() as u32,
                 )
             })
         } else {
@@ -60,6 +144,30 @@ pub fn StatusBar(props: StatusBarProps) -> Element {
                         "📁"
                     }
                 }
+                StatusBarItem {
+                    onclick: toggle_search,
+                    label {
+                        "🔍"
+                    }
+                }
+                StatusBarItem {
+                    onclick: toggle_working_sets,
+                    label {
+                        "🗂"
+                    }
+                }
+                StatusBarItem {
+                    onclick: toggle_processes,
+                    label {
+                        "📊"
+                    }
+                }
+                StatusBarItem {
+                    onclick: toggle_bookmarks,
+                    label {
+                        "🔖"
+                    }
+                }
                 StatusBarItem {
                     onclick: open_settings,
                     label {
@@ -83,17 +191,64 @@ pub fn StatusBar(props: StatusBarProps) -> Element {
                 width: "50%",
                 direction: "horizontal",
                 main_align: "end",
-                if let Some(((row, col), editor_type)) = tab_data {
+                if let Some(((row, col), editor_type, indentation, format, lsp_disabled, zoom)) = tab_data {
                     StatusBarItem {
                         label {
                             "Ln {row + 1}, Col {col + 1}"
                         }
                     }
+                    if zoom != 100 {
+                        StatusBarItem {
+                            onclick: reset_zoom,
+                            label {
+                                "{zoom}%"
+                            }
+                        }
+                    }
+                    StatusBarItem {
+                        onclick: toggle_indent_with_tabs,
+                        label {
+                            "{indentation}"
+                        }
+                    }
+                    StatusBarItem {
+                        label {
+                            "{format.encoding}"
+                        }
+                    }
+                    StatusBarItem {
+                        onclick: toggle_line_ending,
+                        label {
+                            "{format.line_ending}"
+                        }
+                    }
                     StatusBarItem {
                         label {
                             "{editor_type.language_id()}"
                         }
                     }
+                    if let Some(lsp_disabled) = lsp_disabled {
+                        StatusBarItem {
+                            onclick: toggle_lsp,
+                            label {
+                                if lsp_disabled {
+                                    "LSP Off"
+                                } else {
+                                    "LSP"
+                                }
+                            }
+                        }
+                    }
+                }
+                StatusBarItem {
+                    onclick: toggle_notifications,
+                    label {
+                        if unread_notifications > 0 {
+                            "🔔 {unread_notifications}"
+                        } else {
+                            "🔔"
+                        }
+                    }
                 }
             }
         }
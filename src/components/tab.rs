@@ -7,8 +7,12 @@ pub fn EditorTab(
     value: String,
     onclick: EventHandler<()>,
     onclickaction: EventHandler<()>,
+    /// Pressed, it might be dragged to another panel.
+    ondragstart: EventHandler<()>,
     is_selected: bool,
     is_edited: bool,
+    is_preview: bool,
+    is_read_only: bool,
 ) -> Element {
     let mut status = use_signal(ButtonStatus::default);
     let theme = use_get_theme();
@@ -42,6 +46,8 @@ pub fn EditorTab(
         background
     };
     let is_hovering = *status.read() == ButtonStatus::Hovering;
+    let font_style = if is_preview { "italic" } else { "normal" };
+    let lock_icon = if is_read_only { "🔒 " } else { "" };
 
     rsx!(
         rect {
@@ -56,6 +62,7 @@ pub fn EditorTab(
                 color: "{color}",
                 background: "{background}",
                 onclick: move |_| onclick.call(()),
+                onmousedown: move |_| ondragstart.call(()),
                 onmouseenter: onmouseenter,
                 onmouseleave: onmouseleave,
                 height: "fill",
@@ -68,7 +75,8 @@ pub fn EditorTab(
                     max_lines: "1",
                     text_overflow: "ellipsis",
                     text_align: "center",
-                    "{value}"
+                    font_style: "{font_style}",
+                    "{lock_icon}{value}"
                 }
                 rect {
                     width: "24",
@@ -11,6 +11,12 @@ pub struct TextAreaProps {
     pub onchange: EventHandler<String>,
     /// Handler for the `onsubmit` event.
     pub onsubmit: EventHandler<String>,
+    /// Focus the TextArea as soon as it's created.
+    #[props(default = true)]
+    pub autofocus: bool,
+    /// Only process the keyboard input while enabled.
+    #[props(default = true)]
+    pub enabled: bool,
 }
 
 #[allow(non_snake_case)]
@@ -23,9 +29,13 @@ pub fn TextArea(props: TextAreaProps) -> Element {
         EditableMode::MultipleLinesSingleEditor,
     );
     let mut focus = use_focus();
+    let autofocus = props.autofocus;
+    let enabled = props.enabled;
 
     use_hook(move || {
-        focus.queue_focus();
+        if autofocus {
+            focus.queue_focus();
+        }
     });
 
     if &props.value != editable.editor().read().rope() {
@@ -33,7 +43,7 @@ pub fn TextArea(props: TextAreaProps) -> Element {
     }
 
     let onkeydown = move |e: Event<KeyboardData>| {
-        if focus.is_focused() {
+        if focus.is_focused() && enabled {
             if let Key::Enter = e.data.key {
                 props.onsubmit.call(editable.editor().peek().to_string());
             } else {
@@ -57,6 +67,12 @@ pub fn TextArea(props: TextAreaProps) -> Element {
         *status.write() = InputStatus::default();
     };
 
+    let onclick = move |_| {
+        if !focus.is_focused() {
+            focus.focus();
+        }
+    };
+
     let focus_id = focus.attribute();
     let cursor_reference = editable.cursor_attr();
     let highlights = editable.highlights_attr(0);
@@ -89,6 +105,7 @@ pub fn TextArea(props: TextAreaProps) -> Element {
             cursor_reference,
             focus_id,
             focusable: "true",
+            onclick,
             role: "textInput",
             paragraph {
                 margin: "6 10",
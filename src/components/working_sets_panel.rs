@@ -0,0 +1,136 @@
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::{
+    state::{AppState, Channel, EditorView},
+    working_sets::{open_working_set, WorkingSet},
+    TextArea,
+};
+
+#[allow(non_snake_case)]
+pub fn WorkingSetsPanel() -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+    let mut name = use_signal(String::new);
+
+    let is_focused = *radio_app_state.read().focused_view() == EditorView::WorkingSets;
+
+    let onchange_name = move |value: String| {
+        if *name.peek() != value {
+            name.set(value);
+        }
+    };
+
+    let onsubmit_name = move |_| {
+        let working_set_name = name.peek().trim().to_owned();
+        if !working_set_name.is_empty() {
+            let mut app_state = radio_app_state.write_channel(Channel::Global);
+            app_state.save_working_set(working_set_name);
+            name.set(String::new());
+        }
+    };
+
+    let onclick = move |_| {
+        if *radio_app_state.read().focused_view() != EditorView::WorkingSets {
+            radio_app_state
+                .write_channel(Channel::Global)
+                .set_focused_view(EditorView::WorkingSets);
+        }
+    };
+
+    let app_state = radio_app_state.read();
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            padding: "6",
+            onclick,
+            TextArea {
+                placeholder: "Save the panel files as...",
+                value: "{name}",
+                onchange: onchange_name,
+                onsubmit: onsubmit_name,
+                enabled: is_focused,
+            }
+            ScrollView {
+                theme: theme_with!(ScrollViewTheme {
+                    width: "100%".into(),
+                    height: "fill".into(),
+                }),
+                {app_state.working_sets.iter().map(|working_set| rsx!(
+                    WorkingSetItem {
+                        key: "{working_set.name}",
+                        working_set: working_set.clone()
+                    }
+                ))}
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn WorkingSetItem(working_set: WorkingSet) -> Element {
+    let mut radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+    let mut status = use_signal(|| ButtonStatus::Idle);
+
+    let onclick = {
+        let working_set = working_set.clone();
+        move |_| {
+            let working_set = working_set.clone();
+            spawn(async move {
+                open_working_set(radio_app_state, working_set).await;
+            });
+        }
+    };
+
+    let onremove = {
+        let name = working_set.name.clone();
+        move |_| {
+            let mut app_state = radio_app_state.write_channel(Channel::Global);
+            app_state.remove_working_set(&name);
+        }
+    };
+
+    let background = match *status.read() {
+        ButtonStatus::Hovering => "rgb(35, 35, 35)",
+        ButtonStatus::Idle => "transparent",
+    };
+
+    rsx!(
+        rect {
+            onmouseenter: move |_| status.set(ButtonStatus::Hovering),
+            onmouseleave: move |_| status.set(ButtonStatus::Idle),
+            background,
+            width: "100%",
+            padding: "4",
+            direction: "horizontal",
+            cross_align: "center",
+            rect {
+                width: "fill",
+                onclick,
+                label {
+                    max_lines: "1",
+                    text_overflow: "ellipsis",
+                    color: "rgb(235, 235, 235)",
+                    "🗂 {working_set.name}"
+                }
+                label {
+                    color: "rgb(135, 135, 135)",
+                    "{working_set.files.len()} files"
+                }
+            }
+            Button {
+                onpress: onremove,
+                theme: theme_with!(ButtonTheme {
+                    padding: "2 6".into(),
+                    background: "none".into(),
+                    border_fill: "none".into(),
+                }),
+                label {
+                    "✕"
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,306 @@
+use std::{borrow::Cow, ops::Range};
+
+/// Past this amount of edits two texts are considered completely different,
+/// this keeps the memory used by the diff bounded.
+const MAX_EDIT_DISTANCE: usize = 1500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Compute the operations needed to turn `old` into `new`.
+pub fn diff_slices<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut ops = vec![DiffOp::Equal; prefix];
+    ops.extend(myers(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    ops.extend(vec![DiffOp::Equal; suffix]);
+    ops
+}
+
+/// Myers' O(ND) diff algorithm.
+fn myers<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = n + m;
+    let limit = max.min(MAX_EDIT_DISTANCE as isize);
+
+    // Furthest reaching `x` of every diagonal `k`, indexed by `k + max + 1`
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    // Snapshots of `v` before every step, only covering the diagonals `-(d + 1)..=(d + 1)`
+    let mut trace = Vec::new();
+    let index = |k: isize| (k + max + 1) as usize;
+
+    for d in 0..=limit {
+        trace.push(v[index(-d - 1)..=index(d + 1)].to_vec());
+
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            v[index(k)] = x;
+
+            if x >= n && y >= m {
+                return backtrack(&trace, n, m);
+            }
+        }
+    }
+
+    // Too different, simply replace everything
+    let mut ops = vec![DiffOp::Delete; n as usize];
+    ops.extend(vec![DiffOp::Insert; m as usize]);
+    ops
+}
+
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<DiffOp> {
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            ops.push(DiffOp::Equal);
+            x -= 1;
+            y -= 1;
+        }
+
+        if d > 0 {
+            if x == prev_x {
+                ops.push(DiffOp::Insert);
+            } else {
+                ops.push(DiffOp::Delete);
+            }
+        }
+
+        x = prev_x;
+        y = prev_y;
+    }
+
+    ops.reverse();
+    ops
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffRowKind {
+    Unchanged,
+    Added,
+    Removed,
+    Modified,
+}
+
+/// A line of one of the compared texts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffLine {
+    /// Index of the line in its text.
+    pub line: usize,
+    pub text: String,
+    /// Chars that changed inside the line.
+    pub highlights: Vec<Range<usize>>,
+}
+
+impl DiffLine {
+    fn new(line: usize, text: &str) -> Self {
+        Self {
+            line,
+            text: text.to_owned(),
+            highlights: Vec::new(),
+        }
+    }
+}
+
+/// A row of the side-by-side view, a missing side is shown as a blank line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffRow {
+    pub kind: DiffRowKind,
+    pub left: Option<DiffLine>,
+    pub right: Option<DiffLine>,
+}
+
+/// Differences that are not worth showing, e.g after reformatting a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DiffOptions {
+    /// Lines that only differ in their whitespace are equal.
+    pub ignore_whitespace: bool,
+    /// Lines that only differ in their line ending (`\n`, `\r\n` or none at all) are equal.
+    pub ignore_line_endings: bool,
+}
+
+/// Lines of a text without their line endings, along with the lines as they are compared.
+fn split_lines(text: &str, options: DiffOptions) -> (Vec<&str>, Vec<Cow<str>>) {
+    text.split_inclusive('\n')
+        .map(|line| {
+            let content = line
+                .strip_suffix('\n')
+                .map(|line| line.strip_suffix('\r').unwrap_or(line))
+                .unwrap_or(line);
+            let line_ending = &line[content.len()..];
+
+            let key = match (options.ignore_whitespace, options.ignore_line_endings) {
+                (false, false) => Cow::Borrowed(line),
+                (false, true) => Cow::Borrowed(content),
+                (true, ignore_line_endings) => {
+                    let mut key = content
+                        .chars()
+                        .filter(|ch| !ch.is_whitespace())
+                        .collect::<String>();
+                    if !ignore_line_endings {
+                        key.push_str(line_ending);
+                    }
+                    Cow::Owned(key)
+                }
+            };
+
+            (content, key)
+        })
+        .unzip()
+}
+
+/// Diff two texts line by line, aligning them in rows.
+pub fn diff_texts(old: &str, new: &str, options: DiffOptions) -> Vec<DiffRow> {
+    let (old_lines, old_keys) = split_lines(old, options);
+    let (new_lines, new_keys) = split_lines(new, options);
+
+    let ops = diff_slices(&old_keys, &new_keys);
+
+    let mut rows = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+
+    for op in ops.into_iter().chain([DiffOp::Equal]) {
+        match op {
+            DiffOp::Delete => {
+                removed.push(i);
+                i += 1;
+            }
+            DiffOp::Insert => {
+                added.push(j);
+                j += 1;
+            }
+            DiffOp::Equal => {
+                push_changes(&mut rows, &old_lines, &new_lines, &removed, &added);
+                removed.clear();
+                added.clear();
+
+                // The chained operation only flushes the pending changes
+                if i < old_lines.len() && j < new_lines.len() {
+                    rows.push(DiffRow {
+                        kind: DiffRowKind::Unchanged,
+                        left: Some(DiffLine::new(i, old_lines[i])),
+                        right: Some(DiffLine::new(j, new_lines[j])),
+                    });
+                }
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    rows
+}
+
+/// Pair the removed lines with the added lines so they can be compared char by char.
+fn push_changes(
+    rows: &mut Vec<DiffRow>,
+    old_lines: &[&str],
+    new_lines: &[&str],
+    removed: &[usize],
+    added: &[usize],
+) {
+    for n in 0..removed.len().max(added.len()) {
+        let left = removed.get(n).map(|i| DiffLine::new(*i, old_lines[*i]));
+        let right = added.get(n).map(|j| DiffLine::new(*j, new_lines[*j]));
+
+        let row = match (left, right) {
+            (Some(mut left), Some(mut right)) => {
+                (left.highlights, right.highlights) = diff_chars(&left.text, &right.text);
+                DiffRow {
+                    kind: DiffRowKind::Modified,
+                    left: Some(left),
+                    right: Some(right),
+                }
+            }
+            (left, None) => DiffRow {
+                kind: DiffRowKind::Removed,
+                left,
+                right: None,
+            },
+            (None, right) => DiffRow {
+                kind: DiffRowKind::Added,
+                left: None,
+                right,
+            },
+        };
+
+        rows.push(row);
+    }
+}
+
+/// Get the ranges of chars that changed in both lines.
+fn diff_chars(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let old_chars = old.chars().collect::<Vec<char>>();
+    let new_chars = new.chars().collect::<Vec<char>>();
+
+    let mut old_ranges: Vec<Range<usize>> = Vec::new();
+    let mut new_ranges: Vec<Range<usize>> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    let push = |ranges: &mut Vec<Range<usize>>, pos: usize| match ranges.last_mut() {
+        Some(range) if range.end == pos => range.end += 1,
+        _ => ranges.push(pos..pos + 1),
+    };
+
+    for op in diff_slices(&old_chars, &new_chars) {
+        match op {
+            DiffOp::Equal => {
+                i += 1;
+                j += 1;
+            }
+            DiffOp::Delete => {
+                push(&mut old_ranges, i);
+                i += 1;
+            }
+            DiffOp::Insert => {
+                push(&mut new_ranges, j);
+                j += 1;
+            }
+        }
+    }
+
+    (old_ranges, new_ranges)
+}
@@ -0,0 +1,107 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use crate::state::AppSettings;
+
+/// A variable loaded from an env file of a workspace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnvVariable {
+    pub name: String,
+    pub value: String,
+    /// File where it was defined.
+    pub path: PathBuf,
+}
+
+/// Unquote a value, only double quoted values have escapes.
+fn parse_value(value: &str) -> String {
+    let value = value.trim();
+
+    if let Some(value) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        let mut unescaped = String::new();
+        let mut chars = value.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => match chars.next() {
+                    Some('n') => unescaped.push('\n'),
+                    Some('t') => unescaped.push('\t'),
+                    Some(ch) => unescaped.push(ch),
+                    None => unescaped.push('\\'),
+                },
+                ch => unescaped.push(ch),
+            }
+        }
+        return unescaped;
+    }
+
+    if let Some(value) = value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        return value.to_string();
+    }
+
+    // Comments can follow unquoted values
+    match value.split_once(" #") {
+        Some((value, _)) => value.trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+/// Variables of a `.env` file, e.g `export NAME="value"`.
+pub fn parse_env_file(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return None;
+            }
+
+            Some((name.to_string(), parse_value(value)))
+        })
+        .collect()
+}
+
+/// Variables applied to the processes started in a workspace, if enabled in the settings.
+///
+/// Files listed later override the variables of the previous ones.
+pub fn workspace_environment(settings: &AppSettings, root_path: &Path) -> Vec<EnvVariable> {
+    let mut variables: Vec<EnvVariable> = Vec::new();
+
+    if !settings.environment.load_env_files {
+        return variables;
+    }
+
+    for file in &settings.environment.env_files {
+        let path = root_path.join(file);
+        let Ok(content) = read_to_string(&path) else {
+            continue;
+        };
+
+        for (name, value) in parse_env_file(&content) {
+            let variable = EnvVariable {
+                name,
+                value,
+                path: path.clone(),
+            };
+            match variables.iter_mut().find(|var| var.name == variable.name) {
+                Some(existing) => *existing = variable,
+                None => variables.push(variable),
+            }
+        }
+    }
+
+    variables
+}
@@ -1,5 +0,0 @@
-mod interface;
-mod local;
-
-pub use interface::*;
-pub use local::*;
@@ -1,15 +1,44 @@
+use std::{path::PathBuf, sync::Arc};
+
+use freya::prelude::spawn;
+
 use crate::{
-    state::{Channel, EditorCommand, EditorView, Panel, RadioAppState},
-    tabs::settings::Settings,
+    components::open_mounted_folder,
+    environment::workspace_environment,
+    fs::{FSMemory, FSTransport},
+    notifications::notify,
+    processes::{watch_process, ProcessKind},
+    scratchpads::{focused_workspace, open_scratchpad},
+    state::{
+        Channel, CommandOutput, EditorCommand, EditorSidePanel, EditorView, NotificationLevel,
+        Panel, RadioAppState,
+    },
+    tabs::{
+        editor::TabEditorUtils, environment::EnvironmentTab, project_stats::ProjectStatsTab,
+        settings::Settings,
+    },
+    terminal::{open_file_terminal, open_workspace_terminal},
+    utils::open_file_at_position,
 };
 
 #[allow(non_snake_case)]
 pub mod GlobalDefaults {
     use freya::events::{Code, KeyboardData, Modifiers};
 
-    use crate::state::{Channel, EditorCommands, EditorView, KeyboardShortcuts, RadioAppState};
+    use crate::state::{
+        Channel, EditorCommands, EditorView, KeyboardShortcuts, RadioAppState, ShortcutContext,
+    };
 
-    use super::{OpenSettingsCommand, SplitPanelCommand, ToggleCommanderCommand};
+    use super::{
+        ClearOutputCommand, ListEnvironmentCommand, NavigateBackCommand, NavigateForwardCommand,
+        NextBookmarkCommand, OpenBookmarksCommand, OpenEnvironmentCommand, OpenFileTerminalCommand,
+        OpenGlobalScratchpadCommand, OpenMemoryFolderCommand, OpenProcessesCommand,
+        OpenProjectStatsCommand, OpenSearchCommand, OpenSettingsCommand, OpenTerminalCommand,
+        OpenWorkingSetsCommand, OpenWorkspaceScratchpadCommand, SplitPanelCommand,
+        StartCleanSessionCommand, ToggleCommanderCommand, ToggleOutputPanelCommand,
+        TogglePinnedCommanderCommand, ToggleSidePanelCommand, ToggleStatusBarCommand,
+        ToggleZenModeCommand,
+    };
 
     pub fn init(
         keyboard_shorcuts: &mut KeyboardShortcuts,
@@ -19,35 +48,98 @@ pub mod GlobalDefaults {
         // Register Commands
         commands.register(SplitPanelCommand(radio_app_state));
         commands.register(ToggleCommanderCommand(radio_app_state));
+        commands.register(TogglePinnedCommanderCommand(radio_app_state));
         commands.register(OpenSettingsCommand(radio_app_state));
+        commands.register(OpenEnvironmentCommand(radio_app_state));
+        commands.register(ListEnvironmentCommand(radio_app_state));
+        commands.register(OpenProjectStatsCommand(radio_app_state));
+        commands.register(OpenSearchCommand(radio_app_state));
+        commands.register(OpenWorkingSetsCommand(radio_app_state));
+        commands.register(OpenProcessesCommand(radio_app_state));
+        commands.register(OpenWorkspaceScratchpadCommand(radio_app_state));
+        commands.register(OpenGlobalScratchpadCommand(radio_app_state));
+        commands.register(OpenMemoryFolderCommand(radio_app_state));
+        commands.register(ToggleSidePanelCommand(radio_app_state));
+        commands.register(ToggleStatusBarCommand(radio_app_state));
+        commands.register(ToggleOutputPanelCommand(radio_app_state));
+        commands.register(ClearOutputCommand(radio_app_state));
+        commands.register(ToggleZenModeCommand(radio_app_state));
+        commands.register(NavigateBackCommand(radio_app_state));
+        commands.register(NavigateForwardCommand(radio_app_state));
+        commands.register(OpenBookmarksCommand(radio_app_state));
+        commands.register(NextBookmarkCommand(radio_app_state));
+        commands.register(StartCleanSessionCommand(radio_app_state));
+        commands.register(OpenTerminalCommand(radio_app_state));
+        commands.register(OpenFileTerminalCommand(radio_app_state));
 
-        // Register Shortcuts
+        // Register Shortcuts, only `Esc` works while typing in the Commander or another input
         keyboard_shorcuts.register(
-            |data: &KeyboardData,
-             commands: &mut EditorCommands,
-             mut radio_app_state: RadioAppState| {
-                let is_pressing_alt = data.modifiers == Modifiers::ALT;
-
+            ShortcutContext::Global,
+            |data: &KeyboardData, commands: &mut EditorCommands, _: RadioAppState| {
                 match data.code {
                     // Pressing `Esc`
                     Code::Escape => {
                         commands.trigger(ToggleCommanderCommand::id());
                     }
-                    // Pressing `Alt E`
-                    Code::KeyE if is_pressing_alt => {
-                        let mut app_state = radio_app_state.write_channel(Channel::Global);
-                        if *app_state.focused_view() == EditorView::FilesExplorer {
-                            app_state.set_focused_view(EditorView::Panels)
-                        } else {
-                            app_state.set_focused_view(EditorView::FilesExplorer)
-                        }
-                    }
 
                     _ => return false,
                 }
                 true
             },
         );
+        for view in [EditorView::Panels, EditorView::FilesExplorer] {
+            keyboard_shorcuts.register(ShortcutContext::View(view), on_workspace_shortcut);
+        }
+    }
+
+    /// Shortcuts of the editors and the files explorer.
+    fn on_workspace_shortcut(
+        data: &KeyboardData,
+        commands: &mut EditorCommands,
+        mut radio_app_state: RadioAppState,
+    ) -> bool {
+        let is_pressing_alt = data.modifiers == Modifiers::ALT;
+        let is_pressing_ctrl = data.modifiers == Modifiers::CONTROL;
+        let is_pressing_ctrl_shift = data.modifiers == Modifiers::CONTROL | Modifiers::SHIFT;
+
+        match data.code {
+            // Pressing `Alt E`
+            Code::KeyE if is_pressing_alt => {
+                let mut app_state = radio_app_state.write_channel(Channel::Global);
+                if *app_state.focused_view() == EditorView::FilesExplorer {
+                    app_state.set_focused_view(EditorView::Panels)
+                } else {
+                    app_state.set_focused_view(EditorView::FilesExplorer)
+                }
+            }
+            // Pressing `Ctrl Shift F`
+            Code::KeyF if is_pressing_ctrl_shift => {
+                commands.trigger(OpenSearchCommand::id());
+            }
+            // Pressing `Ctrl B`
+            Code::KeyB if is_pressing_ctrl => {
+                commands.trigger(ToggleSidePanelCommand::id());
+            }
+            // Pressing `Alt Z`
+            Code::KeyZ if is_pressing_alt => {
+                commands.trigger(ToggleZenModeCommand::id());
+            }
+            // Pressing `Alt ArrowLeft`
+            Code::ArrowLeft if is_pressing_alt => {
+                commands.trigger(NavigateBackCommand::id());
+            }
+            // Pressing `Alt ArrowRight`
+            Code::ArrowRight if is_pressing_alt => {
+                commands.trigger(NavigateForwardCommand::id());
+            }
+            // Pressing `F2`
+            Code::F2 if data.modifiers.is_empty() => {
+                commands.trigger(NextBookmarkCommand::id());
+            }
+
+            _ => return false,
+        }
+        true
     }
 }
 
@@ -120,6 +212,35 @@ impl EditorCommand for ToggleCommanderCommand {
     }
 }
 
+#[derive(Clone)]
+pub struct TogglePinnedCommanderCommand(pub RadioAppState);
+
+impl TogglePinnedCommanderCommand {
+    pub fn id() -> &'static str {
+        "toggle-pinned-commander"
+    }
+}
+
+impl EditorCommand for TogglePinnedCommanderCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Pinned Commander"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.toggle_commander_pinned();
+    }
+}
+
 #[derive(Clone)]
 pub struct OpenSettingsCommand(pub RadioAppState);
 
@@ -142,9 +263,733 @@ impl EditorCommand for OpenSettingsCommand {
         "Open Settings"
     }
 
+    fn run(&self) {
+        let radio_app_state = self.0;
+
+        // The invalid file is opened as it is instead of the settings in use, so it can be fixed
+        let invalid_settings = radio_app_state.read().invalid_settings.clone();
+        if let Some(invalid) = invalid_settings {
+            spawn(open_file_at_position(
+                radio_app_state,
+                invalid.path.clone(),
+                invalid.path,
+                invalid.line,
+                invalid.column,
+            ));
+            return;
+        }
+
+        spawn(Settings::open(radio_app_state));
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenEnvironmentCommand(pub RadioAppState);
+
+impl OpenEnvironmentCommand {
+    pub fn id() -> &'static str {
+        "open-environment"
+    }
+}
+
+impl EditorCommand for OpenEnvironmentCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Show Environment Variables"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        EnvironmentTab::open_with(&mut app_state);
+    }
+}
+
+#[derive(Clone)]
+pub struct ListEnvironmentCommand(pub RadioAppState);
+
+impl ListEnvironmentCommand {
+    pub fn id() -> &'static str {
+        "list-environment"
+    }
+}
+
+impl EditorCommand for ListEnvironmentCommand {
+    fn matches(&self, input: &str) -> bool {
+        let input = input.to_lowercase();
+        input.starts_with("env") || self.text().to_lowercase().contains(&input)
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "env: List Environment Variables"
+    }
+
+    fn run(&self) {
+        // The variables are only listed in the Commander
+    }
+
+    fn run_with_input(&self, _input: &str) -> Option<CommandOutput> {
+        let app_state = self.0.read();
+        let Some(workspace) = focused_workspace(&app_state) else {
+            return Some(CommandOutput::Text("No folder is opened.".to_string()));
+        };
+
+        let variables = workspace_environment(&app_state.settings, &workspace);
+        if variables.is_empty() {
+            return Some(CommandOutput::Text(format!(
+                "No variables are loaded for {}.",
+                workspace.display()
+            )));
+        }
+
+        Some(CommandOutput::Table {
+            headers: vec!["Name".to_string(), "Value".to_string(), "File".to_string()],
+            rows: variables
+                .into_iter()
+                .map(|variable| {
+                    let file = variable
+                        .path
+                        .strip_prefix(&workspace)
+                        .unwrap_or(&variable.path)
+                        .display()
+                        .to_string();
+                    vec![variable.name, variable.value, file]
+                })
+                .collect(),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenProjectStatsCommand(pub RadioAppState);
+
+impl OpenProjectStatsCommand {
+    pub fn id() -> &'static str {
+        "open-project-stats"
+    }
+}
+
+impl EditorCommand for OpenProjectStatsCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Show Project Stats"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        ProjectStatsTab::open_with(&mut app_state);
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenSearchCommand(pub RadioAppState);
+
+impl OpenSearchCommand {
+    pub fn id() -> &'static str {
+        "open-search"
+    }
+}
+
+impl EditorCommand for OpenSearchCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Search"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.open_side_panel(EditorSidePanel::Search);
+        app_state.set_focused_view(EditorView::Search);
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenWorkingSetsCommand(pub RadioAppState);
+
+impl OpenWorkingSetsCommand {
+    pub fn id() -> &'static str {
+        "open-working-sets"
+    }
+}
+
+impl EditorCommand for OpenWorkingSetsCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Working Sets"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.open_side_panel(EditorSidePanel::WorkingSets);
+        app_state.set_focused_view(EditorView::WorkingSets);
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenProcessesCommand(pub RadioAppState);
+
+impl OpenProcessesCommand {
+    pub fn id() -> &'static str {
+        "open-processes"
+    }
+}
+
+impl EditorCommand for OpenProcessesCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Processes"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.open_side_panel(EditorSidePanel::Processes);
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenWorkspaceScratchpadCommand(pub RadioAppState);
+
+impl OpenWorkspaceScratchpadCommand {
+    pub fn id() -> &'static str {
+        "open-workspace-scratchpad"
+    }
+}
+
+impl EditorCommand for OpenWorkspaceScratchpadCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Open Workspace Scratchpad"
+    }
+
+    fn run(&self) {
+        // Without any workspace opened this is the global scratchpad
+        let workspace = focused_workspace(&self.0.read());
+        spawn(open_scratchpad(self.0, workspace));
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenGlobalScratchpadCommand(pub RadioAppState);
+
+impl OpenGlobalScratchpadCommand {
+    pub fn id() -> &'static str {
+        "open-global-scratchpad"
+    }
+}
+
+impl EditorCommand for OpenGlobalScratchpadCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Open Global Scratchpad"
+    }
+
+    fn run(&self) {
+        spawn(open_scratchpad(self.0, None));
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleSidePanelCommand(pub RadioAppState);
+
+impl ToggleSidePanelCommand {
+    pub fn id() -> &'static str {
+        "toggle-side-panel"
+    }
+}
+
+impl EditorCommand for ToggleSidePanelCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Side Panel"
+    }
+
     fn run(&self) {
         let mut radio_app_state = self.0;
         let mut app_state = radio_app_state.write_channel(Channel::Global);
-        Settings::open_with(&mut app_state);
+        app_state.toggle_side_panel_visibility();
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleStatusBarCommand(pub RadioAppState);
+
+impl ToggleStatusBarCommand {
+    pub fn id() -> &'static str {
+        "toggle-status-bar"
+    }
+}
+
+impl EditorCommand for ToggleStatusBarCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Status Bar"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.toggle_status_bar();
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleOutputPanelCommand(pub RadioAppState);
+
+impl ToggleOutputPanelCommand {
+    pub fn id() -> &'static str {
+        "toggle-output-panel"
+    }
+}
+
+impl EditorCommand for ToggleOutputPanelCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Output Panel"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.toggle_output_panel();
+    }
+}
+
+#[derive(Clone)]
+pub struct ClearOutputCommand(pub RadioAppState);
+
+impl ClearOutputCommand {
+    pub fn id() -> &'static str {
+        "clear-output"
+    }
+}
+
+impl EditorCommand for ClearOutputCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Clear Output"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Output);
+        app_state.output.clear();
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleZenModeCommand(pub RadioAppState);
+
+impl ToggleZenModeCommand {
+    pub fn id() -> &'static str {
+        "toggle-zen-mode"
+    }
+}
+
+impl EditorCommand for ToggleZenModeCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Zen Mode"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.toggle_zen_mode();
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenMemoryFolderCommand(pub RadioAppState);
+
+impl OpenMemoryFolderCommand {
+    pub fn id() -> &'static str {
+        "open-memory-folder"
+    }
+}
+
+impl EditorCommand for OpenMemoryFolderCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Open In-Memory Folder"
+    }
+
+    fn run(&self) {
+        let mounted_folders = self.0.read().mounted_transports.len();
+        let root_path = PathBuf::from(format!("/memory/folder-{}", mounted_folders + 1));
+
+        // Its files are lost when closing the editor
+        let readme = (
+            root_path.join("README.md"),
+            b"# In-memory folder\n\nFiles of this folder only live while Valin is open.\n".to_vec(),
+        );
+        let transport: FSTransport = Arc::new(Box::new(FSMemory::new(root_path.clone(), [readme])));
+
+        spawn(open_mounted_folder(self.0, root_path, transport));
+    }
+}
+
+#[derive(Clone)]
+pub struct NavigateBackCommand(pub RadioAppState);
+
+impl NavigateBackCommand {
+    pub fn id() -> &'static str {
+        "navigate-back"
+    }
+}
+
+impl EditorCommand for NavigateBackCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Go Back"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let location = radio_app_state
+            .write_channel(Channel::Global)
+            .navigation
+            .go_back();
+        if let Some(location) = location {
+            spawn(open_file_at_position(
+                radio_app_state,
+                location.path,
+                location.root_path,
+                location.line,
+                location.column,
+            ));
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NavigateForwardCommand(pub RadioAppState);
+
+impl NavigateForwardCommand {
+    pub fn id() -> &'static str {
+        "navigate-forward"
+    }
+}
+
+impl EditorCommand for NavigateForwardCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Go Forward"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let location = radio_app_state
+            .write_channel(Channel::Global)
+            .navigation
+            .go_forward();
+        if let Some(location) = location {
+            spawn(open_file_at_position(
+                radio_app_state,
+                location.path,
+                location.root_path,
+                location.line,
+                location.column,
+            ));
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenBookmarksCommand(pub RadioAppState);
+
+impl OpenBookmarksCommand {
+    pub fn id() -> &'static str {
+        "open-bookmarks"
+    }
+}
+
+impl EditorCommand for OpenBookmarksCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Bookmarks"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        app_state.open_side_panel(EditorSidePanel::Bookmarks);
+    }
+}
+
+#[derive(Clone)]
+pub struct NextBookmarkCommand(pub RadioAppState);
+
+impl NextBookmarkCommand {
+    pub fn id() -> &'static str {
+        "next-bookmark"
+    }
+}
+
+impl EditorCommand for NextBookmarkCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Go to Next Bookmark"
+    }
+
+    fn run(&self) {
+        let radio_app_state = self.0;
+        let bookmark = {
+            let app_state = radio_app_state.read();
+            let panel = app_state.panel(app_state.focused_panel());
+            // Starting from the cursor of the focused editor
+            let editor = panel
+                .active_tab()
+                .and_then(|tab| panel.tab(tab).as_text_editor())
+                .map(|editor_tab| &editor_tab.editor);
+            let from =
+                editor.and_then(|editor| Some((editor.path()?.as_path(), editor.cursor_row())));
+            app_state.next_bookmark(from)
+        };
+
+        if let Some(bookmark) = bookmark {
+            spawn(open_file_at_position(
+                radio_app_state,
+                bookmark.path,
+                bookmark.root_path,
+                bookmark.line,
+                0,
+            ));
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct StartCleanSessionCommand(pub RadioAppState);
+
+impl StartCleanSessionCommand {
+    pub fn id() -> &'static str {
+        "start-clean-session"
+    }
+}
+
+impl EditorCommand for StartCleanSessionCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Start Clean Session"
+    }
+
+    fn run(&self) {
+        let radio_app_state = self.0;
+        // With the same folders, to try to reproduce a problem without the configuration
+        let folders = radio_app_state
+            .read()
+            .file_explorer_folders
+            .iter()
+            .map(|folder| folder.path().clone())
+            .collect::<Vec<PathBuf>>();
+
+        let result = std::env::current_exe().and_then(|exe| {
+            tokio::process::Command::new(exe)
+                .arg("--clean")
+                .args(folders)
+                .spawn()
+        });
+        match result {
+            Ok(child) => watch_process(
+                radio_app_state,
+                child,
+                "valin --clean".to_string(),
+                ProcessKind::Session,
+            ),
+            Err(err) => notify(
+                radio_app_state,
+                NotificationLevel::Error,
+                format!("Failed to start a clean session: {err}"),
+                None,
+            ),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenTerminalCommand(pub RadioAppState);
+
+impl OpenTerminalCommand {
+    pub fn id() -> &'static str {
+        "open-terminal"
+    }
+}
+
+impl EditorCommand for OpenTerminalCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Open Terminal"
+    }
+
+    fn run(&self) {
+        open_workspace_terminal(self.0);
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenFileTerminalCommand(pub RadioAppState);
+
+impl OpenFileTerminalCommand {
+    pub fn id() -> &'static str {
+        "open-file-terminal"
+    }
+}
+
+impl EditorCommand for OpenFileTerminalCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Open Terminal in the Folder of the File"
+    }
+
+    fn run(&self) {
+        open_file_terminal(self.0);
     }
 }
@@ -1,5 +1,15 @@
+mod use_autosave;
+mod use_backups;
 mod use_edit;
+mod use_follow_mode;
+mod use_idle_maintenance;
 mod use_lsp_status;
+mod use_navigation_history;
 
+pub use use_autosave::*;
+pub use use_backups::*;
 pub use use_edit::*;
+pub use use_follow_mode::*;
+pub use use_idle_maintenance::*;
 pub use use_lsp_status::*;
+pub use use_navigation_history::*;
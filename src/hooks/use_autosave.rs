@@ -0,0 +1,32 @@
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use freya::prelude::use_hook;
+
+use crate::state::{AutosaveMode, EditorView, RadioAppState};
+use crate::tabs::editor::{autosave_editor_tab, TabEditorUtils};
+
+/// Save the focused editor when it loses the focus, if the autosave is set to [`AutosaveMode::OnFocusChange`].
+/// Must be used in a component subscribed to the [`Channel::Global`](crate::state::Channel::Global) channel.
+pub fn use_autosave(radio_app_state: RadioAppState) {
+    // Editors are tracked by their file, as the tabs indexes change when closing tabs
+    let previous_focused_file = use_hook(|| Rc::new(RefCell::new(None::<PathBuf>)));
+
+    let (focused_file, autosave) = {
+        let app_state = radio_app_state.read();
+        let panel = app_state.panel(app_state.focused_panel);
+        let focused_file = panel
+            .active_tab()
+            .filter(|_| *app_state.focused_view() == EditorView::Panels)
+            .and_then(|tab| panel.tab(tab).as_text_editor())
+            .and_then(|editor_tab| editor_tab.editor.path().cloned());
+        (focused_file, app_state.settings.editor.autosave)
+    };
+
+    let previous_file = previous_focused_file.replace(focused_file.clone());
+
+    if autosave == AutosaveMode::OnFocusChange && previous_file != focused_file {
+        if let Some(previous_file) = previous_file {
+            autosave_editor_tab(radio_app_state, &previous_file);
+        }
+    }
+}
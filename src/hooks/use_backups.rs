@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use freya::prelude::{spawn, use_hook};
+
+use crate::state::RadioAppState;
+use crate::tabs::editor::{backup_edited_files, offer_backups, BACKUP_INTERVAL};
+
+/// Periodically back up the edited files so their changes survive a crash,
+/// and offer to restore the backups left by the previous session, unless this is a clean one.
+pub fn use_backups(radio_app_state: RadioAppState) {
+    use_hook(move || {
+        if !radio_app_state.read().clean_session {
+            spawn(offer_backups(radio_app_state));
+        }
+
+        spawn(async move {
+            let mut backed_up = HashMap::new();
+            loop {
+                tokio::time::sleep(BACKUP_INTERVAL).await;
+                backup_edited_files(radio_app_state, &mut backed_up).await;
+            }
+        });
+    });
+}
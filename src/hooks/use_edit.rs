@@ -1,6 +1,6 @@
 use dioxus::{dioxus_core::AttributeValue, prelude::use_memo};
 
-use crate::tabs::editor::AppStateEditorUtils;
+use crate::tabs::editor::{AppStateEditorUtils, Motion};
 use freya::common::{CursorLayoutResponse, EventMessage, TextGroupMeasurement};
 use freya::prelude::{keyboard::Modifiers, *};
 use freya_node_state::CursorReference;
@@ -15,9 +15,17 @@ pub struct UseEdit {
     pub(crate) radio: RadioAppState,
     pub(crate) cursor_reference: Memo<CursorReference>,
     pub(crate) dragging: Signal<TextDragging>,
+    /// Dragging the mouse to make a block selection.
+    pub(crate) block_dragging: Signal<bool>,
+    /// Line where dragging over the gutter started, to select whole lines.
+    pub(crate) line_dragging: Signal<Option<usize>>,
+    /// Modifiers being held, mouse events don't carry them.
+    pub(crate) modifiers: Signal<Modifiers>,
     pub(crate) platform: UsePlatform,
     pub(crate) panel_index: usize,
     pub(crate) tab_index: usize,
+    /// Viewport of the editor, there are two in split editors.
+    pub(crate) viewport: usize,
 }
 
 impl UseEdit {
@@ -28,46 +36,149 @@ impl UseEdit {
         ))
     }
 
+    /// Check if the cursor of the editor belongs to this viewport.
+    pub fn is_viewport_focused(&self) -> bool {
+        let app_state = self.radio.read();
+        let editor_tab = app_state.editor_tab(self.panel_index, self.tab_index);
+        editor_tab.is_viewport_focused(self.viewport)
+    }
+
     /// Check if there is any highlight at all.
     pub fn has_any_highlight(&self) -> bool {
         let app_state = self.radio.read();
         let editor_tab = app_state.editor_tab(self.panel_index, self.tab_index);
-        editor_tab
-            .editor
-            .selected
-            .map(|highlight| highlight.0 != highlight.1)
-            .unwrap_or_default()
+        editor_tab.is_viewport_focused(self.viewport)
+            && (editor_tab.editor.block_selection.is_some()
+                || editor_tab
+                    .editor
+                    .selected
+                    .map(|highlight| highlight.0 != highlight.1)
+                    .unwrap_or_default())
     }
 
     /// Create a highlights attribute.
     pub fn highlights_attr(&self, editor_id: usize) -> AttributeValue {
         let app_state = self.radio.read();
         let editor_tab = app_state.editor_tab(self.panel_index, self.tab_index);
+        let editor = &editor_tab.editor;
+        let selection = match editor.block_selection {
+            Some(block_selection) => block_selection
+                .line_columns(&editor.rope, editor_id)
+                .filter(|columns| !columns.is_empty())
+                .map(|columns| {
+                    let line = editor.rope.line(editor_id);
+                    (
+                        line.char_to_utf16_cu(columns.start),
+                        line.char_to_utf16_cu(columns.end),
+                    )
+                }),
+            None => editor.get_visible_selection(editor_id),
+        };
         AttributeValue::any_value(CustomAttributeValues::TextHighlights(
-            editor_tab
-                .editor
-                .get_visible_selection(editor_id)
+            selection
+                .filter(|_| editor_tab.is_viewport_focused(self.viewport))
                 .map(|v| vec![v])
                 .unwrap_or_default(),
         ))
     }
 
+    /// Column of the char closest to some coordinates of a line.
+    fn column_at(&self, coords: CursorPoint) -> usize {
+        let app_state = self.radio.read();
+        let editor_tab = app_state.editor_tab(self.panel_index, self.tab_index);
+        let char_width = editor_tab.editor.metrics.char_width.max(1.0);
+        (coords.x as f32 / char_width).round().max(0.0) as usize
+    }
+
+    /// Select a whole line when pressing its number in the gutter,
+    /// or up to it from the selection (or cursor) when holding `Shift`.
+    pub fn process_gutter_mousedown(&mut self, line: usize) {
+        *self.dragging.write() = TextDragging::None;
+        let extend = self.modifiers.peek().contains(Modifiers::SHIFT);
+
+        let mut app_state = self.radio.write();
+        let editor_tab = app_state.editor_tab_mut(self.panel_index, self.tab_index);
+        editor_tab.focus_viewport(self.viewport);
+        editor_tab.editor.clear_block_selection();
+
+        let editor = &mut editor_tab.editor;
+        let anchor = if extend {
+            let anchor = editor
+                .selected
+                .map(|(from, _)| from)
+                .unwrap_or(editor.cursor_pos());
+            editor.rope.char_to_line(anchor)
+        } else {
+            line
+        };
+        editor.select_line_range(anchor, line);
+        self.line_dragging.set(Some(anchor));
+    }
+
+    /// Extend the whole lines selection while dragging over the gutter (or the lines).
+    pub fn process_gutter_mouseover(&mut self, line: usize) {
+        if let Some(anchor) = *self.line_dragging.peek() {
+            let mut app_state = self.radio.write();
+            let editor = &mut app_state
+                .editor_tab_mut(self.panel_index, self.tab_index)
+                .editor;
+            editor.select_line_range(anchor, line);
+        }
+    }
+
     /// Process a [`EditableEvent`] event.
     pub fn process_event(&mut self, edit_event: &EditableEvent) {
         let res = match edit_event {
             EditableEvent::MouseDown(e, id) => {
                 let coords = e.get_element_coordinates();
+                if self.line_dragging.peek().is_some() {
+                    self.line_dragging.set(None);
+                }
+
+                // Dragging with `Alt Shift` makes a block selection
+                if self
+                    .modifiers
+                    .peek()
+                    .contains(Modifiers::ALT | Modifiers::SHIFT)
+                {
+                    let column = self.column_at(coords);
+                    *self.dragging.write() = TextDragging::None;
+                    self.block_dragging.set(true);
+
+                    let mut app_state = self.radio.write();
+                    let editor_tab = app_state.editor_tab_mut(self.panel_index, self.tab_index);
+                    editor_tab.focus_viewport(self.viewport);
+                    editor_tab.editor.start_block_selection(*id, column);
+                    return;
+                }
 
                 self.dragging.write().set_cursor_coords(coords);
 
                 let mut app_state = self.radio.write();
                 let editor_tab = app_state.editor_tab_mut(self.panel_index, self.tab_index);
+                editor_tab.focus_viewport(self.viewport);
                 editor_tab.editor.clear_selection();
+                editor_tab.editor.clear_block_selection();
 
                 Some((*id, Some(coords), None))
             }
             EditableEvent::MouseOver(e, id) => {
-                if let Some(src) = self.dragging.peek().get_cursor_coords() {
+                if self.line_dragging.peek().is_some() {
+                    self.process_gutter_mouseover(*id);
+                    None
+                } else if *self.block_dragging.peek() {
+                    let column = self.column_at(e.get_element_coordinates());
+                    let mut app_state = self.radio.write();
+                    let editor_tab = app_state.editor_tab_mut(self.panel_index, self.tab_index);
+                    if editor_tab
+                        .editor
+                        .block_selection
+                        .is_some_and(|block_selection| block_selection.head != (*id, column))
+                    {
+                        editor_tab.editor.extend_block_selection(*id, column);
+                    }
+                    None
+                } else if let Some(src) = self.dragging.peek().get_cursor_coords() {
                     let new_dist = e.get_element_coordinates();
 
                     Some((*id, None, Some((src, new_dist))))
@@ -76,6 +187,12 @@ impl UseEdit {
                 }
             }
             EditableEvent::Click => {
+                if *self.block_dragging.peek() {
+                    self.block_dragging.set(false);
+                }
+                if self.line_dragging.peek().is_some() {
+                    self.line_dragging.set(None);
+                }
                 let selection = &mut *self.dragging.write();
                 match selection {
                     TextDragging::FromCursorToPoint { shift, clicked, .. } if *shift => {
@@ -88,6 +205,8 @@ impl UseEdit {
                 None
             }
             EditableEvent::KeyDown(e) => {
+                self.modifiers.set(e.modifiers | key_modifier(&e.key));
+
                 if e.code == Code::ShiftLeft {
                     let dragging = &mut *self.dragging.write();
                     match dragging {
@@ -113,21 +232,143 @@ impl UseEdit {
 
                 let is_plus = e.key == Key::Character("+".to_string());
                 let is_minus = e.key == Key::Character("-".to_string());
+                let is_a = e.code == Code::KeyA;
+                let is_b = e.code == Code::KeyB;
                 let is_e = e.code == Code::KeyE;
                 let is_s = e.code == Code::KeyS;
+                let is_f = e.code == Code::KeyF;
+                let is_j = e.code == Code::KeyJ;
+                let is_k = e.code == Code::KeyK;
+                let is_l = e.code == Code::KeyL;
+                let is_t = e.code == Code::KeyT;
+                let is_z = e.code == Code::KeyZ;
+                let is_slash = e.code == Code::Slash;
+                let is_backslash = e.code == Code::Backslash;
+                let is_period = e.code == Code::Period;
+                let is_enter = e.code == Code::Enter;
+                let is_space = e.code == Code::Space;
+                let is_vertical_arrow = e.code == Code::ArrowUp || e.code == Code::ArrowDown;
+                let is_horizontal_arrow = e.code == Code::ArrowLeft || e.code == Code::ArrowRight;
 
                 if e.code == Code::Escape
-                    || (e.modifiers.contains(Modifiers::ALT) && (is_plus || is_minus || is_e))
-                    || (e.modifiers.contains(Modifiers::CONTROL) && is_s)
+                    || (e.modifiers.contains(Modifiers::ALT)
+                        && (is_plus
+                            || is_minus
+                            || is_a
+                            || is_e
+                            || is_j
+                            || is_z
+                            || is_vertical_arrow
+                            || is_horizontal_arrow))
+                    || (e.modifiers.contains(Modifiers::CONTROL)
+                        && (is_b
+                            || is_s
+                            || is_enter
+                            || is_j
+                            || is_l
+                            || is_t
+                            || is_slash
+                            || is_period
+                            || is_space
+                            || is_vertical_arrow))
+                    || (e.modifiers.contains(Modifiers::CONTROL | Modifiers::SHIFT)
+                        && (is_f || is_k || is_backslash))
                 {
                     return;
                 }
 
                 let mut app_state = self.radio.write();
+                let reindent_on_paste = app_state.settings.editor.reindent_on_paste;
+                let auto_close_pairs = app_state.settings.editor.auto_close_pairs;
                 let editor_tab = app_state.editor_tab_mut(self.panel_index, self.tab_index);
+
+                // Typing, deleting and the clipboard apply to every line of a block selection
+                let history_change = editor_tab.editor.history.current_change();
+                if editor_tab
+                    .editor
+                    .process_block_selection_key(&e.key, &e.code, &e.modifiers)
+                {
+                    if editor_tab.editor.history.current_change() != history_change {
+                        editor_tab.preview = false;
+                    }
+                    *self.dragging.write() = TextDragging::None;
+                    return;
+                }
+
+                let select = e.modifiers.contains(Modifiers::SHIFT);
+                let is_ctrl = e.modifiers.contains(Modifiers::CONTROL);
+                let motion = match e.code {
+                    Code::ArrowLeft if is_ctrl => Some(Motion::WordLeft),
+                    Code::ArrowRight if is_ctrl => Some(Motion::WordRight),
+                    Code::Home if is_ctrl => Some(Motion::TextStart),
+                    Code::End if is_ctrl => Some(Motion::TextEnd),
+                    Code::Home => Some(Motion::LineHome),
+                    Code::End => Some(Motion::LineEnd),
+                    _ => None,
+                };
+                if let Some(motion) = motion {
+                    editor_tab.editor.apply_motion(motion, select);
+                    return;
+                }
+
+                // Read-only editors can only move the cursor, select and copy
+                if editor_tab.editor.read_only {
+                    let is_navigation = matches!(
+                        e.code,
+                        Code::ArrowUp
+                            | Code::ArrowDown
+                            | Code::ArrowLeft
+                            | Code::ArrowRight
+                            | Code::PageUp
+                            | Code::PageDown
+                    );
+                    let is_copy = is_ctrl && matches!(e.code, Code::KeyC | Code::KeyA);
+                    if !is_navigation && !is_copy {
+                        return;
+                    }
+                }
+
+                // Tab indents the selected lines instead of replacing them
+                if e.code == Code::Tab {
+                    let history_change = editor_tab.editor.history.current_change();
+                    if select {
+                        editor_tab.editor.outdent();
+                    } else {
+                        editor_tab.editor.indent();
+                    }
+                    if editor_tab.editor.history.current_change() != history_change {
+                        editor_tab.preview = false;
+                    }
+                    *self.dragging.write() = TextDragging::None;
+                    return;
+                }
+
+                let is_paste = e.code == Code::KeyV && e.modifiers.contains(Modifiers::CONTROL);
+                if is_paste && reindent_on_paste {
+                    if let Ok(text) = editor_tab.editor.get_clipboard().get() {
+                        editor_tab.editor.paste_reindented(&text);
+                        editor_tab.preview = false;
+                        *self.dragging.write() = TextDragging::None;
+                        return;
+                    }
+                }
+
+                // Brackets and strings are closed as they are opened
+                if let Key::Character(text) = &e.key {
+                    let mut chars = text.chars();
+                    if let (Some(ch), None) = (chars.next(), chars.next()) {
+                        if auto_close_pairs && !is_ctrl && editor_tab.editor.auto_close_pair(ch) {
+                            editor_tab.preview = false;
+                            *self.dragging.write() = TextDragging::None;
+                            return;
+                        }
+                    }
+                }
+
                 let event = editor_tab.editor.process_key(&e.key, &e.code, &e.modifiers);
                 if event.contains(TextEvent::TEXT_CHANGED) {
                     editor_tab.editor.run_parser();
+                    editor_tab.preview = false;
                     *self.dragging.write() = TextDragging::None;
                 } else if event.contains(TextEvent::SELECTION_CHANGED) {
                     self.dragging.write();
@@ -136,6 +377,8 @@ impl UseEdit {
                 None
             }
             EditableEvent::KeyUp(e) => {
+                self.modifiers.set(e.modifiers - key_modifier(&e.key));
+
                 if e.code == Code::ShiftLeft {
                     if let TextDragging::FromCursorToPoint { shift, .. } =
                         &mut *self.dragging.write()
@@ -165,8 +408,27 @@ impl UseEdit {
     }
 }
 
-pub fn use_edit(radio: &RadioAppState, panel_index: usize, tab_index: usize) -> UseEdit {
+/// Modifier of a modifier key, to keep track of them as they are pressed and released.
+fn key_modifier(key: &Key) -> Modifiers {
+    match key {
+        Key::Alt => Modifiers::ALT,
+        Key::Control => Modifiers::CONTROL,
+        Key::Shift => Modifiers::SHIFT,
+        Key::Meta => Modifiers::META,
+        _ => Modifiers::empty(),
+    }
+}
+
+pub fn use_edit(
+    radio: &RadioAppState,
+    panel_index: usize,
+    tab_index: usize,
+    viewport: usize,
+) -> UseEdit {
     let dragging = use_signal(|| TextDragging::None);
+    let block_dragging = use_signal(|| false);
+    let line_dragging = use_signal(|| None);
+    let modifiers = use_signal(Modifiers::empty);
     let platform = use_platform();
     let mut cursor_receiver_task = use_signal::<Option<Task>>(|| None);
 
@@ -261,8 +523,12 @@ pub fn use_edit(radio: &RadioAppState, panel_index: usize, tab_index: usize) ->
         radio: *radio,
         cursor_reference,
         dragging,
+        block_dragging,
+        line_dragging,
+        modifiers,
         platform,
         panel_index,
         tab_index,
+        viewport,
     }
 }
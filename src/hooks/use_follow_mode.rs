@@ -0,0 +1,31 @@
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use freya::prelude::{spawn, use_hook};
+
+use crate::components::reveal_file;
+use crate::state::RadioAppState;
+use crate::tabs::editor::TabEditorUtils;
+
+/// Reveal the focused file in the files explorer when it changes, if the follow mode is enabled.
+/// Must be used in a component subscribed to the [`Channel::Global`](crate::state::Channel::Global) channel.
+pub fn use_follow_mode(radio_app_state: RadioAppState) {
+    let previous_focused_file = use_hook(|| Rc::new(RefCell::new(None::<PathBuf>)));
+
+    let (focused_file, follow_mode) = {
+        let app_state = radio_app_state.read();
+        let panel = app_state.panel(app_state.focused_panel);
+        let focused_file = panel
+            .active_tab()
+            .and_then(|tab| panel.tab(tab).as_text_editor())
+            .and_then(|editor_tab| editor_tab.editor.path().cloned());
+        (focused_file, app_state.settings.files.follow_mode)
+    };
+
+    let previous_file = previous_focused_file.replace(focused_file.clone());
+
+    if follow_mode && previous_file != focused_file {
+        if let Some(focused_file) = focused_file {
+            spawn(reveal_file(radio_app_state, focused_file));
+        }
+    }
+}
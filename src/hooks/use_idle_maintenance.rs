@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+use freya::prelude::{spawn, use_hook, use_signal, Readable, Signal};
+
+use crate::output::OutputLine;
+use crate::processes::format_bytes;
+use crate::state::{AppState, Channel, RadioAppState};
+use crate::tabs::editor::TabEditorUtils;
+
+/// Time without pressing any key after which the editors are compacted.
+const IDLE_DELAY: Duration = Duration::from_secs(60);
+/// How often the idle time is checked.
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Compact the text of every editor and trim the syntax blocks of those in the background,
+/// returning how many bytes were reclaimed.
+fn compact_editors(app_state: &mut AppState) -> usize {
+    let mut reclaimed = 0;
+    for panel_index in 0..app_state.panels().len() {
+        let panel = app_state.panel_mut(panel_index);
+        let active_tab = panel.active_tab();
+        for tab_index in 0..panel.tabs().len() {
+            if let Some(editor_tab) = panel.tab_mut(tab_index).as_text_editor_mut() {
+                reclaimed += editor_tab.editor.compact(active_tab != Some(tab_index));
+            }
+        }
+    }
+    reclaimed
+}
+
+/// Once no key has been pressed for a while, compact the memory left fragmented by long editing sessions
+/// and report what was reclaimed in the output panel. This happens once per idle period.
+/// Returns the time of the last activity, to be updated on every key press.
+pub fn use_idle_maintenance(mut radio_app_state: RadioAppState) -> Signal<Instant> {
+    let last_activity = use_signal(Instant::now);
+
+    use_hook(move || {
+        spawn(async move {
+            let mut compacted_activity = None;
+            loop {
+                tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+
+                let activity = *last_activity.peek();
+                if activity.elapsed() < IDLE_DELAY || compacted_activity == Some(activity) {
+                    continue;
+                }
+                compacted_activity = Some(activity);
+
+                let reclaimed =
+                    compact_editors(&mut radio_app_state.write_channel(Channel::AllTabs));
+                if reclaimed > 0 {
+                    radio_app_state.write_channel(Channel::Output).output.push(
+                        OutputLine::message(format!(
+                            "Idle maintenance reclaimed {} from the editors",
+                            format_bytes(reclaimed as u64)
+                        )),
+                    );
+                }
+            }
+        });
+    });
+
+    last_activity
+}
@@ -0,0 +1,51 @@
+use std::{cell::Cell, rc::Rc};
+
+use freya::prelude::{use_effect, use_hook, use_reactive};
+
+use crate::state::{Channel, RadioAppState};
+use crate::tabs::editor::AppStateEditorUtils;
+
+/// Remember the locations the cursor of a focused editor jumps from, to navigate back to them.
+pub fn use_navigation_history(
+    mut radio_app_state: RadioAppState,
+    panel_index: usize,
+    tab_index: usize,
+) {
+    let last_history_change = use_hook(|| Rc::new(Cell::new(None::<usize>)));
+
+    let (location, history_change) = {
+        let app_state = radio_app_state.read();
+        let is_focused = app_state.focused_panel() == panel_index
+            && app_state.panel(panel_index).active_tab() == Some(tab_index);
+        let editor = &app_state.editor_tab(panel_index, tab_index).editor;
+        (
+            editor.navigation_location().filter(|_| is_focused),
+            editor.history.current_change(),
+        )
+    };
+
+    use_effect(use_reactive(
+        &(location, history_change),
+        move |(location, history_change)| {
+            let Some(location) = location else {
+                return;
+            };
+
+            let previous_location = radio_app_state.read().navigation.move_to(location.clone());
+
+            // Edits move the cursor too, but they are not jumps
+            let is_edit = last_history_change
+                .replace(Some(history_change))
+                .is_some_and(|change| change != history_change);
+
+            if let Some(previous_location) = previous_location {
+                if !is_edit && previous_location.is_far_from(&location) {
+                    radio_app_state
+                        .write_channel(Channel::Global)
+                        .navigation
+                        .record(previous_location, &location);
+                }
+            }
+        },
+    ));
+}
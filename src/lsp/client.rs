@@ -1,6 +1,7 @@
+use std::ops::ControlFlow;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::{Arc, Mutex};
-use std::{fmt::Display, ops::ControlFlow};
 
 use async_lsp::concurrency::ConcurrencyLayer;
 use async_lsp::panic::CatchUnwindLayer;
@@ -9,8 +10,14 @@ use async_lsp::tracing::TracingLayer;
 use async_lsp::{LanguageServer, ServerSocket};
 use lsp_types::{
     notification::{Progress, PublishDiagnostics, ShowMessage},
-    DidCloseTextDocumentParams, DidOpenTextDocumentParams, HoverParams, TextDocumentIdentifier,
-    TextDocumentItem,
+    request::ApplyWorkspaceEdit,
+    ApplyWorkspaceEditResponse, CodeActionClientCapabilities, CodeActionKindLiteralSupport,
+    CodeActionLiteralSupport, CodeActionOrCommand, CodeActionParams, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentHighlight,
+    DocumentHighlightClientCapabilities, DocumentHighlightParams, ExecuteCommandParams,
+    HoverParams, TextDocumentClientCapabilities, TextDocumentContentChangeEvent,
+    TextDocumentIdentifier, TextDocumentItem, VersionedTextDocumentIdentifier,
+    WorkspaceClientCapabilities, WorkspaceEdit,
 };
 use lsp_types::{
     ClientCapabilities, InitializeParams, InitializedParams, NumberOrString, ProgressParamsValue,
@@ -20,12 +27,15 @@ use tokio::process::Command;
 use tower::ServiceBuilder;
 use tracing::info;
 
-use crate::{tabs::editor::EditorType, LspStatusSender};
+use crate::{environment::EnvVariable, tabs::editor::EditorType, LspStatusSender};
+
+use super::LanguageId;
 
 struct RouterState {
     pub(crate) indexed: Arc<Mutex<bool>>,
     pub(crate) lsp_sender: LspStatusSender,
     pub(crate) language_server: String,
+    pub(crate) applied_edits: Arc<Mutex<Vec<WorkspaceEdit>>>,
 }
 
 struct Stop;
@@ -35,6 +45,12 @@ pub struct LSPClient {
     pub(crate) indexed: Arc<Mutex<bool>>,
     pub(crate) server_socket: ServerSocket,
     pub(crate) language_id: LanguageId,
+    /// Process id of the language server.
+    pub(crate) pid: Option<u32>,
+    /// Edits the language server asked to apply (e.g while running a command), not applied yet.
+    pub(crate) applied_edits: Arc<Mutex<Vec<WorkspaceEdit>>>,
+    /// Version of the last change sent, shared by all the documents so it always increases.
+    pub(crate) version: Arc<AtomicI32>,
 }
 
 impl LSPClient {
@@ -64,12 +80,56 @@ impl LSPClient {
             .unwrap();
     }
 
+    /// Send the whole new text of a document.
+    pub fn change_file(&mut self, file_uri: Url, file_text: String) {
+        let version = self.version.fetch_add(1, Ordering::Relaxed) + 1;
+        self.server_socket
+            .did_change(DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier {
+                    uri: file_uri,
+                    version,
+                },
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: file_text,
+                }],
+            })
+            .ok();
+    }
+
+    pub async fn code_actions(
+        &mut self,
+        params: CodeActionParams,
+    ) -> Result<Option<Vec<CodeActionOrCommand>>, async_lsp::Error> {
+        self.server_socket.code_action(params).await
+    }
+
+    pub async fn execute_command(
+        &mut self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>, async_lsp::Error> {
+        self.server_socket.execute_command(params).await
+    }
+
+    /// Take the edits the language server asked to apply.
+    pub fn take_applied_edits(&self) -> Vec<WorkspaceEdit> {
+        std::mem::take(&mut *self.applied_edits.lock().unwrap())
+    }
+
     pub async fn hover_file_with_prams(
         &mut self,
         hover_params: HoverParams,
     ) -> Result<Option<lsp_types::Hover>, async_lsp::Error> {
         self.server_socket.hover(hover_params).await
     }
+
+    pub async fn document_highlight(
+        &mut self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>, async_lsp::Error> {
+        self.server_socket.document_highlight(params).await
+    }
 }
 
 #[derive(Clone)]
@@ -89,8 +149,13 @@ impl LspConfig {
     }
 }
 
-pub async fn create_lsp_client(config: LspConfig, lsp_sender: LspStatusSender) -> LSPClient {
+pub async fn create_lsp_client(
+    config: LspConfig,
+    lsp_sender: LspStatusSender,
+    env: Vec<EnvVariable>,
+) -> tokio::io::Result<LSPClient> {
     let indexed = Arc::new(Mutex::new(false));
+    let applied_edits = Arc::new(Mutex::new(Vec::new()));
     let (_, root_path) = config.editor_type.paths().expect("Something went wrong.");
 
     let (mainloop, mut server) =
@@ -99,6 +164,7 @@ pub async fn create_lsp_client(config: LspConfig, lsp_sender: LspStatusSender) -
                 indexed: indexed.clone(),
                 lsp_sender,
                 language_server: config.language_server.clone(),
+                applied_edits: applied_edits.clone(),
             });
             router
             .notification::<Progress>(|client_state, prog| {
@@ -147,6 +213,17 @@ pub async fn create_lsp_client(config: LspConfig, lsp_sender: LspStatusSender) -
                 }
                 ControlFlow::Continue(())
             })
+            .request::<ApplyWorkspaceEdit, _>(|client_state, params| {
+                // Applied by whoever sent the request that caused it
+                client_state.applied_edits.lock().unwrap().push(params.edit);
+                async move {
+                    Ok(ApplyWorkspaceEditResponse {
+                        applied: true,
+                        failure_reason: None,
+                        failed_change: None,
+                    })
+                }
+            })
             .notification::<PublishDiagnostics>(|_, _| ControlFlow::Continue(()))
             .notification::<ShowMessage>(|_, _params| ControlFlow::Continue(()))
             .event(|_, _: Stop| ControlFlow::Break(Ok(())));
@@ -160,11 +237,12 @@ pub async fn create_lsp_client(config: LspConfig, lsp_sender: LspStatusSender) -
 
     let child = Command::new(config.language_server)
         .current_dir(root_path)
+        .envs(env.into_iter().map(|var| (var.name, var.value)))
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::inherit())
-        .spawn()
-        .expect("Failed to start Language Server.");
+        .spawn()?;
+    let pid = child.id();
     let stdout = tokio_util::compat::TokioAsyncReadCompatExt::compat(child.stdout.unwrap());
     let stdin = tokio_util::compat::TokioAsyncWriteCompatExt::compat_write(child.stdin.unwrap());
 
@@ -182,6 +260,26 @@ pub async fn create_lsp_client(config: LspConfig, lsp_sender: LspStatusSender) -
                     work_done_progress: Some(true),
                     ..WindowClientCapabilities::default()
                 }),
+                workspace: Some(WorkspaceClientCapabilities {
+                    apply_edit: Some(true),
+                    ..WorkspaceClientCapabilities::default()
+                }),
+                text_document: Some(TextDocumentClientCapabilities {
+                    code_action: Some(CodeActionClientCapabilities {
+                        code_action_literal_support: Some(CodeActionLiteralSupport {
+                            code_action_kind: CodeActionKindLiteralSupport {
+                                value_set: vec![
+                                    "quickfix".to_string(),
+                                    "refactor".to_string(),
+                                    "source".to_string(),
+                                ],
+                            },
+                        }),
+                        ..CodeActionClientCapabilities::default()
+                    }),
+                    document_highlight: Some(DocumentHighlightClientCapabilities::default()),
+                    ..TextDocumentClientCapabilities::default()
+                }),
                 ..ClientCapabilities::default()
             },
             ..InitializeParams::default()
@@ -190,53 +288,12 @@ pub async fn create_lsp_client(config: LspConfig, lsp_sender: LspStatusSender) -
         .unwrap();
     server.initialized(InitializedParams {}).unwrap();
 
-    LSPClient {
+    Ok(LSPClient {
         indexed,
         server_socket: server,
         language_id: config.editor_type.language_id(),
-    }
-}
-
-#[derive(Default, Clone, Debug, PartialEq, Copy)]
-pub enum LanguageId {
-    Rust,
-    Python,
-    JavaScript,
-    TypeScript,
-    Markdown,
-    #[default]
-    Unknown,
-}
-
-impl Display for LanguageId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Rust => f.write_str("Rust"),
-            Self::Python => f.write_str("Python"),
-            Self::JavaScript => f.write_str("JavaScript"),
-            Self::TypeScript => f.write_str("TypeScript"),
-            Self::Markdown => f.write_str("Markdown"),
-            Self::Unknown => f.write_str("Unknown"),
-        }
-    }
-}
-
-impl LanguageId {
-    pub fn parse(id: &str) -> Self {
-        match id {
-            "rs" => LanguageId::Rust,
-            "py" => LanguageId::Python,
-            "js" => LanguageId::JavaScript,
-            "ts" => LanguageId::TypeScript,
-            "md" => LanguageId::Markdown,
-            _ => LanguageId::Unknown,
-        }
-    }
-
-    pub fn language_server(&self) -> Option<&str> {
-        match self {
-            LanguageId::Rust => Some("rust-analyzer"),
-            _ => None,
-        }
-    }
+        pid,
+        applied_edits,
+        version: Arc::new(AtomicI32::new(0)),
+    })
 }
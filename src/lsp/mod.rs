@@ -5,3 +5,4 @@ mod utils;
 pub use client::*;
 pub use use_lsp::*;
 pub use utils::*;
+pub use valin_core::language::LanguageId;
@@ -1,8 +1,8 @@
 use crate::tabs::editor::{AppStateEditorUtils, EditorType};
 use freya::prelude::*;
 use lsp_types::{
-    Hover, HoverParams, Position, TextDocumentIdentifier, TextDocumentPositionParams, Url,
-    WorkDoneProgressParams,
+    DocumentHighlightParams, Hover, HoverParams, PartialResultParams, Position, Range,
+    TextDocumentIdentifier, TextDocumentPositionParams, Url, WorkDoneProgressParams,
 };
 use std::sync::Arc;
 use tokio_stream::StreamExt;
@@ -18,6 +18,50 @@ use crate::{
 pub enum LspAction {
     Hover(Position),
     Clear,
+    /// Highlight the occurrences of the symbol at a position of the given text.
+    DocumentHighlight {
+        position: Position,
+        text: Rope,
+        history_change: usize,
+    },
+}
+
+/// Occurrences of the symbol under the cursor, as reported by the language server.
+#[derive(Clone, PartialEq)]
+pub struct DocumentHighlights {
+    /// History change of the text the occurrences were found in.
+    pub history_change: usize,
+    pub ranges: Vec<Range>,
+}
+
+impl DocumentHighlights {
+    pub fn contains(&self, position: Position) -> bool {
+        self.ranges
+            .iter()
+            .any(|range| range.start <= position && position <= range.end)
+    }
+
+    /// Highlighted columns (in UTF-16 code units) of a line.
+    pub fn line_highlights(&self, line: usize, line_len: usize) -> Vec<(usize, usize)> {
+        let line = line as u32;
+        self.ranges
+            .iter()
+            .filter(|range| range.start.line <= line && line <= range.end.line)
+            .map(|range| {
+                let start = if range.start.line == line {
+                    range.start.character as usize
+                } else {
+                    0
+                };
+                let end = if range.end.line == line {
+                    range.end.character as usize
+                } else {
+                    line_len
+                };
+                (start, end)
+            })
+            .collect()
+    }
 }
 
 #[derive(Clone, PartialEq, Copy)]
@@ -43,10 +87,10 @@ pub fn use_lsp(
     tab_index: usize,
     radio: RadioAppState,
     mut hover_location: Signal<Option<(u32, Hover)>>,
+    mut document_highlights: Signal<Option<DocumentHighlights>>,
 ) -> UseLsp {
     let args = use_context::<Arc<Args>>();
-    let lsp_config = args
-        .lsp
+    let lsp_config = (args.lsp && !args.clean)
         .then(|| LspConfig::new(editor_type.clone()))
         .flatten();
 
@@ -57,15 +101,20 @@ pub fn use_lsp(
             let (file_uri, file_text) = {
                 let app_state = radio.read();
                 let editor_tab = app_state.editor_tab(panel_index, tab_index);
-                (editor_tab.editor.uri(), editor_tab.editor.text())
+                let file_uri = editor_tab
+                    .editor
+                    .uri()
+                    .filter(|_| !editor_tab.is_lsp_disabled(&app_state));
+                (file_uri, editor_tab.editor.text())
             };
 
             if let Some(file_uri) = file_uri {
                 // Notify language server the file has been opened
                 spawn(async move {
-                    let mut lsp_client =
-                        AppState::get_or_create_lsp_client(radio, &lsp_config).await;
-                    lsp_client.open_file(file_uri, file_text);
+                    let lsp_client = AppState::get_or_create_lsp_client(radio, &lsp_config).await;
+                    if let Some(mut lsp_client) = lsp_client {
+                        lsp_client.open_file(file_uri, file_text);
+                    }
                 });
             }
         });
@@ -79,6 +128,22 @@ pub fn use_lsp(
                 let file_uri = Url::from_file_path(file_path).unwrap();
 
                 while let Some(action) = rx.next().await {
+                    // Files with the language server turned off don't hear from it
+                    let is_lsp_disabled = {
+                        let app_state = radio.read();
+                        match app_state.find_editor_tab(file_path) {
+                            Some((panel, tab)) => {
+                                app_state.editor_tab(panel, tab).is_lsp_disabled(&app_state)
+                            }
+                            None => true,
+                        }
+                    };
+                    if is_lsp_disabled {
+                        *hover_location.write() = None;
+                        *document_highlights.write() = None;
+                        continue;
+                    }
+
                     let lsp = radio.read().lsp(&lsp_config).cloned();
                     let mut lsp = if let Some(lsp) = lsp {
                         let is_indexed = *lsp.indexed.lock().unwrap();
@@ -117,6 +182,40 @@ pub fn use_lsp(
                         LspAction::Clear => {
                             *hover_location.write() = None;
                         }
+                        LspAction::DocumentHighlight {
+                            position,
+                            text,
+                            history_change,
+                        } => {
+                            // The language server only knows about the saved text otherwise
+                            lsp.change_file(file_uri.clone(), text.to_string());
+
+                            let response = lsp
+                                .document_highlight(DocumentHighlightParams {
+                                    text_document_position_params: TextDocumentPositionParams {
+                                        text_document: TextDocumentIdentifier {
+                                            uri: file_uri.clone(),
+                                        },
+                                        position,
+                                    },
+                                    work_done_progress_params: WorkDoneProgressParams::default(),
+                                    partial_result_params: PartialResultParams::default(),
+                                })
+                                .await;
+
+                            let ranges = match response {
+                                Ok(Some(highlights)) => highlights
+                                    .into_iter()
+                                    .map(|highlight| highlight.range)
+                                    .collect::<Vec<Range>>(),
+                                _ => Vec::new(),
+                            };
+                            *document_highlights.write() =
+                                (!ranges.is_empty()).then_some(DocumentHighlights {
+                                    history_change,
+                                    ranges,
+                                });
+                        }
                     }
                 }
             },
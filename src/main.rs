@@ -6,17 +6,27 @@
 mod app;
 mod components;
 mod constants;
-mod fs;
+mod diff;
+mod environment;
 mod global_defaults;
 mod hooks;
 mod keyboard_navigation;
 mod lsp;
 mod metrics;
-mod parser;
+mod notifications;
+mod output;
+mod processes;
+mod project_stats;
+mod scratchpads;
+mod search;
 mod settings;
 mod state;
+mod symbols;
 mod tabs;
+mod tasks;
+mod terminal;
 mod utils;
+mod working_sets;
 
 use std::{path::PathBuf, sync::Arc};
 
@@ -28,6 +38,7 @@ use freya::prelude::*;
 use hooks::*;
 use tracing::info;
 use tracing_subscriber::{EnvFilter, FmtSubscriber};
+use valin_core::{fs, language, parser};
 
 const CUSTOM_THEME: Theme = Theme {
     button: ButtonTheme {
@@ -44,6 +55,11 @@ struct Args {
     #[arg(short, long)]
     lsp: bool,
 
+    /// Start with the default settings and layout, without language servers
+    /// and without restoring anything from previous sessions.
+    #[arg(long)]
+    clean: bool,
+
     // Open certain folders or files.
     #[arg(num_args(0..))]
     paths: Vec<PathBuf>,
@@ -65,6 +81,12 @@ fn main() {
 
     info!("Starting valin. \n{args:#?}");
 
+    let title = if args.clean {
+        "Valin (clean session)"
+    } else {
+        "Valin"
+    };
+
     launch_cfg(
         || {
             rsx!(
@@ -79,7 +101,7 @@ fn main() {
         LaunchConfig::<Arc<Args>>::default()
             .with_width(1280.0)
             .with_height(720.0)
-            .with_title("Valin")
+            .with_title(title)
             .with_state(Arc::new(args)),
     );
 }
@@ -8,11 +8,14 @@ use skia_safe::textlayout::ParagraphBuilder;
 use skia_safe::textlayout::ParagraphStyle;
 use skia_safe::textlayout::TextStyle;
 
+use crate::language::LanguageConfig;
 use crate::parser::*;
 
 pub struct EditorMetrics {
     pub(crate) syntax_blocks: SyntaxBlocks,
     pub(crate) longest_width: f32,
+    /// Width of a character in the editor font, to place the indent guides.
+    pub(crate) char_width: f32,
 }
 
 impl EditorMetrics {
@@ -20,18 +23,21 @@ impl EditorMetrics {
         Self {
             syntax_blocks: SyntaxBlocks::default(),
             longest_width: 0.0,
+            char_width: 0.0,
         }
     }
 
     pub fn measure_longest_line(
         &mut self,
         font_size: f32,
+        font_family: &str,
         rope: &Rope,
         font_collection: &FontCollection,
     ) {
         let mut paragraph_style = ParagraphStyle::default();
         let mut text_style = TextStyle::default();
         text_style.set_font_size(font_size);
+        text_style.set_font_families(&[font_family]);
         paragraph_style.set_text_style(&text_style);
         let mut paragraph_builder = ParagraphBuilder::new(&paragraph_style, font_collection);
 
@@ -61,9 +67,20 @@ impl EditorMetrics {
         paragraph.layout(scalar::MAX);
 
         self.longest_width = paragraph.longest_line();
+
+        let mut char_text_style = TextStyle::default();
+        char_text_style.set_font_size(font_size);
+        char_text_style.set_font_families(&[font_family]);
+        paragraph_style.set_text_style(&char_text_style);
+        let mut paragraph_builder = ParagraphBuilder::new(&paragraph_style, font_collection);
+        paragraph_builder.add_text(" ");
+        let mut paragraph = paragraph_builder.build();
+        paragraph.layout(scalar::MAX);
+
+        self.char_width = paragraph.max_intrinsic_width();
     }
 
-    pub fn run_parser(&mut self, rope: &Rope) {
-        parse(rope, &mut self.syntax_blocks);
+    pub fn run_parser(&mut self, rope: &Rope, language: &LanguageConfig) {
+        parse(rope, &mut self.syntax_blocks, language);
     }
 }
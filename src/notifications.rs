@@ -0,0 +1,88 @@
+use freya::prelude::spawn;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    components::restore_trashed_item,
+    state::{Channel, Notification, NotificationAction, NotificationLevel, RadioAppState},
+    tabs::editor::restore_backups,
+    utils::open_file_at_position,
+};
+
+/// Add a notification to the history and show it as a toast for a while.
+pub fn notify(
+    mut radio_app_state: RadioAppState,
+    level: NotificationLevel,
+    message: impl Into<String>,
+    action: Option<NotificationAction>,
+) {
+    let id = Uuid::new_v4();
+    let message = message.into();
+    info!("[{level}] {message}");
+
+    radio_app_state
+        .write_channel(Channel::Global)
+        .notifications
+        .push(Notification {
+            id,
+            level,
+            message,
+            action,
+        });
+
+    spawn(async move {
+        tokio::time::sleep(level.toast_duration()).await;
+
+        // It might have been replaced or closed in the meantime
+        let is_shown = radio_app_state.read().notifications.is_toast_shown(id);
+        if is_shown {
+            radio_app_state
+                .write_channel(Channel::Global)
+                .notifications
+                .hide_toast(id);
+        }
+    });
+}
+
+/// Run the action of a notification, hiding its toast.
+pub fn run_notification_action(mut radio_app_state: RadioAppState, id: Uuid) {
+    let action = radio_app_state
+        .write_channel(Channel::Global)
+        .notifications
+        .take_action(id);
+    let Some(action) = action else {
+        return;
+    };
+
+    match action {
+        NotificationAction::RestoreFromTrash {
+            path,
+            root_path,
+            closed_files,
+        } => {
+            spawn(restore_trashed_item(
+                radio_app_state,
+                path,
+                root_path,
+                closed_files,
+            ));
+        }
+        NotificationAction::RestoreBackups { paths } => {
+            spawn(restore_backups(radio_app_state, paths));
+        }
+        NotificationAction::OpenFile {
+            path,
+            root_path,
+            line,
+            column,
+        } => {
+            spawn(open_file_at_position(
+                radio_app_state,
+                path,
+                root_path,
+                line,
+                column,
+            ));
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::{Path, PathBuf};
+
+/// Lines kept in the output, the oldest ones are dropped first.
+const MAX_OUTPUT_LINES: usize = 10_000;
+
+/// Colors of the 16 basic ANSI colors, normal ones first and then the bright ones.
+const ANSI_COLORS: [(u8, u8, u8); 16] = [
+    (40, 40, 40),
+    (205, 80, 80),
+    (110, 190, 110),
+    (215, 185, 90),
+    (90, 140, 215),
+    (185, 110, 200),
+    (80, 180, 190),
+    (210, 210, 210),
+    (120, 120, 120),
+    (240, 110, 110),
+    (140, 220, 140),
+    (240, 215, 120),
+    (120, 170, 240),
+    (215, 140, 230),
+    (110, 210, 220),
+    (245, 245, 245),
+];
+
+/// A piece of a line with the same style.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputSpan {
+    pub text: String,
+    pub color: Option<(u8, u8, u8)>,
+    pub bold: bool,
+}
+
+/// A file location mentioned in the output, e.g `src/main.rs:10:5`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputLocation {
+    pub path: PathBuf,
+    pub root_path: PathBuf,
+    /// Starting from 0.
+    pub line: usize,
+    /// Starting from 0.
+    pub column: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputLine {
+    pub spans: Vec<OutputSpan>,
+    pub location: Option<OutputLocation>,
+}
+
+impl OutputLine {
+    /// Parse a line printed by a process running in `cwd`.
+    pub fn parse(text: &str, cwd: &Path, root_path: &Path) -> Self {
+        let spans = parse_ansi(text);
+        let plain_text = spans
+            .iter()
+            .map(|span| span.text.as_str())
+            .collect::<String>();
+        Self {
+            location: parse_location(&plain_text, cwd, root_path),
+            spans,
+        }
+    }
+
+    /// A line without any style, used for the messages of the editor itself.
+    pub fn message(text: impl Into<String>) -> Self {
+        Self {
+            spans: vec![OutputSpan {
+                text: text.into(),
+                color: Some(ANSI_COLORS[8]),
+                bold: false,
+            }],
+            location: None,
+        }
+    }
+}
+
+/// Output of the tasks, shared by all of them.
+#[derive(Default)]
+pub struct TaskOutput {
+    lines: Vec<OutputLine>,
+}
+
+impl TaskOutput {
+    pub fn lines(&self) -> &[OutputLine] {
+        &self.lines
+    }
+
+    pub fn push(&mut self, line: OutputLine) {
+        self.lines.push(line);
+        if self.lines.len() > MAX_OUTPUT_LINES {
+            let overflow = self.lines.len() - MAX_OUTPUT_LINES;
+            self.lines.drain(..overflow);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct AnsiStyle {
+    color: Option<(u8, u8, u8)>,
+    bold: bool,
+}
+
+impl AnsiStyle {
+    /// Apply the parameters of a `Select Graphic Rendition` sequence, e.g `1;31`.
+    fn apply(&mut self, params: &str) {
+        let mut params = params
+            .split(';')
+            .map(|param| param.parse::<u8>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                22 => self.bold = false,
+                30..=37 => self.color = Some(ANSI_COLORS[(param - 30) as usize]),
+                90..=97 => self.color = Some(ANSI_COLORS[(param - 90 + 8) as usize]),
+                39 => self.color = None,
+                38 => match params.next() {
+                    Some(5) => self.color = params.next().map(color_256),
+                    Some(2) => {
+                        let (r, g, b) = (params.next(), params.next(), params.next());
+                        self.color = Some((r.unwrap_or(0), g.unwrap_or(0), b.unwrap_or(0)));
+                    }
+                    _ => {}
+                },
+                // Background colors are not supported, their arguments are skipped
+                48 => match params.next() {
+                    Some(5) => {
+                        params.next();
+                    }
+                    Some(2) => {
+                        params.nth(2);
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Color of the 256 colors palette.
+fn color_256(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_COLORS[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            (level(index / 36), level((index / 6) % 6), level(index % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Split a line into styled spans, escape sequences other than colors are removed.
+pub fn parse_ansi(text: &str) -> Vec<OutputSpan> {
+    let mut spans = Vec::new();
+    let mut style = AnsiStyle::default();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\u{1b}' => {
+                if chars.peek() != Some(&'[') {
+                    chars.next();
+                    continue;
+                }
+                chars.next();
+
+                let mut params = String::new();
+                let mut command = None;
+                for ch in chars.by_ref() {
+                    if ch.is_ascii_alphabetic() || ch == '~' {
+                        command = Some(ch);
+                        break;
+                    }
+                    params.push(ch);
+                }
+
+                if command == Some('m') {
+                    if !current.is_empty() {
+                        spans.push(OutputSpan {
+                            text: std::mem::take(&mut current),
+                            color: style.color,
+                            bold: style.bold,
+                        });
+                    }
+                    style.apply(&params);
+                }
+            }
+            '\r' => {}
+            '\t' => current.push_str("    "),
+            ch => current.push(ch),
+        }
+    }
+
+    if !current.is_empty() {
+        spans.push(OutputSpan {
+            text: current,
+            color: style.color,
+            bold: style.bold,
+        });
+    }
+
+    spans
+}
+
+/// Find the first `path:line` or `path:line:column` of a line.
+pub fn parse_location(text: &str, cwd: &Path, root_path: &Path) -> Option<OutputLocation> {
+    text.split_whitespace().find_map(|word| {
+        let word =
+            word.trim_matches(|ch: char| matches!(ch, '(' | ')' | '[' | ']' | ',' | '\'' | '"'));
+        // Parsed from the end so paths with `:` (e.g `C:\`) are kept
+        let mut parts = word.rsplitn(3, ':');
+        let (path, line, column) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(column), Some(line), Some(path)) if line.parse::<usize>().is_ok() => {
+                (path, line, column.parse::<usize>().unwrap_or(1))
+            }
+            _ => {
+                let (path, line) = word.rsplit_once(':')?;
+                (path, line, 1)
+            }
+        };
+        let line = line.parse::<usize>().ok()?;
+
+        // Avoid things like timestamps
+        if path.is_empty() || path.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+
+        Some(OutputLocation {
+            path: cwd.join(path),
+            root_path: root_path.to_path_buf(),
+            line: line.saturating_sub(1),
+            column: column.saturating_sub(1),
+        })
+    })
+}
@@ -0,0 +1,196 @@
+use std::{collections::HashMap, fmt::Display};
+
+use freya::prelude::spawn;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use tokio::process::Child;
+
+use crate::state::{Channel, RadioAppState};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProcessKind {
+    LanguageServer,
+    Task,
+    Formatter,
+    Terminal,
+    Session,
+}
+
+impl Display for ProcessKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LanguageServer => f.write_str("Language Server"),
+            Self::Task => f.write_str("Task"),
+            Self::Formatter => f.write_str("Formatter"),
+            Self::Terminal => f.write_str("Terminal"),
+            Self::Session => f.write_str("Session"),
+        }
+    }
+}
+
+/// A child process started by the editor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpawnedProcess {
+    pub pid: u32,
+    pub name: String,
+    pub kind: ProcessKind,
+}
+
+/// CPU (percentage) and memory (bytes) used by a process.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct ProcessUsage {
+    pub cpu: f32,
+    pub memory: u64,
+}
+
+/// Child processes of the editor, so they can be inspected and killed.
+#[derive(Default)]
+pub struct ProcessRegistry {
+    processes: Vec<SpawnedProcess>,
+}
+
+impl ProcessRegistry {
+    pub fn register(&mut self, pid: u32, name: String, kind: ProcessKind) {
+        self.processes.push(SpawnedProcess { pid, name, kind });
+    }
+
+    pub fn unregister(&mut self, pid: u32) {
+        self.processes.retain(|process| process.pid != pid);
+    }
+
+    pub fn processes(&self) -> &[SpawnedProcess] {
+        &self.processes
+    }
+
+    pub fn unregister_all(&mut self, pids: &[u32]) {
+        self.processes
+            .retain(|process| !pids.contains(&process.pid));
+    }
+}
+
+/// Keep a child process that nothing else waits for in the registry until it exits.
+pub fn watch_process(
+    mut radio_app_state: RadioAppState,
+    mut child: Child,
+    name: String,
+    kind: ProcessKind,
+) {
+    let Some(pid) = child.id() else {
+        return;
+    };
+    radio_app_state
+        .write_channel(Channel::Global)
+        .processes
+        .register(pid, name, kind);
+
+    spawn(async move {
+        child.wait().await.ok();
+        radio_app_state
+            .write_channel(Channel::Global)
+            .processes
+            .unregister(pid);
+    });
+}
+
+/// Usage of the processes that are still running.
+pub fn process_usages(
+    processes: &[SpawnedProcess],
+    system: &mut System,
+) -> HashMap<u32, ProcessUsage> {
+    processes
+        .iter()
+        .filter_map(|process| {
+            let pid = Pid::from_u32(process.pid);
+            let is_alive = system
+                .refresh_process_specifics(pid, ProcessRefreshKind::new().with_cpu().with_memory());
+            let info = system.process(pid).filter(|_| is_alive)?;
+            Some((
+                process.pid,
+                ProcessUsage {
+                    cpu: info.cpu_usage(),
+                    memory: info.memory(),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Kill a process along with the processes it started (e.g the commands of a shell),
+/// returns whether it was killed.
+pub fn kill_process(pid: u32) -> bool {
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessRefreshKind::new());
+    let root = Pid::from_u32(pid);
+    if system.process(root).is_none() {
+        return false;
+    }
+
+    let leads_group = leads_process_group(pid);
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i];
+        tree.extend(
+            system
+                .processes()
+                .iter()
+                .filter(|(_, process)| process.parent() == Some(parent))
+                .map(|(pid, _)| *pid),
+        );
+        i += 1;
+    }
+
+    // Children first, so they aren't left behind as orphans
+    for pid in tree.iter().skip(1).rev() {
+        if let Some(process) = system.process(*pid) {
+            process.kill();
+        }
+    }
+    let killed = system.process(root).is_some_and(|process| process.kill());
+
+    // Those that were orphaned before are still in the group
+    if leads_group {
+        kill_process_group(pid);
+    }
+
+    killed
+}
+
+/// Whether a process leads its own process group, like the tasks do.
+#[cfg(unix)]
+fn leads_process_group(pid: u32) -> bool {
+    let pid = pid as libc::pid_t;
+    // SAFETY: It only reads the group of the process
+    unsafe { libc::getpgid(pid) == pid }
+}
+
+#[cfg(not(unix))]
+fn leads_process_group(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn kill_process_group(pgid: u32) {
+    // SAFETY: It only sends a signal to the processes of the group
+    unsafe {
+        libc::killpg(pgid as libc::pid_t, libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pgid: u32) {}
+
+/// Human readable size, e.g `12.5 MB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[0])
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
@@ -0,0 +1,102 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::{
+    lsp::LanguageId,
+    search::{collect_files, read_text_file},
+    state::RadioAppState,
+};
+
+/// Files, lines and blank lines of a language.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct LanguageStats {
+    pub language: String,
+    pub files: usize,
+    pub lines: usize,
+    pub blank_lines: usize,
+}
+
+/// Overview of the text files of the opened folders.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ProjectStats {
+    pub files: usize,
+    pub lines: usize,
+    /// Sorted by lines, most used first.
+    pub languages: Vec<LanguageStats>,
+    /// Binary and too big files.
+    pub skipped_files: usize,
+}
+
+/// Name of the language of a file, based on its extension.
+fn language_name(path: &Path) -> String {
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return "Other".to_string();
+    };
+    match LanguageId::parse(extension) {
+        LanguageId::Unknown => extension.to_uppercase(),
+        language_id => language_id.to_string(),
+    }
+}
+
+/// Count the files and lines of every opened folder, by language.
+pub async fn compute_project_stats(radio_app_state: RadioAppState) -> ProjectStats {
+    let roots = {
+        let app_state = radio_app_state.read();
+        app_state
+            .file_explorer_folders
+            .iter()
+            .map(|folder| {
+                let root = folder.path().clone();
+                (
+                    app_state.transport(&root),
+                    app_state.exclude_matcher(&root),
+                    root,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut stats = ProjectStats::default();
+    let mut languages = HashMap::<String, LanguageStats>::new();
+
+    for (transport, exclude_matcher, root) in roots {
+        for path in collect_files(&root, &transport, &exclude_matcher).await {
+            let Some((rope, _)) = read_text_file(&path, &transport).await else {
+                stats.skipped_files += 1;
+                continue;
+            };
+
+            let language = language_name(&path);
+            let language_stats =
+                languages
+                    .entry(language.clone())
+                    .or_insert_with(|| LanguageStats {
+                        language,
+                        ..LanguageStats::default()
+                    });
+
+            // The empty line after the last line break is not counted
+            let mut lines = 0;
+            let mut blank_lines = 0;
+            for line in rope.lines().filter(|line| line.len_chars() > 0) {
+                lines += 1;
+                if line.chars().all(char::is_whitespace) {
+                    blank_lines += 1;
+                }
+            }
+
+            language_stats.files += 1;
+            language_stats.lines += lines;
+            language_stats.blank_lines += blank_lines;
+            stats.files += 1;
+            stats.lines += lines;
+        }
+    }
+
+    stats.languages = languages.into_values().collect();
+    stats.languages.sort_by(|a, b| {
+        b.lines
+            .cmp(&a.lines)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+    stats
+}
@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::{
+    settings::data_path,
+    state::{AppState, RadioAppState},
+    tabs::editor::TabEditorUtils,
+    utils::{focus_file, stable_hash},
+};
+
+fn scratchpads_path() -> Option<PathBuf> {
+    Some(data_path()?.join("scratchpads"))
+}
+
+/// File of the scratchpad of a workspace, or of the global one.
+fn scratchpad_path(workspace: Option<&Path>) -> Option<PathBuf> {
+    let scratchpads_path = scratchpads_path()?;
+
+    let Some(workspace) = workspace else {
+        return Some(scratchpads_path.join("Scratchpad.md"));
+    };
+
+    // Workspaces with the same folder name get different scratchpads
+    let hash = stable_hash([workspace.as_os_str().as_encoded_bytes()]);
+    let folder_name = workspace.file_name()?.to_string_lossy();
+
+    Some(
+        scratchpads_path
+            .join(format!("{hash:x}"))
+            .join(format!("Scratchpad ({folder_name}).md")),
+    )
+}
+
+/// Whether the file is a scratchpad, these are saved while typing.
+pub fn is_scratchpad(path: &Path) -> bool {
+    scratchpads_path().is_some_and(|scratchpads_path| path.starts_with(scratchpads_path))
+}
+
+/// Workspace of the focused file, or the first opened folder.
+pub fn focused_workspace(app_state: &AppState) -> Option<PathBuf> {
+    let panel = app_state.panel(app_state.focused_panel);
+    let focused_root = panel
+        .active_tab()
+        .and_then(|tab| panel.tab(tab).as_text_editor())
+        .and_then(|editor_tab| editor_tab.editor.editor_type.paths())
+        .map(|(_, root_path)| root_path.clone())
+        .filter(|root_path| !is_scratchpad(root_path));
+
+    focused_root.or_else(|| {
+        app_state
+            .file_explorer_folders
+            .first()
+            .map(|folder| folder.path().clone())
+    })
+}
+
+/// Open the scratchpad of a workspace, or the global one, creating it if needed.
+pub async fn open_scratchpad(radio_app_state: RadioAppState, workspace: Option<PathBuf>) {
+    let Some(path) = scratchpad_path(workspace.as_deref()) else {
+        return;
+    };
+    let Some(root_path) = path.parent().map(Path::to_path_buf) else {
+        return;
+    };
+
+    let transport = radio_app_state.read().transport(&path);
+    if transport.metadata(&path).await.is_err() {
+        let created = match transport.create_dir_all(&root_path).await {
+            Ok(()) => transport.write(&path, &[]).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = created {
+            info!("Failed to create the scratchpad {path:?}: {err:?}");
+            return;
+        }
+    }
+
+    focus_file(radio_app_state, path, root_path).await;
+}
@@ -0,0 +1,362 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use ropey::Rope;
+
+use crate::{
+    fs::{ExcludeMatcher, FSEntry, FSTransport, FileContent, TextFormat},
+    notifications::notify,
+    state::{AppStateUtils, Channel, NotificationLevel, RadioAppState},
+    tabs::editor::{AppStateEditorUtils, TabEditorUtils},
+};
+
+/// Files bigger than this are not searched.
+const MAX_SEARCH_FILE_SIZE: usize = 5_000_000;
+
+/// Stop collecting files from a folder after this many.
+const MAX_SEARCH_FILES: usize = 10_000;
+
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct SearchOptions {
+    pub case_sensitive: bool,
+    /// Only match occurrences that are not surrounded by other word characters.
+    pub whole_word: bool,
+    /// Adapt the case of the replacement to the case of every match.
+    pub preserve_case: bool,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum SearchScope {
+    #[default]
+    Workspace,
+    ActiveFile,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct LineMatch {
+    pub line: usize,
+    pub line_text: String,
+    /// Chars range of the match in the whole document.
+    pub range: Range<usize>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FileMatches {
+    pub path: PathBuf,
+    pub root_path: PathBuf,
+    pub matches: Vec<LineMatch>,
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn chars_eq(a: char, b: char, case_sensitive: bool) -> bool {
+    a == b || (!case_sensitive && a.to_lowercase().eq(b.to_lowercase()))
+}
+
+/// Find the chars ranges of every occurrence of `query` in `text`.
+pub fn find_matches(text: &[char], query: &str, options: SearchOptions) -> Vec<Range<usize>> {
+    let query = query.chars().collect::<Vec<char>>();
+    let mut matches = Vec::new();
+
+    if query.is_empty() || query.len() > text.len() {
+        return matches;
+    }
+
+    let mut i = 0;
+    while i + query.len() <= text.len() {
+        let end = i + query.len();
+        let is_match = text[i..end]
+            .iter()
+            .zip(&query)
+            .all(|(a, b)| chars_eq(*a, *b, options.case_sensitive));
+
+        let is_whole_word = !options.whole_word
+            || ((i == 0 || !is_word_char(text[i - 1]))
+                && (end == text.len() || !is_word_char(text[end])));
+
+        if is_match && is_whole_word {
+            matches.push(i..end);
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+
+    matches
+}
+
+/// Adapt `replacement` to the case style of `matched`, e.g `foo`→`bar`, `Foo`→`Bar` and `FOO`→`BAR`.
+pub fn preserve_case(matched: &str, replacement: &str) -> String {
+    let letters = matched
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .collect::<Vec<char>>();
+
+    if letters.is_empty() {
+        return replacement.to_owned();
+    }
+
+    let all_upper = letters.iter().all(|ch| ch.is_uppercase());
+    let all_lower = letters.iter().all(|ch| ch.is_lowercase());
+
+    if all_upper && letters.len() > 1 {
+        replacement.to_uppercase()
+    } else if all_lower {
+        replacement.to_lowercase()
+    } else if letters[0].is_uppercase() {
+        let mut chars = replacement.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    } else {
+        replacement.to_owned()
+    }
+}
+
+/// Get the text that should replace `matched`.
+pub fn replacement_for(matched: &str, replacement: &str, options: SearchOptions) -> String {
+    if options.preserve_case {
+        preserve_case(matched, replacement)
+    } else {
+        replacement.to_owned()
+    }
+}
+
+pub fn search_rope(rope: &Rope, query: &str, options: SearchOptions) -> Vec<LineMatch> {
+    let text = rope.chars().collect::<Vec<char>>();
+    find_matches(&text, query, options)
+        .into_iter()
+        .map(|range| {
+            let line = rope.char_to_line(range.start);
+            LineMatch {
+                line,
+                line_text: rope.line(line).to_string().trim().to_owned(),
+                range,
+            }
+        })
+        .collect()
+}
+
+/// Recursively collect the files of a folder.
+pub async fn collect_files(
+    root: &Path,
+    transport: &FSTransport,
+    exclude_matcher: &ExcludeMatcher,
+) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    // Every folder keeps the `.gitignore` files of the folders above it
+    let root_matcher = exclude_matcher.for_folder(root, transport).await;
+    let mut pending = vec![(root.to_path_buf(), root_matcher)];
+
+    while let Some((folder, exclude_matcher)) = pending.pop() {
+        let Ok(entries) = transport.list(&folder).await else {
+            continue;
+        };
+        for FSEntry { path, is_dir } in entries {
+            if exclude_matcher.is_excluded(&path, is_dir) {
+                continue;
+            }
+            if is_dir {
+                let mut exclude_matcher = exclude_matcher.clone();
+                exclude_matcher.load_gitignore(&path, transport).await;
+                pending.push((path, exclude_matcher));
+            } else {
+                files.push(path);
+            }
+            if files.len() >= MAX_SEARCH_FILES {
+                return files;
+            }
+        }
+    }
+
+    files
+}
+
+/// Get the text of a file, preferring the content of an opened editor over the disk.
+async fn read_rope(
+    radio_app_state: RadioAppState,
+    path: &Path,
+    transport: &FSTransport,
+) -> Option<Rope> {
+    let opened_rope = {
+        let app_state = radio_app_state.read();
+        app_state
+            .find_editor_tab(path)
+            .map(|(panel, tab)| app_state.editor_tab(panel, tab).editor.rope().clone())
+    };
+
+    if opened_rope.is_some() {
+        return opened_rope;
+    }
+
+    read_text_file(path, transport).await.map(|(rope, _)| rope)
+}
+
+/// Read a text file from disk, skipping the binary and too big ones.
+pub async fn read_text_file(path: &Path, transport: &FSTransport) -> Option<(Rope, TextFormat)> {
+    let bytes = transport.read(path).await.ok()?;
+    if bytes.len() > MAX_SEARCH_FILE_SIZE {
+        return None;
+    }
+    match FileContent::detect(path, bytes) {
+        FileContent::Text(text, format) => Some((Rope::from_str(&text), format)),
+        _ => None,
+    }
+}
+
+/// Get the files (and their root folder) that are targeted by a search.
+async fn search_targets(
+    radio_app_state: RadioAppState,
+    scope: SearchScope,
+) -> Vec<(PathBuf, PathBuf)> {
+    match scope {
+        SearchScope::ActiveFile => {
+            let (panel, active_tab) = radio_app_state.get_focused_data();
+            let app_state = radio_app_state.read();
+            active_tab
+                .and_then(|tab| app_state.panel(panel).tab(tab).as_text_editor())
+                .and_then(|editor_tab| editor_tab.editor.editor_type().paths())
+                .map(|(path, root_path)| vec![(path.clone(), root_path.clone())])
+                .unwrap_or_default()
+        }
+        SearchScope::Workspace => {
+            let roots = {
+                let app_state = radio_app_state.read();
+                app_state
+                    .file_explorer_folders
+                    .iter()
+                    .map(|folder| {
+                        let root = folder.path().clone();
+                        let exclude_matcher = app_state.exclude_matcher(&root);
+                        let transport = app_state.transport(&root);
+                        (root, exclude_matcher, transport)
+                    })
+                    .collect::<Vec<(PathBuf, ExcludeMatcher, FSTransport)>>()
+            };
+
+            let mut targets = Vec::new();
+            for (root, exclude_matcher, transport) in roots {
+                for path in collect_files(&root, &transport, &exclude_matcher).await {
+                    targets.push((path, root.clone()));
+                }
+            }
+            targets
+        }
+    }
+}
+
+pub async fn search_workspace(
+    radio_app_state: RadioAppState,
+    query: &str,
+    options: SearchOptions,
+    scope: SearchScope,
+) -> Vec<FileMatches> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results = Vec::new();
+
+    for (path, root_path) in search_targets(radio_app_state, scope).await {
+        let transport = radio_app_state.read().transport(&path);
+        let Some(rope) = read_rope(radio_app_state, &path, &transport).await else {
+            continue;
+        };
+
+        let matches = search_rope(&rope, query, options);
+        if !matches.is_empty() {
+            results.push(FileMatches {
+                path,
+                root_path,
+                matches,
+            })
+        }
+    }
+
+    results
+}
+
+/// Replace every match of `query`, editing opened editors in place and writing the rest of files to disk.
+pub async fn replace_in_workspace(
+    mut radio_app_state: RadioAppState,
+    query: &str,
+    replacement: &str,
+    options: SearchOptions,
+    scope: SearchScope,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    let mut replaced_matches = 0;
+    let mut replaced_files = 0;
+
+    for (path, _) in search_targets(radio_app_state, scope).await {
+        let opened_tab = radio_app_state.read().find_editor_tab(&path);
+
+        if let Some((panel, tab)) = opened_tab {
+            let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+            let editor = &mut app_state.editor_tab_mut(panel, tab).editor;
+            let ranges = search_rope(editor.rope(), query, options)
+                .into_iter()
+                .map(|line_match| line_match.range)
+                .collect::<Vec<Range<usize>>>();
+            let replaced = editor.replace_ranges(&ranges, |matched| {
+                replacement_for(matched, replacement, options)
+            });
+            if replaced > 0 {
+                replaced_matches += replaced;
+                replaced_files += 1;
+            }
+        } else {
+            let transport = radio_app_state.read().transport(&path);
+            let Some((mut rope, format)) = read_text_file(&path, &transport).await else {
+                continue;
+            };
+
+            let matches = search_rope(&rope, query, options);
+            if matches.is_empty() {
+                continue;
+            }
+            let matches_len = matches.len();
+
+            for line_match in matches.into_iter().rev() {
+                let matched = rope.slice(line_match.range.clone()).to_string();
+                rope.remove(line_match.range.clone());
+                rope.insert(
+                    line_match.range.start,
+                    &replacement_for(&matched, replacement, options),
+                );
+            }
+
+            let content = format.encode(&rope.to_string());
+            match transport.write(&path, &content).await {
+                Ok(()) => {
+                    replaced_matches += matches_len;
+                    replaced_files += 1;
+                }
+                Err(err) => {
+                    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                    notify(
+                        radio_app_state,
+                        NotificationLevel::Error,
+                        format!("Failed to replace in {file_name}: {err}"),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+
+    notify(
+        radio_app_state,
+        NotificationLevel::Info,
+        format!("Replaced {replaced_matches} matches in {replaced_files} files"),
+        None,
+    );
+}
@@ -7,7 +7,10 @@ use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
 use tokio::sync::mpsc::channel;
 use tracing::info;
 
-use crate::state::{AppSettings, Channel, RadioAppState};
+use crate::{
+    notifications::notify,
+    state::{AppSettings, Channel, NotificationAction, NotificationLevel, RadioAppState},
+};
 
 pub fn settings_path() -> Option<PathBuf> {
     let home_dir = home::home_dir()?;
@@ -17,21 +20,97 @@ pub fn settings_path() -> Option<PathBuf> {
     Some(settings_path)
 }
 
-pub fn load_settings() -> Option<AppSettings> {
-    let settings_path = settings_path()?;
+/// Directory where the editor keeps its data, e.g the undo histories.
+pub fn data_path() -> Option<PathBuf> {
+    let home_dir = home::home_dir()?;
+
+    let data_path = home_dir.join(".valin");
+
+    Some(data_path)
+}
+
+/// Error in the settings file, and where it is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidSettings {
+    pub path: PathBuf,
+    pub message: String,
+    /// Starting from 0.
+    pub line: usize,
+    /// Starting from 0.
+    pub column: usize,
+}
+
+impl InvalidSettings {
+    fn new(path: PathBuf, content: &str, err: toml::de::Error) -> Self {
+        let offset = err.span().map(|span| span.start).unwrap_or_default();
+        let before = content.get(..offset).unwrap_or(content);
+        let line = before.matches('\n').count();
+        let column = before
+            .rsplit('\n')
+            .next()
+            .map(|line| line.chars().count())
+            .unwrap_or_default();
+        Self {
+            path,
+            message: err.message().to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+pub enum SettingsError {
+    /// The settings file couldn't be created or read.
+    Unavailable,
+    Invalid(InvalidSettings),
+}
+
+pub fn load_settings() -> Result<AppSettings, SettingsError> {
+    let settings_path = settings_path().ok_or(SettingsError::Unavailable)?;
 
     // Create if it doesn't exist
     if std::fs::metadata(&settings_path).is_err() {
         let default_settings_content = toml::to_string(&AppSettings::default()).unwrap();
-        write(&settings_path, default_settings_content).ok()?;
+        write(&settings_path, default_settings_content).map_err(|_| SettingsError::Unavailable)?;
         info!("Settings file didn't exist, so one was created.");
     }
 
-    let settings_content = read_to_string(&settings_path).ok()?;
+    let settings_content =
+        read_to_string(&settings_path).map_err(|_| SettingsError::Unavailable)?;
 
-    let settings: AppSettings = toml::from_str(&settings_content).ok()?;
+    toml::from_str(&settings_content).map_err(|err| {
+        SettingsError::Invalid(InvalidSettings::new(settings_path, &settings_content, err))
+    })
+}
+
+/// Tell that the settings file is invalid and the defaults are used instead,
+/// offering to open the file where the error is.
+pub fn notify_invalid_settings(radio_app_state: RadioAppState, invalid: &InvalidSettings) {
+    notify(
+        radio_app_state,
+        NotificationLevel::Error,
+        format!(
+            "Invalid settings at line {}, using the defaults: {}",
+            invalid.line + 1,
+            invalid.message
+        ),
+        Some(NotificationAction::OpenFile {
+            path: invalid.path.clone(),
+            root_path: invalid.path.clone(),
+            line: invalid.line,
+            column: invalid.column,
+        }),
+    );
+}
 
-    Some(settings)
+/// Tell that the ligatures can't be turned off because the font has no variant without them.
+pub fn notify_unavoidable_ligatures(radio_app_state: RadioAppState, font_family: &str) {
+    notify(
+        radio_app_state,
+        NotificationLevel::Warning,
+        format!("Ligatures can't be turned off, \"{font_family} NL\" is not installed"),
+        None,
+    );
 }
 
 pub async fn watch_settings(mut radio_app_state: RadioAppState) -> Option<()> {
@@ -56,12 +135,37 @@ pub async fn watch_settings(mut radio_app_state: RadioAppState) -> Option<()> {
         .ok()?;
 
     while rx.recv().await.is_some() {
-        let settings = load_settings();
-        if let Some(settings) = settings {
-            let mut app_state = radio_app_state.write_channel(Channel::Settings);
-            app_state.set_settings(settings);
-        } else {
-            info!("Failed to update in-memory settings with the newest changes.")
+        match load_settings() {
+            Ok(settings) => {
+                let mut app_state = radio_app_state.write_channel(Channel::Settings);
+                let had_unavoidable_ligatures =
+                    app_state.settings.editor.has_unavoidable_ligatures();
+                app_state.set_settings(settings);
+                app_state.invalid_settings = None;
+                let editor_settings = &app_state.settings.editor;
+                let font_family = (!had_unavoidable_ligatures
+                    && editor_settings.has_unavoidable_ligatures())
+                .then(|| editor_settings.font_family.clone());
+                drop(app_state);
+
+                if let Some(font_family) = font_family {
+                    notify_unavoidable_ligatures(radio_app_state, &font_family);
+                }
+            }
+            // The last valid settings are kept
+            Err(SettingsError::Invalid(invalid)) => {
+                let is_reported =
+                    radio_app_state.read().invalid_settings.as_ref() == Some(&invalid);
+                if !is_reported {
+                    notify_invalid_settings(radio_app_state, &invalid);
+                    radio_app_state
+                        .write_channel(Channel::Settings)
+                        .invalid_settings = Some(invalid);
+                }
+            }
+            Err(SettingsError::Unavailable) => {
+                info!("Failed to update in-memory settings with the newest changes.")
+            }
         }
     }
 
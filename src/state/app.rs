@@ -1,17 +1,35 @@
-use std::{collections::HashMap, vec};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    path::{Path, PathBuf},
+    vec,
+};
 
 use dioxus_radio::prelude::{Radio, RadioChannel};
 use dioxus_sdk::clipboard::UseClipboard;
+use serde::{Deserialize, Serialize};
 use skia_safe::{textlayout::FontCollection, FontMgr};
 use tracing::info;
 
 use crate::{
-    fs::FSTransport,
+    environment::workspace_environment,
+    fs::{ExcludeMatcher, FSTransport},
     lsp::{create_lsp_client, LSPClient, LspConfig},
+    notifications::notify,
+    output::TaskOutput,
+    processes::{kill_process, ProcessKind, ProcessRegistry},
+    settings::InvalidSettings,
+    symbols::SymbolIndex,
+    tabs::editor::{AppStateEditorUtils, TabEditorUtils},
+    tasks::WorkspaceTask,
+    working_sets::{load_working_sets, save_working_sets, WorkingSet},
     ExplorerItem, LspStatusSender,
 };
 
-use super::{AppSettings, EditorView, Panel, PanelTab};
+use super::{
+    AppSettings, Bookmark, EditorView, LayoutVisibility, NotificationLevel, Notifications, Panel,
+    PanelTab,
+};
 
 pub type RadioAppState = Radio<AppState, Channel>;
 
@@ -46,6 +64,8 @@ pub enum Channel {
     Settings,
     // Only affects the file explorer
     FileExplorer,
+    /// Only affects the output panel
+    Output,
 }
 
 impl RadioChannel<AppState> for Channel {
@@ -112,10 +132,14 @@ impl Channel {
     }
 }
 
-#[derive(Clone, Default, PartialEq, Copy)]
+#[derive(Clone, Default, PartialEq, Copy, Debug, Serialize, Deserialize)]
 pub enum EditorSidePanel {
     #[default]
     FileExplorer,
+    Search,
+    WorkingSets,
+    Processes,
+    Bookmarks,
 }
 
 pub struct AppState {
@@ -125,10 +149,35 @@ pub struct AppState {
     pub panels: Vec<Panel>,
     pub settings: AppSettings,
     pub language_servers: HashMap<String, LSPClient>,
+    /// Root paths of the workspaces whose files don't use their language server.
+    pub lsp_disabled_workspaces: HashSet<PathBuf>,
     pub lsp_sender: LspStatusSender,
-    pub side_panel: Option<EditorSidePanel>,
+    pub layout: LayoutVisibility,
+    /// Started with `--clean`, the settings and the layout are the default ones and they are not kept.
+    pub clean_session: bool,
+    /// Error of the settings file, the default settings (or the last valid ones) are used meanwhile.
+    pub invalid_settings: Option<InvalidSettings>,
+    pub commander_pinned: bool,
+    pub working_sets: Vec<WorkingSet>,
+    pub symbol_index: SymbolIndex,
+    pub processes: ProcessRegistry,
+    /// Tasks of the opened folders.
+    pub tasks: Vec<WorkspaceTask>,
+    pub output: TaskOutput,
+    /// Locations of the cursor to jump back and forward to.
+    pub navigation: NavigationHistory,
+    /// Errors and other messages, shown as toasts over the editors for a while.
+    pub notifications: Notifications,
+    /// Tab being dragged to another panel or to a new one.
+    pub tab_drag: Option<TabDrag>,
+    /// Bookmarks of the files that are not opened, the editors keep the ones of their file.
+    pub closed_bookmarks: Vec<Bookmark>,
     pub file_explorer_folders: Vec<ExplorerItem>,
+    /// File to focus in the files explorer, once its folders are opened.
+    pub file_explorer_revealed_file: Option<PathBuf>,
     pub default_transport: FSTransport,
+    /// Transports of the folders that are not in the local filesystem, by their root path.
+    pub mounted_transports: Vec<(PathBuf, FSTransport)>,
     pub font_collection: FontCollection,
     pub clipboard: UseClipboard,
 }
@@ -138,38 +187,173 @@ impl AppState {
         lsp_sender: LspStatusSender,
         default_transport: FSTransport,
         clipboard: UseClipboard,
+        clean_session: bool,
     ) -> Self {
         let mut font_collection = FontCollection::new();
         font_collection.set_default_font_manager(FontMgr::default(), "Jetbrains Mono");
 
+        let (mut settings, invalid_settings) = if clean_session {
+            (AppSettings::default(), None)
+        } else {
+            AppSettings::load()
+        };
+        settings.editor.find_font_variants();
+
         Self {
             previous_focused_view: None,
             focused_view: EditorView::default(),
             focused_panel: 0,
             panels: vec![Panel::new()],
-            settings: AppSettings::load(),
+            settings,
             language_servers: HashMap::default(),
+            lsp_disabled_workspaces: HashSet::new(),
             lsp_sender,
-            side_panel: Some(EditorSidePanel::default()),
+            layout: if clean_session {
+                LayoutVisibility::default()
+            } else {
+                LayoutVisibility::load()
+            },
+            commander_pinned: false,
+            working_sets: load_working_sets(),
+            symbol_index: SymbolIndex::default(),
+            processes: ProcessRegistry::default(),
+            tasks: Vec::new(),
+            output: TaskOutput::default(),
+            navigation: NavigationHistory::default(),
+            notifications: Notifications::default(),
+            tab_drag: None,
+            closed_bookmarks: Vec::new(),
             file_explorer_folders: Vec::new(),
+            file_explorer_revealed_file: None,
             default_transport,
+            mounted_transports: Vec::new(),
             font_collection,
             clipboard,
+            clean_session,
+            invalid_settings,
+        }
+    }
+
+    /// Keep the layout for the next sessions, unless this is a clean one.
+    fn save_layout(&self) {
+        if !self.clean_session {
+            self.layout.save();
         }
     }
 
     pub fn toggle_side_panel(&mut self, side_panel: EditorSidePanel) {
-        if let Some(current_side_panel) = self.side_panel {
-            if current_side_panel == side_panel {
-                self.side_panel = None;
-                return;
+        if self.layout.visible_side_panel() == Some(side_panel) {
+            self.layout.side_panel = None;
+            self.save_layout();
+            return;
+        }
+
+        self.open_side_panel(side_panel);
+    }
+
+    pub fn open_side_panel(&mut self, side_panel: EditorSidePanel) {
+        self.layout.side_panel = Some(side_panel);
+        self.layout.last_side_panel = side_panel;
+        self.layout.zen_mode = false;
+        self.save_layout();
+    }
+
+    /// Hide the side panel, or show again the last one.
+    pub fn toggle_side_panel_visibility(&mut self) {
+        if self.layout.visible_side_panel().is_some() {
+            self.layout.side_panel = None;
+            self.save_layout();
+            self.focus_panels_if_hidden();
+        } else {
+            self.open_side_panel(self.layout.last_side_panel);
+        }
+    }
+
+    pub fn toggle_status_bar(&mut self) {
+        self.layout.status_bar = !self.layout.is_status_bar_visible();
+        self.layout.zen_mode = false;
+        self.save_layout();
+    }
+
+    pub fn toggle_output_panel(&mut self) {
+        self.layout.output_panel = !self.layout.is_output_panel_visible();
+        self.layout.zen_mode = false;
+        self.save_layout();
+    }
+
+    pub fn show_output_panel(&mut self) {
+        if !self.layout.is_output_panel_visible() {
+            self.toggle_output_panel();
+        }
+    }
+
+    /// Hide everything but the focused panel, or show back the rest of the window.
+    pub fn toggle_zen_mode(&mut self) {
+        self.layout.zen_mode = !self.layout.zen_mode;
+        self.save_layout();
+
+        if self.layout.zen_mode && self.commander_pinned {
+            self.toggle_commander_pinned();
+        }
+        self.focus_panels_if_hidden();
+    }
+
+    /// Move the focus back to the panels if the focused side panel is not visible anymore.
+    fn focus_panels_if_hidden(&mut self) {
+        let is_side_view = matches!(
+            self.focused_view,
+            EditorView::FilesExplorer | EditorView::Search | EditorView::WorkingSets
+        );
+        if is_side_view && self.layout.visible_side_panel().is_none() {
+            self.set_focused_view(EditorView::Panels);
+        }
+    }
+
+    /// Dock the Commander above the panels, so it stays visible while editing.
+    pub fn toggle_commander_pinned(&mut self) {
+        self.commander_pinned = !self.commander_pinned;
+
+        if self.commander_pinned {
+            if self.layout.zen_mode {
+                self.toggle_zen_mode();
             }
+            self.set_focused_view(EditorView::Commander);
+        } else if self.focused_view == EditorView::Commander {
+            self.set_focused_view_to_previous();
+        }
+    }
+
+    /// Save the files opened in the focused panel as a working set, replacing the one with the same name.
+    pub fn save_working_set(&mut self, name: String) {
+        let files = self
+            .panel(self.focused_panel)
+            .tabs()
+            .iter()
+            .filter_map(|tab| tab.as_text_editor())
+            .filter_map(|editor_tab| editor_tab.editor.editor_type().paths())
+            .map(|(path, root_path)| (path.clone(), root_path.clone()))
+            .collect();
+
+        let working_set = WorkingSet { name, files };
+        match self
+            .working_sets
+            .iter_mut()
+            .find(|set| set.name == working_set.name)
+        {
+            Some(set) => *set = working_set,
+            None => self.working_sets.push(working_set),
         }
 
-        self.side_panel = Some(side_panel);
+        save_working_sets(&self.working_sets);
+    }
+
+    pub fn remove_working_set(&mut self, name: &str) {
+        self.working_sets.retain(|set| set.name != name);
+        save_working_sets(&self.working_sets);
     }
 
-    pub fn set_settings(&mut self, settins: AppSettings) {
+    pub fn set_settings(&mut self, mut settins: AppSettings) {
+        settins.editor.find_font_variants();
         self.settings = settins;
         self.apply_settings();
     }
@@ -205,6 +389,11 @@ impl AppState {
         }
     }
 
+    /// Get the matcher of the excluded files for the given root folder.
+    pub fn exclude_matcher(&self, root_path: &Path) -> ExcludeMatcher {
+        ExcludeMatcher::new(root_path, &self.settings.files.exclude)
+    }
+
     pub fn font_size(&self) -> f32 {
         self.settings.editor.font_size
     }
@@ -248,7 +437,28 @@ impl AppState {
         );
     }
 
+    /// Replace a tab with a new one and focus it.
+    pub fn replace_tab(&mut self, panel: usize, tab: usize, new_tab: impl PanelTab + 'static) {
+        let mut panel_tab = std::mem::replace(&mut self.panels[panel].tabs[tab], Box::new(new_tab));
+        panel_tab.on_close(self);
+
+        self.focused_panel = panel;
+        self.panels[panel].active_tab = Some(tab);
+        self.focused_view = EditorView::Panels;
+    }
+
     pub fn close_tab(&mut self, panel: usize, tab: usize) {
+        info!(
+            "Closed tab [panel={panel}] [tab={}]",
+            self.panels[panel].tabs.len()
+        );
+
+        let mut panel_tab = self.remove_tab(panel, tab);
+        panel_tab.on_close(self);
+    }
+
+    /// Take a tab out of its panel, the tab next to it becomes the active one.
+    fn remove_tab(&mut self, panel: usize, tab: usize) -> Box<dyn PanelTab> {
         if let Some(active_tab) = self.panels[panel].active_tab {
             let prev_tab = tab > 0;
             let next_tab = self.panels[panel].tabs.get(tab + 1).is_some();
@@ -265,19 +475,159 @@ impl AppState {
             }
         }
 
-        info!(
-            "Closed tab [panel={panel}] [tab={}]",
-            self.panels[panel].tabs.len()
-        );
+        self.panels[panel].tabs.remove(tab)
+    }
 
-        let mut panel_tab = self.panels[panel].tabs.remove(tab);
-        panel_tab.on_close(self);
+    /// Move a tab to another panel, where it becomes the active and focused one.
+    pub fn move_tab(&mut self, panel: usize, tab: usize, target_panel: usize) {
+        if panel == target_panel {
+            self.focused_panel = panel;
+            self.panels[panel].active_tab = Some(tab);
+            return;
+        }
+
+        let id = self.panels[panel].tab(tab).get_data().id;
+        let opened_tab = self.panels[target_panel]
+            .tabs
+            .iter()
+            .position(|target_tab| target_tab.get_data().id == id);
+
+        match opened_tab {
+            // The other panel already has it opened
+            Some(opened_tab) => {
+                self.close_tab(panel, tab);
+                self.panels[target_panel].active_tab = Some(opened_tab);
+            }
+            None => {
+                let panel_tab = self.remove_tab(panel, tab);
+                self.panels[target_panel].tabs.push(panel_tab);
+                self.panels[target_panel].active_tab =
+                    Some(self.panels[target_panel].tabs.len() - 1);
+            }
+        }
+        self.focused_panel = target_panel;
+        self.focused_view = EditorView::Panels;
+    }
+
+    /// Drop a tab in a zone of a panel, moving it there or to a new panel next to it.
+    /// The panel the tab comes from is closed if it ends up empty.
+    pub fn drop_tab(&mut self, panel: usize, tab: usize, target_panel: usize, zone: TabDropZone) {
+        let column = self.panel_column(target_panel);
+        let (new_panel, stacked) = match zone {
+            // New columns go next to the whole column of the panel
+            TabDropZone::Left => (Some(column.start), false),
+            TabDropZone::Right => (Some(column.end), false),
+            TabDropZone::Top => (Some(target_panel), self.panels[target_panel].stacked),
+            TabDropZone::Bottom => (Some(target_panel + 1), true),
+            TabDropZone::Center => (None, false),
+        };
+
+        let (mut panel, mut target_panel) = (panel, target_panel);
+        if let Some(new_panel) = new_panel {
+            self.panels.insert(
+                new_panel,
+                Panel {
+                    stacked,
+                    ..Panel::new()
+                },
+            );
+            // The panel that was there is now below it
+            if zone == TabDropZone::Top {
+                self.panels[new_panel + 1].stacked = true;
+            }
+            if panel >= new_panel {
+                panel += 1;
+            }
+            target_panel = new_panel;
+        }
+
+        self.move_tab(panel, tab, target_panel);
+
+        if panel != target_panel && self.panels[panel].tabs.is_empty() {
+            self.remove_panel(panel);
+            if target_panel > panel {
+                target_panel -= 1;
+            }
+            self.focused_panel = target_panel;
+        }
+    }
+
+    /// Remove a panel, the one below it (if any) takes its place in the column.
+    fn remove_panel(&mut self, panel: usize) {
+        let removed = self.panels.remove(panel);
+        if let Some(next_panel) = self.panels.get_mut(panel) {
+            next_panel.stacked &= removed.stacked;
+        }
+    }
+
+    /// Panels of every column, from left to right. The panels of a column are stacked from top to bottom.
+    pub fn panel_columns(&self) -> Vec<Range<usize>> {
+        let mut columns: Vec<Range<usize>> = Vec::new();
+        for (panel_index, panel) in self.panels.iter().enumerate() {
+            match columns.last_mut() {
+                Some(column) if panel.stacked => column.end = panel_index + 1,
+                _ => columns.push(panel_index..panel_index + 1),
+            }
+        }
+        columns
+    }
+
+    /// Panels of the column of a panel.
+    pub fn panel_column(&self, panel: usize) -> Range<usize> {
+        self.panel_columns()
+            .into_iter()
+            .find(|column| column.contains(&panel))
+            .unwrap_or(panel..panel + 1)
     }
 
     pub fn push_panel(&mut self, panel: Panel) {
         self.panels.push(panel);
     }
 
+    /// Bookmarks of every file, sorted by file and line.
+    pub fn bookmarks(&self) -> Vec<Bookmark> {
+        let mut bookmarks = self
+            .panels
+            .iter()
+            .flat_map(|panel| panel.tabs())
+            .filter_map(|tab| tab.as_text_editor())
+            .flat_map(|editor_tab| editor_tab.editor.bookmarks())
+            .collect::<Vec<Bookmark>>();
+        bookmarks.extend(
+            self.closed_bookmarks
+                .iter()
+                .filter(|bookmark| self.find_editor_tab(&bookmark.path).is_none())
+                .cloned(),
+        );
+        bookmarks.sort();
+        bookmarks.dedup();
+        bookmarks
+    }
+
+    pub fn remove_bookmark(&mut self, bookmark: &Bookmark) {
+        match self.find_editor_tab(&bookmark.path) {
+            Some((panel, tab)) => {
+                self.editor_tab_mut(panel, tab)
+                    .editor
+                    .bookmarks
+                    .remove(&bookmark.line);
+            }
+            None => self.closed_bookmarks.retain(|closed| closed != bookmark),
+        }
+    }
+
+    /// The bookmark after a line of a file, or the first one after the last one.
+    pub fn next_bookmark(&self, from: Option<(&Path, usize)>) -> Option<Bookmark> {
+        let bookmarks = self.bookmarks();
+        from.and_then(|from| {
+            bookmarks
+                .iter()
+                .find(|bookmark| (bookmark.path.as_path(), bookmark.line) > from)
+        })
+        .or(bookmarks.first())
+        .cloned()
+    }
+
     pub fn panels(&self) -> &[Panel] {
         &self.panels
     }
@@ -296,7 +646,7 @@ impl AppState {
 
     pub fn close_panel(&mut self, panel: usize) {
         if self.panels.len() > 1 {
-            self.panels.remove(panel);
+            self.remove_panel(panel);
             if self.focused_panel > 0 {
                 self.focused_panel -= 1;
             }
@@ -311,24 +661,73 @@ impl AppState {
         self.language_servers.insert(language_server, client);
     }
 
+    /// The language server of a config, started if it isn't running yet.
     pub async fn get_or_create_lsp_client(
         mut radio: RadioAppState,
         lsp_config: &LspConfig,
-    ) -> LSPClient {
+    ) -> Option<LSPClient> {
         let server = radio.read().lsp(lsp_config).cloned();
         match server {
-            Some(server) => server,
+            Some(server) => Some(server),
             None => {
-                let lsp_sender = radio.read().lsp_sender.clone();
-                let client = create_lsp_client(lsp_config.clone(), lsp_sender).await;
-                radio
-                    .write_channel(Channel::Global)
-                    .insert_lsp_client(lsp_config.language_server.clone(), client.clone());
-                client
+                let (lsp_sender, env) = {
+                    let app_state = radio.read();
+                    let env = lsp_config
+                        .editor_type
+                        .paths()
+                        .map(|(_, root_path)| workspace_environment(&app_state.settings, root_path))
+                        .unwrap_or_default();
+                    (app_state.lsp_sender.clone(), env)
+                };
+                let client = match create_lsp_client(lsp_config.clone(), lsp_sender, env).await {
+                    Ok(client) => client,
+                    Err(err) => {
+                        notify(
+                            radio,
+                            NotificationLevel::Error,
+                            format!("Failed to start {}: {err}", lsp_config.language_server),
+                            None,
+                        );
+                        return None;
+                    }
+                };
+                let mut app_state = radio.write_channel(Channel::Global);
+                if let Some(pid) = client.pid {
+                    app_state.processes.register(
+                        pid,
+                        lsp_config.language_server.clone(),
+                        ProcessKind::LanguageServer,
+                    );
+                }
+                app_state.insert_lsp_client(lsp_config.language_server.clone(), client.clone());
+                Some(client)
             }
         }
     }
 
+    /// Kill a child process, language servers are started again when needed.
+    pub fn kill_process(&mut self, pid: u32) {
+        if !kill_process(pid) {
+            info!("Failed to kill the process {pid}");
+        }
+        self.processes.unregister(pid);
+        self.language_servers
+            .retain(|_, client| client.pid != Some(pid));
+    }
+
+    /// Transport of the folder that contains the given path.
+    pub fn transport(&self, path: &Path) -> FSTransport {
+        self.mounted_transports
+            .iter()
+            .find(|(root_path, _)| path.starts_with(root_path))
+            .map(|(_, transport)| transport.clone())
+            .unwrap_or_else(|| self.default_transport.clone())
+    }
+
+    pub fn mount_transport(&mut self, root_path: PathBuf, transport: FSTransport) {
+        self.mounted_transports.push((root_path, transport));
+    }
+
     pub fn open_folder(&mut self, item: ExplorerItem) {
         self.file_explorer_folders.push(item)
     }
@@ -0,0 +1,9 @@
+use std::path::PathBuf;
+
+/// Line bookmarked in a file.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bookmark {
+    pub path: PathBuf,
+    pub line: usize,
+    pub root_path: PathBuf,
+}
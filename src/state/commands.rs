@@ -1,5 +1,16 @@
 use std::collections::HashMap;
 
+/// Result of a command, shown below the Commander input.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommandOutput {
+    Text(String),
+    List(Vec<String>),
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
 pub trait EditorCommand {
     fn is_visible(&self) -> bool {
         true
@@ -12,6 +23,13 @@ pub trait EditorCommand {
     fn text(&self) -> &str;
 
     fn run(&self);
+
+    /// Run the command with the text typed in the Commander, returning a result to show below it.
+    /// Commands without a result are simply run.
+    fn run_with_input(&self, _input: &str) -> Option<CommandOutput> {
+        self.run();
+        None
+    }
 }
 
 #[derive(Default)]
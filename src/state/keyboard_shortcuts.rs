@@ -1,20 +1,30 @@
 use freya::events::KeyboardData;
 
-use super::{EditorCommands, RadioAppState};
+use super::{EditorCommands, EditorView, RadioAppState};
 
 type KeyboardShortcutHandler = dyn Fn(&KeyboardData, &mut EditorCommands, RadioAppState) -> bool;
 
+/// Where a shortcut handler listens for key chords.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ShortcutContext {
+    /// Whatever view is focused, even while typing in the Commander or another input.
+    Global,
+    /// Only while the given view is focused.
+    View(EditorView),
+}
+
 #[derive(Default)]
 pub struct KeyboardShortcuts {
-    handlers: Vec<Box<KeyboardShortcutHandler>>,
+    handlers: Vec<(ShortcutContext, Box<KeyboardShortcutHandler>)>,
 }
 
 impl KeyboardShortcuts {
     pub fn register(
         &mut self,
+        context: ShortcutContext,
         handler: impl Fn(&KeyboardData, &mut EditorCommands, RadioAppState) -> bool + 'static,
     ) {
-        self.handlers.push(Box::new(handler))
+        self.handlers.push((context, Box::new(handler)))
     }
 
     pub fn run(
@@ -23,7 +33,15 @@ impl KeyboardShortcuts {
         editor_commands: &mut EditorCommands,
         radio_app_state: RadioAppState,
     ) {
-        for event_handler in &self.handlers {
+        let focused_view = *radio_app_state.read().focused_view();
+
+        for (context, event_handler) in &self.handlers {
+            if let ShortcutContext::View(view) = context {
+                if *view != focused_view {
+                    continue;
+                }
+            }
+
             let res = (event_handler)(data, editor_commands, radio_app_state);
 
             if res {
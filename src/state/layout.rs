@@ -0,0 +1,69 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::data_path;
+
+use super::EditorSidePanel;
+
+/// What parts of the window around the editors are visible, kept across sessions.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(default)]
+pub struct LayoutVisibility {
+    pub side_panel: Option<EditorSidePanel>,
+    /// Side panel shown again when the side panel is toggled back.
+    pub last_side_panel: EditorSidePanel,
+    pub status_bar: bool,
+    /// The panel with the output of the tasks, below the editors.
+    pub output_panel: bool,
+    /// Hide everything but the focused panel, centered in the window.
+    pub zen_mode: bool,
+}
+
+impl Default for LayoutVisibility {
+    fn default() -> Self {
+        Self {
+            side_panel: Some(EditorSidePanel::default()),
+            last_side_panel: EditorSidePanel::default(),
+            status_bar: true,
+            output_panel: false,
+            zen_mode: false,
+        }
+    }
+}
+
+fn layout_path() -> Option<PathBuf> {
+    Some(data_path()?.join("layout.json"))
+}
+
+impl LayoutVisibility {
+    pub fn load() -> Self {
+        layout_path()
+            .and_then(|path| read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Option<()> {
+        let path = layout_path()?;
+        create_dir_all(path.parent()?).ok()?;
+        let content = serde_json::to_string_pretty(self).ok()?;
+        write(path, content).ok()
+    }
+
+    /// The side panel to show, if any.
+    pub fn visible_side_panel(&self) -> Option<EditorSidePanel> {
+        self.side_panel.filter(|_| !self.zen_mode)
+    }
+
+    pub fn is_status_bar_visible(&self) -> bool {
+        self.status_bar && !self.zen_mode
+    }
+
+    pub fn is_output_panel_visible(&self) -> bool {
+        self.output_panel && !self.zen_mode
+    }
+}
@@ -1,13 +1,21 @@
 mod app;
+mod bookmarks;
 mod commands;
 mod keyboard_shortcuts;
+mod layout;
+mod navigation;
+mod notifications;
 mod panels_tabs;
 mod settings;
 mod views;
 
 pub use app::*;
+pub use bookmarks::*;
 pub use commands::*;
 pub use keyboard_shortcuts::*;
+pub use layout::*;
+pub use navigation::*;
+pub use notifications::*;
 pub use panels_tabs::*;
 pub use settings::*;
 pub use views::*;
@@ -0,0 +1,100 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// How many locations are remembered to go back to.
+const MAX_LOCATIONS: usize = 100;
+
+/// Lines the cursor has to move at once for its previous location to be remembered.
+const JUMP_LINES: usize = 10;
+
+/// A position of the cursor in a file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavigationLocation {
+    pub path: PathBuf,
+    pub root_path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl NavigationLocation {
+    /// Whether going from one location to the other is a jump worth remembering.
+    pub fn is_far_from(&self, other: &Self) -> bool {
+        self.path != other.path || self.line.abs_diff(other.line) >= JUMP_LINES
+    }
+
+    fn is_same_line(&self, other: &Self) -> bool {
+        self.path == other.path && self.line == other.line
+    }
+}
+
+/// Locations the cursor jumped from, across files, to go back and forward through them.
+#[derive(Default)]
+pub struct NavigationHistory {
+    back: Vec<NavigationLocation>,
+    forward: Vec<NavigationLocation>,
+    /// Where the cursor of the focused editor was last seen.
+    /// It changes with every cursor movement so it's updated without notifying anyone.
+    current: Arc<Mutex<Option<NavigationLocation>>>,
+    /// Location being navigated to, which is not a new jump.
+    navigating_to: Option<NavigationLocation>,
+}
+
+impl NavigationHistory {
+    /// Update where the cursor of the focused editor is, returning where it was before.
+    pub fn move_to(&self, location: NavigationLocation) -> Option<NavigationLocation> {
+        self.current.lock().unwrap().replace(location)
+    }
+
+    /// Remember the location the cursor jumped from.
+    pub fn record(&mut self, from: NavigationLocation, to: &NavigationLocation) {
+        let is_navigating = self
+            .navigating_to
+            .take()
+            .is_some_and(|navigating_to| navigating_to.is_same_line(to));
+        if is_navigating || self.back.last() == Some(&from) {
+            return;
+        }
+
+        self.back.push(from);
+        if self.back.len() > MAX_LOCATIONS {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    /// Take the previous location to go back to.
+    pub fn go_back(&mut self) -> Option<NavigationLocation> {
+        let current = self.current.lock().unwrap().clone();
+        let location = pop_far_from(&mut self.back, current.as_ref())?;
+        self.forward.extend(current);
+        self.navigating_to = Some(location.clone());
+        Some(location)
+    }
+
+    /// Take the location that was left by going back.
+    pub fn go_forward(&mut self) -> Option<NavigationLocation> {
+        let current = self.current.lock().unwrap().clone();
+        let location = pop_far_from(&mut self.forward, current.as_ref())?;
+        self.back.extend(current);
+        self.navigating_to = Some(location.clone());
+        Some(location)
+    }
+}
+
+/// Pop the last location that is far from the current one, there would be nothing to jump to otherwise.
+fn pop_far_from(
+    locations: &mut Vec<NavigationLocation>,
+    current: Option<&NavigationLocation>,
+) -> Option<NavigationLocation> {
+    while let Some(location) = locations.pop() {
+        if current
+            .map(|current| current.is_far_from(&location))
+            .unwrap_or(true)
+        {
+            return Some(location);
+        }
+    }
+    None
+}
@@ -0,0 +1,184 @@
+use std::{collections::VecDeque, fmt::Display, path::PathBuf, time::Duration};
+
+use uuid::Uuid;
+
+/// How many notifications are kept in the history.
+const MAX_NOTIFICATIONS: usize = 100;
+
+/// How many toasts are shown at once, the oldest ones are hidden first.
+const MAX_TOASTS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Info => "ℹ️",
+            Self::Warning => "⚠️",
+            Self::Error => "⛔",
+        }
+    }
+
+    /// Color of the border of its toasts.
+    pub fn color(&self) -> &'static str {
+        match self {
+            Self::Info => "rgb(60, 60, 60)",
+            Self::Warning => "rgb(200, 160, 60)",
+            Self::Error => "rgb(200, 80, 80)",
+        }
+    }
+
+    /// How long its toasts are shown for, errors stay for longer.
+    pub fn toast_duration(&self) -> Duration {
+        match self {
+            Self::Info | Self::Warning => Duration::from_secs(8),
+            Self::Error => Duration::from_secs(16),
+        }
+    }
+}
+
+impl Display for NotificationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => f.write_str("Info"),
+            Self::Warning => f.write_str("Warning"),
+            Self::Error => f.write_str("Error"),
+        }
+    }
+}
+
+/// Action offered by a notification, like undoing what it is about.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotificationAction {
+    /// Bring back an entry moved to the trash, and reopen the files that were closed with it.
+    RestoreFromTrash {
+        path: PathBuf,
+        root_path: PathBuf,
+        closed_files: Vec<PathBuf>,
+    },
+    /// Open the files with the unsaved changes backed up by a previous session.
+    RestoreBackups { paths: Vec<PathBuf> },
+    /// Open a file with the cursor at the given position, e.g where an error is.
+    OpenFile {
+        path: PathBuf,
+        root_path: PathBuf,
+        line: usize,
+        column: usize,
+    },
+}
+
+impl NotificationAction {
+    pub fn text(&self) -> &'static str {
+        match self {
+            Self::RestoreFromTrash { .. } => "Undo",
+            Self::RestoreBackups { .. } => "Restore",
+            Self::OpenFile { .. } => "Open",
+        }
+    }
+}
+
+/// Message about something that happened, like an error or the end of a long operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub id: Uuid,
+    pub level: NotificationLevel,
+    pub message: String,
+    pub action: Option<NotificationAction>,
+}
+
+/// Notifications received so far, the newest ones are also shown as toasts for a while.
+#[derive(Default)]
+pub struct Notifications {
+    /// Oldest first.
+    history: VecDeque<Notification>,
+    /// Notifications shown as toasts, oldest first.
+    toasts: Vec<Uuid>,
+    /// Notifications received since the history was last opened.
+    unread: usize,
+    is_history_open: bool,
+}
+
+impl Notifications {
+    pub fn push(&mut self, notification: Notification) {
+        self.toasts.push(notification.id);
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+
+        self.history.push_back(notification);
+        if self.history.len() > MAX_NOTIFICATIONS {
+            if let Some(removed) = self.history.pop_front() {
+                self.toasts.retain(|id| *id != removed.id);
+            }
+        }
+
+        if !self.is_history_open {
+            self.unread += 1;
+        }
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&Notification> {
+        self.history
+            .iter()
+            .find(|notification| notification.id == id)
+    }
+
+    /// Notifications shown as toasts, none while the history is open.
+    pub fn toasts(&self) -> Vec<Notification> {
+        if self.is_history_open {
+            return Vec::new();
+        }
+        self.toasts
+            .iter()
+            .filter_map(|id| self.get(*id).cloned())
+            .collect()
+    }
+
+    pub fn is_toast_shown(&self, id: Uuid) -> bool {
+        self.toasts.contains(&id)
+    }
+
+    pub fn hide_toast(&mut self, id: Uuid) {
+        self.toasts.retain(|toast| *toast != id);
+    }
+
+    /// Take the action of a notification so it can only run once, hiding its toast.
+    pub fn take_action(&mut self, id: Uuid) -> Option<NotificationAction> {
+        self.hide_toast(id);
+        self.history
+            .iter_mut()
+            .find(|notification| notification.id == id)
+            .and_then(|notification| notification.action.take())
+    }
+
+    /// Newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter().rev()
+    }
+
+    pub fn unread(&self) -> usize {
+        self.unread
+    }
+
+    pub fn is_history_open(&self) -> bool {
+        self.is_history_open
+    }
+
+    /// Open or close the history, which marks every notification as read.
+    pub fn toggle_history(&mut self) {
+        self.is_history_open = !self.is_history_open;
+        self.toasts.clear();
+        self.unread = 0;
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.toasts.clear();
+        self.unread = 0;
+    }
+}
@@ -35,12 +35,59 @@ pub struct PanelTabData {
     pub edited: bool,
     pub title: String,
     pub id: String,
+    /// Temporary tab, replaced by the next previewed file.
+    pub preview: bool,
+    pub read_only: bool,
+}
+
+/// Where a dragged tab is dropped over a panel.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TabDropZone {
+    /// Into a new panel at the left of the panel.
+    Left,
+    /// Into a new panel at the right of the panel.
+    Right,
+    /// Into a new panel above the panel.
+    Top,
+    /// Into a new panel below the panel.
+    Bottom,
+    /// Into the panel itself.
+    Center,
+}
+
+impl TabDropZone {
+    /// Zone of a panel under a position, relative to the size of the panel.
+    /// Each edge is a quarter of the panel, in the corners the closest edge wins.
+    pub fn from_position(x: f32, y: f32, width: f32, height: f32) -> Self {
+        let edges = [
+            (x / width, Self::Left),
+            (1.0 - x / width, Self::Right),
+            (y / height, Self::Top),
+            (1.0 - y / height, Self::Bottom),
+        ];
+        edges
+            .into_iter()
+            .filter(|(distance, _)| *distance < 0.25)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map_or(Self::Center, |(_, zone)| zone)
+    }
+}
+
+/// A tab being dragged from its panel.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TabDrag {
+    pub panel: usize,
+    pub tab: usize,
+    /// Panel and zone the tab would be dropped in.
+    pub target: Option<(usize, TabDropZone)>,
 }
 
 #[derive(Default)]
 pub struct Panel {
     pub active_tab: Option<usize>,
     pub tabs: Vec<Box<dyn PanelTab>>,
+    /// Below the previous panel, in its column.
+    pub stacked: bool,
 }
 
 impl Panel {
@@ -1,7 +1,13 @@
+use std::path::Path;
+
 use serde::{Deserialize, Serialize, Serializer};
+use skia_safe::FontMgr;
 use tracing::info;
 
-use crate::settings::load_settings;
+use crate::{
+    language::LanguageSettings,
+    settings::{load_settings, InvalidSettings, SettingsError},
+};
 
 fn human_number_serializer<S>(value: &f32, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -15,6 +21,129 @@ pub struct EditorSettings {
     pub(crate) font_size: f32,
     #[serde(serialize_with = "human_number_serializer")]
     pub(crate) line_height: f32,
+    /// Re-indent the pasted lines to the indentation of the cursor.
+    #[serde(default = "enabled")]
+    pub(crate) reindent_on_paste: bool,
+    /// Keep the undo history of the files after closing them.
+    #[serde(default)]
+    pub(crate) persistent_undo: bool,
+    /// How many undo entries are kept per file.
+    #[serde(default = "default_persistent_undo_limit")]
+    pub(crate) persistent_undo_limit: usize,
+    /// Pin the headers of the scopes enclosing the first visible line.
+    #[serde(default = "enabled")]
+    pub(crate) sticky_scroll: bool,
+    #[serde(default)]
+    pub(crate) autosave: AutosaveMode,
+    /// Milliseconds without typing before saving, when using [`AutosaveMode::AfterDelay`].
+    #[serde(default = "default_autosave_delay")]
+    pub(crate) autosave_delay: u64,
+    /// Indent with tabs instead of spaces, unless the file is already indented otherwise.
+    #[serde(default)]
+    pub(crate) indent_with_tabs: bool,
+    /// Columns of an indentation level, unless the file is already indented otherwise.
+    #[serde(default = "default_indent_width")]
+    pub(crate) indent_width: u8,
+    /// Draw a vertical guide for every indentation level.
+    #[serde(default = "enabled")]
+    pub(crate) indent_guides: bool,
+    /// Color the brackets by how nested they are.
+    #[serde(default)]
+    pub(crate) rainbow_brackets: bool,
+    /// Text of the comments added with the `Add TODO` command, `{user}` and `{date}` are replaced.
+    #[serde(default = "default_todo_template")]
+    pub(crate) todo_template: String,
+    /// Lines kept visible above and below the cursor when it moves near the edges of the editor.
+    #[serde(default = "default_scroll_margin")]
+    pub(crate) scroll_margin: usize,
+    /// Close brackets and strings as they are opened, and type over the closing characters.
+    #[serde(default = "enabled")]
+    pub(crate) auto_close_pairs: bool,
+    /// Font of the code.
+    #[serde(default = "default_font_family")]
+    pub(crate) font_family: String,
+    /// Join characters like `->` or `!=` into a single symbol, if the font has ligatures.
+    /// They can only be turned off for fonts with a variant without them, e.g `Jetbrains Mono NL`.
+    #[serde(default = "enabled")]
+    pub(crate) font_ligatures: bool,
+    /// Installed variant of the font without ligatures, see [`Self::find_font_variants`].
+    #[serde(skip)]
+    pub(crate) font_family_without_ligatures: Option<String>,
+    #[serde(default)]
+    pub(crate) cursor_style: CursorStyle,
+    #[serde(default)]
+    pub(crate) cursor_blink: bool,
+}
+
+/// Shape of the cursor of the editors.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CursorStyle {
+    #[default]
+    Bar,
+    Block,
+    Underline,
+}
+
+/// When are the edited files saved automatically.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutosaveMode {
+    #[default]
+    Off,
+    AfterDelay,
+    OnFocusChange,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+fn default_persistent_undo_limit() -> usize {
+    500
+}
+
+fn default_autosave_delay() -> u64 {
+    1000
+}
+
+fn default_indent_width() -> u8 {
+    4
+}
+
+fn default_todo_template() -> String {
+    "TODO: ".to_string()
+}
+
+fn default_scroll_margin() -> usize {
+    3
+}
+
+fn default_font_family() -> String {
+    "Jetbrains Mono".to_string()
+}
+
+impl EditorSettings {
+    /// Look for the installed variant of the font without ligatures,
+    /// the text can't turn the ligatures of a font off otherwise.
+    pub fn find_font_variants(&mut self) {
+        let family = format!("{} NL", self.font_family);
+        self.font_family_without_ligatures =
+            (FontMgr::default().match_family(&family).count() > 0).then_some(family);
+    }
+
+    /// Font the code is rendered with, its variant without ligatures if they are turned off.
+    pub fn rendered_font_family(&self) -> &str {
+        match &self.font_family_without_ligatures {
+            Some(family) if !self.font_ligatures => family,
+            _ => &self.font_family,
+        }
+    }
+
+    /// Whether the ligatures are turned off but the font has no variant without them.
+    pub fn has_unavoidable_ligatures(&self) -> bool {
+        !self.font_ligatures && self.font_family_without_ligatures.is_none()
+    }
 }
 
 impl Default for EditorSettings {
@@ -22,20 +151,158 @@ impl Default for EditorSettings {
         Self {
             font_size: 17.0,
             line_height: 1.6_f32,
+            reindent_on_paste: true,
+            persistent_undo: false,
+            persistent_undo_limit: default_persistent_undo_limit(),
+            sticky_scroll: true,
+            autosave: AutosaveMode::default(),
+            autosave_delay: default_autosave_delay(),
+            indent_with_tabs: false,
+            indent_width: default_indent_width(),
+            indent_guides: true,
+            rainbow_brackets: false,
+            todo_template: default_todo_template(),
+            scroll_margin: default_scroll_margin(),
+            auto_close_pairs: true,
+            font_family: default_font_family(),
+            font_ligatures: true,
+            font_family_without_ligatures: None,
+            cursor_style: CursorStyle::default(),
+            cursor_blink: false,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilesSettings {
+    /// Globs (in `.gitignore` syntax) of the files hidden from the explorer and the search.
+    #[serde(default = "default_exclude")]
+    pub(crate) exclude: Vec<String>,
+    /// Reveal the focused file in the files explorer when switching between files.
+    #[serde(default)]
+    pub(crate) follow_mode: bool,
+    /// Save through a temporary file renamed into place, disable it for watchers that don't like it.
+    #[serde(default = "enabled")]
+    pub(crate) atomic_save: bool,
+}
+
+impl Default for FilesSettings {
+    fn default() -> Self {
+        Self {
+            exclude: default_exclude(),
+            follow_mode: false,
+            atomic_save: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct DiffSettings {
+    /// Lines that only differ in their whitespace are shown as unchanged.
+    #[serde(default)]
+    pub(crate) ignore_whitespace: bool,
+    /// Lines that only differ in their line ending are shown as unchanged.
+    #[serde(default)]
+    pub(crate) ignore_line_endings: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EnvironmentSettings {
+    /// Load the env files of the workspaces into the processes started from them.
+    #[serde(default)]
+    pub(crate) load_env_files: bool,
+    /// Env files relative to the workspace, the later ones override the previous ones.
+    #[serde(default = "default_env_files")]
+    pub(crate) env_files: Vec<String>,
+    /// Command opening a terminal, started in the folder where it's opened.
+    #[serde(default = "default_terminal")]
+    pub(crate) terminal: String,
+}
+
+fn default_exclude() -> Vec<String> {
+    vec![
+        "target".to_string(),
+        "node_modules".to_string(),
+        ".git".to_string(),
+    ]
+}
+
+fn default_env_files() -> Vec<String> {
+    vec![".env".to_string()]
+}
+
+fn default_terminal() -> String {
+    if cfg!(windows) {
+        "start cmd".to_string()
+    } else if cfg!(target_os = "macos") {
+        "open -a Terminal .".to_string()
+    } else {
+        "x-terminal-emulator".to_string()
+    }
+}
+
+impl Default for EnvironmentSettings {
+    fn default() -> Self {
+        Self {
+            load_env_files: false,
+            env_files: default_env_files(),
+            terminal: default_terminal(),
+        }
+    }
+}
+
+/// External command formatting the files with any of its extensions,
+/// it gets the text in its stdin and prints the formatted text to its stdout.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FormatterSettings {
+    pub(crate) extensions: Vec<String>,
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) args: Vec<String>,
+    /// Format the files before saving them.
+    #[serde(default)]
+    pub(crate) on_save: bool,
+}
+
+impl FormatterSettings {
+    /// Formatter of a file, the first one with its extension.
+    pub fn find<'a>(formatters: &'a [Self], path: &Path) -> Option<&'a Self> {
+        let extension = path.extension()?.to_str()?;
+        formatters
+            .iter()
+            .find(|formatter| formatter.extensions.iter().any(|ext| ext == extension))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct AppSettings {
     pub(crate) editor: EditorSettings,
+    #[serde(default)]
+    pub(crate) files: FilesSettings,
+    #[serde(default)]
+    pub(crate) environment: EnvironmentSettings,
+    #[serde(default)]
+    pub(crate) diff: DiffSettings,
+    /// Languages unknown to the editor, or overrides of the known ones.
+    #[serde(default)]
+    pub(crate) languages: Vec<LanguageSettings>,
+    #[serde(default)]
+    pub(crate) formatters: Vec<FormatterSettings>,
 }
 
 impl AppSettings {
-    pub fn load() -> Self {
-        load_settings().unwrap_or_else(|| {
-            info!("Failed to load settings, using defaults.");
-            Self::default()
-        })
+    /// Load the settings, or the defaults if they can't be loaded, along with the error of an invalid file.
+    pub fn load() -> (Self, Option<InvalidSettings>) {
+        match load_settings() {
+            Ok(settings) => (settings, None),
+            Err(SettingsError::Invalid(invalid)) => {
+                info!("Invalid settings, using defaults: {}", invalid.message);
+                (Self::default(), Some(invalid))
+            }
+            Err(SettingsError::Unavailable) => {
+                info!("Failed to load settings, using defaults.");
+                (Self::default(), None)
+            }
+        }
     }
 }
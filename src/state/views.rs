@@ -6,6 +6,8 @@ pub enum EditorView {
     Panels,
     FilesExplorer,
     Commander,
+    Search,
+    WorkingSets,
 }
 
 impl Display for EditorView {
@@ -14,6 +16,8 @@ impl Display for EditorView {
             Self::Panels => f.write_str("Panels"),
             Self::FilesExplorer => f.write_str("Files Explorer"),
             Self::Commander => f.write_str("Commander"),
+            Self::Search => f.write_str("Search"),
+            Self::WorkingSets => f.write_str("Working Sets"),
         }
     }
 }
@@ -0,0 +1,241 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+    ops::Range,
+    path::PathBuf,
+};
+
+use ropey::Rope;
+
+use crate::{
+    search::{collect_files, read_text_file},
+    state::{Channel, RadioAppState},
+    utils::fuzzy_match,
+};
+
+/// How many symbols are listed when searching.
+const MAX_SYMBOL_RESULTS: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SymbolKind {
+    Function,
+    Type,
+    Module,
+    Constant,
+    Macro,
+}
+
+impl Display for SymbolKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Function => f.write_str("fn"),
+            Self::Type => f.write_str("type"),
+            Self::Module => f.write_str("mod"),
+            Self::Constant => f.write_str("const"),
+            Self::Macro => f.write_str("macro"),
+        }
+    }
+}
+
+impl SymbolKind {
+    /// Kind of the symbols defined after a keyword, in any of the supported languages.
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "fn" | "function" | "def" => Some(Self::Function),
+            "struct" | "enum" | "trait" | "type" | "union" | "class" | "interface" => {
+                Some(Self::Type)
+            }
+            "mod" | "namespace" => Some(Self::Module),
+            "const" | "static" => Some(Self::Constant),
+            "macro_rules" => Some(Self::Macro),
+            _ => None,
+        }
+    }
+}
+
+/// A definition found in a text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Chars range of the name.
+    pub range: Range<usize>,
+    pub line: usize,
+}
+
+/// A symbol and the file where it was found.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymbolLocation {
+    pub path: PathBuf,
+    pub root_path: PathBuf,
+    pub symbol: Symbol,
+}
+
+/// Identifiers of a line, as chars and bytes ranges.
+fn identifiers(line: &str) -> Vec<(Range<usize>, Range<usize>)> {
+    let mut identifiers = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+
+    for (char_idx, (byte_idx, ch)) in line.char_indices().enumerate() {
+        let is_identifier = ch.is_alphanumeric() || ch == '_';
+        match current {
+            Some(_) if is_identifier => {}
+            Some((char_start, byte_start)) => {
+                identifiers.push((char_start..char_idx, byte_start..byte_idx));
+                current = None;
+            }
+            // Identifiers don't start with a digit
+            None if is_identifier && !ch.is_ascii_digit() => current = Some((char_idx, byte_idx)),
+            None => {}
+        }
+    }
+
+    if let Some((char_start, byte_start)) = current {
+        identifiers.push((char_start..line.chars().count(), byte_start..line.len()));
+    }
+
+    identifiers
+}
+
+/// Find the definitions of a text, ctags-style: names right after a definition keyword.
+pub fn parse_symbols(rope: &Rope) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+
+    for (line_idx, line) in rope.lines().enumerate() {
+        let line = line.to_string();
+        let trimmed = line.trim_start();
+        if ["//", "/*", "*", "#"]
+            .iter()
+            .any(|comment| trimmed.starts_with(comment))
+        {
+            continue;
+        }
+
+        let line_start = rope.line_to_char(line_idx);
+        let mut keyword: Option<(SymbolKind, usize)> = None;
+
+        for (chars_range, bytes_range) in identifiers(&line) {
+            let word = &line[bytes_range.clone()];
+
+            // Only the name right after the keyword counts, e.g not the `u8` of `fn(u8)`
+            let kind = keyword.take().filter(|(_, keyword_end)| {
+                matches!(line[*keyword_end..bytes_range.start].trim(), "" | "!")
+            });
+
+            if let Some(next_kind) = SymbolKind::from_keyword(word) {
+                keyword = Some((next_kind, bytes_range.end));
+            } else if word == "mut" {
+                keyword = kind.map(|(kind, _)| (kind, bytes_range.end));
+            } else if let Some((kind, _)) = kind {
+                symbols.push(Symbol {
+                    name: word.to_string(),
+                    kind,
+                    range: line_start + chars_range.start..line_start + chars_range.end,
+                    line: line_idx,
+                });
+            }
+        }
+    }
+
+    symbols
+}
+
+/// Symbols of the files in the opened folders, used when there is no language server.
+#[derive(Default)]
+pub struct SymbolIndex {
+    /// Root path and symbols of every file.
+    files: HashMap<PathBuf, (PathBuf, Vec<Symbol>)>,
+}
+
+impl SymbolIndex {
+    pub fn update_file(&mut self, path: PathBuf, root_path: PathBuf, rope: &Rope) {
+        self.files.insert(path, (root_path, parse_symbols(rope)));
+    }
+
+    fn locations(&self) -> impl Iterator<Item = SymbolLocation> + '_ {
+        self.files.iter().flat_map(|(path, (root_path, symbols))| {
+            symbols.iter().map(|symbol| SymbolLocation {
+                path: path.clone(),
+                root_path: root_path.clone(),
+                symbol: symbol.clone(),
+            })
+        })
+    }
+
+    /// Symbols with exactly the given name.
+    pub fn definitions(&self, name: &str) -> Vec<SymbolLocation> {
+        let mut definitions = self
+            .locations()
+            .filter(|location| location.symbol.name == name)
+            .collect::<Vec<SymbolLocation>>();
+        definitions.sort_by(|a, b| a.path.cmp(&b.path));
+        definitions
+    }
+
+    /// Symbols whose name fuzzy matches the query, best matches first.
+    pub fn search(&self, query: &str) -> Vec<SymbolLocation> {
+        search_symbols(self.locations(), query)
+    }
+
+    /// Names of the symbols starting with the given prefix.
+    pub fn completions(&self, prefix: &str) -> BTreeSet<String> {
+        self.files
+            .values()
+            .flat_map(|(_, symbols)| symbols)
+            .filter(|symbol| symbol.name.starts_with(prefix))
+            .map(|symbol| symbol.name.clone())
+            .collect()
+    }
+}
+
+/// Keep the symbols whose name fuzzy matches the query, best matches first.
+pub fn search_symbols(
+    locations: impl Iterator<Item = SymbolLocation>,
+    query: &str,
+) -> Vec<SymbolLocation> {
+    // Without a query the symbols are kept in their order
+    if query.trim().is_empty() {
+        return locations.take(MAX_SYMBOL_RESULTS).collect();
+    }
+
+    let mut results = locations
+        .filter_map(|location| Some((fuzzy_match(&location.symbol.name, query)?, location)))
+        .collect::<Vec<(usize, SymbolLocation)>>();
+    results.sort_by(|(a_gaps, a), (b_gaps, b)| {
+        a_gaps
+            .cmp(b_gaps)
+            .then_with(|| a.symbol.name.len().cmp(&b.symbol.name.len()))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    results
+        .into_iter()
+        .take(MAX_SYMBOL_RESULTS)
+        .map(|(_, location)| location)
+        .collect()
+}
+
+/// Index the symbols of every file of a folder in the background.
+pub async fn index_folder(mut radio_app_state: RadioAppState, root_path: PathBuf) {
+    let (transport, exclude_matcher) = {
+        let app_state = radio_app_state.read();
+        (
+            app_state.transport(&root_path),
+            app_state.exclude_matcher(&root_path),
+        )
+    };
+
+    let mut files = Vec::new();
+    for path in collect_files(&root_path, &transport, &exclude_matcher).await {
+        if let Some((rope, _)) = read_text_file(&path, &transport).await {
+            files.push((path, rope));
+        }
+    }
+
+    let mut app_state = radio_app_state.write_channel(Channel::Global);
+    for (path, rope) in files {
+        app_state
+            .symbol_index
+            .update_file(path, root_path.clone(), &rope);
+    }
+}
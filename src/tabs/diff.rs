@@ -0,0 +1,353 @@
+use dioxus::dioxus_core::AttributeValue;
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::{
+    components::EditorScrollView,
+    diff::{diff_texts, DiffLine, DiffOptions, DiffRow, DiffRowKind},
+    state::{AppState, Channel, PanelTab, PanelTabData, RadioAppState, TabProps},
+    utils::Divider,
+};
+
+/// A read-only tab that compares two texts side by side.
+pub struct DiffTab {
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) left_title: String,
+    pub(crate) right_title: String,
+    /// Compared texts, kept to diff them again when the options change.
+    pub(crate) left: String,
+    pub(crate) right: String,
+    pub(crate) options: DiffOptions,
+    pub(crate) rows: Vec<DiffRow>,
+    /// Bumped every time the compared texts are updated.
+    pub(crate) revision: usize,
+}
+
+impl PanelTab for DiffTab {
+    fn get_data(&self) -> PanelTabData {
+        PanelTabData {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            edited: false,
+            preview: false,
+            read_only: false,
+        }
+    }
+
+    fn render(&self) -> fn(TabProps) -> Element {
+        DiffViewer
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl DiffTab {
+    /// Open a DiffTab in the focused panel, or update it if it was already opened.
+    pub fn open_with(
+        app_state: &mut AppState,
+        id: String,
+        title: String,
+        (left_title, left): (String, &str),
+        (right_title, right): (String, &str),
+    ) {
+        let diff_settings = &app_state.settings.diff;
+        let options = DiffOptions {
+            ignore_whitespace: diff_settings.ignore_whitespace,
+            ignore_line_endings: diff_settings.ignore_line_endings,
+        };
+        let panel = app_state.focused_panel;
+
+        let opened_tab = app_state
+            .panel_mut(panel)
+            .tabs
+            .iter_mut()
+            .filter_map(|opened_tab| opened_tab.as_any_mut().downcast_mut::<DiffTab>())
+            .find(|opened_tab| opened_tab.id == id);
+
+        // Refresh the already opened tab, which `push_tab` will simply focus
+        if let Some(opened_tab) = opened_tab {
+            opened_tab.left = left.to_string();
+            opened_tab.right = right.to_string();
+            opened_tab.refresh();
+        }
+
+        let tab = Self {
+            id,
+            title,
+            left_title,
+            right_title,
+            left: left.to_string(),
+            right: right.to_string(),
+            options,
+            rows: diff_texts(left, right, options),
+            revision: 0,
+        };
+        app_state.push_tab(tab, panel, true);
+    }
+
+    /// Diff the texts again, e.g after changing the options.
+    fn refresh(&mut self) {
+        self.rows = diff_texts(&self.left, &self.right, self.options);
+        self.revision += 1;
+    }
+}
+
+fn diff_tab(app_state: &AppState, panel_index: usize, tab_index: usize) -> &DiffTab {
+    app_state
+        .panel(panel_index)
+        .tab(tab_index)
+        .as_any()
+        .downcast_ref::<DiffTab>()
+        .unwrap()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DiffSide {
+    Left,
+    Right,
+}
+
+#[derive(Clone, PartialEq)]
+struct DiffBuilderArgs {
+    radio_app_state: RadioAppState,
+    panel_index: usize,
+    tab_index: usize,
+    side: DiffSide,
+    font_size: f32,
+    line_height: f32,
+    revision: usize,
+}
+
+#[allow(non_snake_case)]
+pub fn DiffViewer(
+    TabProps {
+        panel_index,
+        tab_index,
+    }: TabProps,
+) -> Element {
+    let mut radio_app_state =
+        use_radio::<AppState, Channel>(Channel::follow_tab(panel_index, tab_index));
+
+    // Both sides share the same scroll positions
+    let mut scroll_offsets = use_signal(|| (0, 0));
+
+    let app_state = radio_app_state.read();
+    let tab = diff_tab(&app_state, panel_index, tab_index);
+    let font_size = app_state.font_size();
+    let line_height = (font_size * app_state.line_height()).floor();
+    let rows_len = tab.rows.len();
+
+    let changed_rows = tab
+        .rows
+        .iter()
+        .filter(|row| row.kind != DiffRowKind::Unchanged);
+    let added = changed_rows
+        .clone()
+        .filter(|row| row.right.is_some())
+        .count();
+    let removed = changed_rows.filter(|row| row.left.is_some()).count();
+
+    let onscroll = move |(axis, scroll): (Axis, i32)| match axis {
+        Axis::X => {
+            if scroll_offsets.read().0 != scroll {
+                scroll_offsets.write().0 = scroll
+            }
+        }
+        Axis::Y => {
+            if scroll_offsets.read().1 != scroll {
+                scroll_offsets.write().1 = scroll
+            }
+        }
+    };
+
+    let mut toggle_option = move |toggle: fn(&mut DiffOptions)| {
+        let mut app_state =
+            radio_app_state.write_channel(Channel::follow_tab(panel_index, tab_index));
+        if let Some(tab) = app_state
+            .panel_mut(panel_index)
+            .tab_mut(tab_index)
+            .as_any_mut()
+            .downcast_mut::<DiffTab>()
+        {
+            toggle(&mut tab.options);
+            tab.refresh();
+        }
+    };
+
+    let toggle_ignore_whitespace =
+        move |_| toggle_option(|options| options.ignore_whitespace = !options.ignore_whitespace);
+
+    let toggle_ignore_line_endings = move |_| {
+        toggle_option(|options| options.ignore_line_endings = !options.ignore_line_endings)
+    };
+
+    let check = |enabled: bool| if enabled { "☑" } else { "☐" };
+    let ignore_whitespace = check(tab.options.ignore_whitespace);
+    let ignore_line_endings = check(tab.options.ignore_line_endings);
+
+    let builder_args = |side: DiffSide| DiffBuilderArgs {
+        radio_app_state,
+        panel_index,
+        tab_index,
+        side,
+        font_size,
+        line_height,
+        revision: tab.revision,
+    };
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            background: "rgb(40, 40, 40)",
+            rect {
+                width: "100%",
+                height: "28",
+                direction: "horizontal",
+                cross_align: "center",
+                color: "rgb(215, 215, 215)",
+                label {
+                    width: "50%",
+                    padding: "0 10",
+                    max_lines: "1",
+                    text_overflow: "ellipsis",
+                    "{tab.left_title}"
+                }
+                label {
+                    width: "fill",
+                    padding: "0 10",
+                    max_lines: "1",
+                    text_overflow: "ellipsis",
+                    "{tab.right_title}"
+                }
+                label {
+                    onclick: toggle_ignore_whitespace,
+                    padding: "0 10",
+                    "{ignore_whitespace} Ignore Whitespace"
+                }
+                label {
+                    onclick: toggle_ignore_line_endings,
+                    padding: "0 10",
+                    "{ignore_line_endings} Ignore Line Endings"
+                }
+                label {
+                    padding: "0 10",
+                    "+{added} -{removed}"
+                }
+            }
+            rect {
+                width: "100%",
+                height: "fill",
+                direction: "horizontal",
+                rect {
+                    width: "50%",
+                    height: "100%",
+                    EditorScrollView {
+                        offset_x: scroll_offsets.read().0,
+                        offset_y: scroll_offsets.read().1,
+                        onscroll,
+                        length: rows_len,
+                        item_size: line_height,
+                        builder_args: builder_args(DiffSide::Left),
+                        builder: diff_row_builder
+                    }
+                }
+                Divider {}
+                rect {
+                    width: "fill",
+                    height: "100%",
+                    EditorScrollView {
+                        offset_x: scroll_offsets.read().0,
+                        offset_y: scroll_offsets.read().1,
+                        onscroll,
+                        length: rows_len,
+                        item_size: line_height,
+                        builder_args: builder_args(DiffSide::Right),
+                        builder: diff_row_builder
+                    }
+                }
+            }
+        }
+    )
+}
+
+fn diff_row_builder(row_index: usize, args: &DiffBuilderArgs) -> Element {
+    let app_state = args.radio_app_state.read();
+    let tab = diff_tab(&app_state, args.panel_index, args.tab_index);
+    let row = &tab.rows[row_index];
+    let font_size = args.font_size;
+    let line_height = args.line_height;
+    let gutter_width = font_size * 3.0;
+
+    let line = match args.side {
+        DiffSide::Left => row.left.as_ref(),
+        DiffSide::Right => row.right.as_ref(),
+    };
+
+    let (background, highlight_color) = match (row.kind, args.side) {
+        (DiffRowKind::Unchanged, _) => ("transparent", "transparent"),
+        _ if line.is_none() => ("rgb(45, 45, 45)", "transparent"),
+        (_, DiffSide::Left) => ("rgb(75, 40, 40)", "rgb(130, 50, 50)"),
+        (_, DiffSide::Right) => ("rgb(40, 70, 45)", "rgb(50, 120, 60)"),
+    };
+
+    let Some(DiffLine {
+        line,
+        text,
+        highlights,
+    }) = line
+    else {
+        return rsx!(rect {
+            key: "{row_index}",
+            width: "100%",
+            height: "{line_height}",
+            background: "{background}",
+        });
+    };
+
+    // The highlights are measured in UTF-16 code units
+    let utf16_offset = |pos: usize| text.chars().take(pos).map(char::len_utf16).sum::<usize>();
+    let highlights = AttributeValue::any_value(CustomAttributeValues::TextHighlights(
+        highlights
+            .iter()
+            .map(|range| (utf16_offset(range.start), utf16_offset(range.end)))
+            .collect(),
+    ));
+
+    rsx!(
+        rect {
+            key: "{row_index}",
+            width: "100%",
+            height: "{line_height}",
+            direction: "horizontal",
+            cross_align: "center",
+            background: "{background}",
+            label {
+                width: "{gutter_width}",
+                text_align: "center",
+                font_size: "{font_size}",
+                color: "rgb(135, 135, 135)",
+                "{line + 1} "
+            }
+            paragraph {
+                width: "fill",
+                max_lines: "1",
+                highlights,
+                highlight_color: "{highlight_color}",
+                font_size: "{font_size}",
+                font_family: "Jetbrains Mono",
+                color: "rgb(215, 215, 215)",
+                text {
+                    "{text}"
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,211 @@
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, read_dir, read_to_string, remove_file, write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    notifications::notify,
+    settings::data_path,
+    state::{Channel, NotificationAction, NotificationLevel, RadioAppState},
+    utils::{is_read_only, open_file},
+};
+
+use super::{persisted_history::path_hash, text_digest, AppStateEditorUtils, TabEditorUtils};
+
+/// How often the edited files are backed up.
+pub const BACKUP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Unsaved content of a file, kept in case the editor is closed without saving it.
+#[derive(Serialize, Deserialize)]
+struct Backup {
+    path: PathBuf,
+    root_path: PathBuf,
+    content: String,
+    backed_up_at: SystemTime,
+}
+
+fn backups_path() -> Option<PathBuf> {
+    Some(data_path()?.join("backups"))
+}
+
+fn backup_path(file_path: &Path) -> Option<PathBuf> {
+    Some(backups_path()?.join(format!("{:x}.json", path_hash(file_path))))
+}
+
+fn save_backup(backup: &Backup) -> Option<()> {
+    let backup_path = backup_path(&backup.path)?;
+    create_dir_all(backup_path.parent()?).ok()?;
+
+    let content = serde_json::to_string(backup).ok()?;
+    write(backup_path, content).ok()
+}
+
+fn load_backup(file_path: &Path) -> Option<Backup> {
+    let content = read_to_string(backup_path(file_path)?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Forget the backup of a file, once it's saved or its changes are discarded.
+pub fn remove_backup(file_path: &Path) {
+    if let Some(backup_path) = backup_path(file_path) {
+        remove_file(backup_path).ok();
+    }
+}
+
+fn load_backups() -> Vec<Backup> {
+    let Some(entries) = backups_path().and_then(|backups_path| read_dir(backups_path).ok()) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let content = read_to_string(entry.path()).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect()
+}
+
+/// Back up the edited files whose content changed since their last backup,
+/// and forget the ones that are no longer edited.
+/// `backed_up` keeps the digest of the last backup of every file.
+pub async fn backup_edited_files(
+    radio_app_state: RadioAppState,
+    backed_up: &mut HashMap<PathBuf, u64>,
+) {
+    let edited_files = {
+        let app_state = radio_app_state.read();
+        app_state
+            .panels()
+            .iter()
+            .flat_map(|panel| panel.tabs())
+            .filter_map(|tab| tab.as_text_editor())
+            .filter(|editor_tab| editor_tab.editor.is_edited())
+            .filter_map(|editor_tab| {
+                let (path, root_path) = editor_tab.editor.editor_type.paths()?;
+                Some((
+                    path.clone(),
+                    root_path.clone(),
+                    editor_tab.editor.rope.clone(),
+                ))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let previously_backed_up = std::mem::take(backed_up);
+    let result = tokio::task::spawn_blocking(move || {
+        let mut backed_up = HashMap::new();
+        for (path, root_path, rope) in edited_files {
+            let digest = text_digest(&rope);
+            if previously_backed_up.get(&path) != Some(&digest) {
+                let backup = Backup {
+                    path: path.clone(),
+                    root_path,
+                    content: rope.to_string(),
+                    backed_up_at: SystemTime::now(),
+                };
+                if save_backup(&backup).is_none() {
+                    info!("Failed to back up {path:?}");
+                    continue;
+                }
+            }
+            backed_up.insert(path, digest);
+        }
+
+        for path in previously_backed_up.keys() {
+            if !backed_up.contains_key(path) {
+                remove_backup(path);
+            }
+        }
+
+        backed_up
+    })
+    .await;
+
+    match result {
+        Ok(result) => *backed_up = result,
+        Err(err) => info!("Failed to back up the edited files: {err:?}"),
+    }
+}
+
+/// Offer to restore the backups left by a previous session that was not closed properly.
+/// Backups older than their files are outdated, so they are removed instead.
+pub async fn offer_backups(radio_app_state: RadioAppState) {
+    let backups = tokio::task::spawn_blocking(load_backups)
+        .await
+        .unwrap_or_default();
+
+    let mut paths = Vec::new();
+    for backup in backups {
+        let transport = radio_app_state.read().transport(&backup.path);
+        let modified = transport
+            .metadata(&backup.path)
+            .await
+            .ok()
+            .and_then(|metadata| metadata.modified);
+
+        if modified.is_some_and(|modified| modified > backup.backed_up_at) {
+            info!("Discarded the outdated backup of {:?}", backup.path);
+            remove_backup(&backup.path);
+        } else {
+            paths.push(backup.path);
+        }
+    }
+
+    if paths.is_empty() {
+        return;
+    }
+
+    let message = if paths.len() == 1 {
+        format!(
+            "Found unsaved changes of {}",
+            paths[0].file_name().unwrap_or_default().to_string_lossy()
+        )
+    } else {
+        format!("Found unsaved changes of {} files", paths.len())
+    };
+    notify(
+        radio_app_state,
+        NotificationLevel::Warning,
+        message,
+        Some(NotificationAction::RestoreBackups { paths }),
+    );
+}
+
+/// Open the files with the content of their backups, as an edit that can be undone.
+pub async fn restore_backups(mut radio_app_state: RadioAppState, paths: Vec<PathBuf>) {
+    for path in paths {
+        let Some(backup) = load_backup(&path) else {
+            continue;
+        };
+
+        if radio_app_state.read().find_editor_tab(&path).is_none() {
+            // Files removed in the meantime are created again once saved
+            let transport = radio_app_state.read().transport(&path);
+            let content = transport.read(&path).await.unwrap_or_default();
+            let read_only = is_read_only(&transport, &path).await;
+            let mut app_state = radio_app_state.write_channel(Channel::Global);
+            open_file(
+                &mut app_state,
+                path.clone(),
+                backup.root_path,
+                content,
+                read_only,
+            );
+        }
+
+        let opened_tab = radio_app_state.read().find_editor_tab(&path);
+        if let Some((panel, tab)) = opened_tab {
+            let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+            app_state
+                .editor_tab_mut(panel, tab)
+                .editor
+                .replace_text(&backup.content);
+        }
+    }
+}
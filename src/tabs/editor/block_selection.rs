@@ -0,0 +1,104 @@
+use std::ops::{Range, RangeInclusive};
+
+use ropey::Rope;
+
+/// Direction a block selection is extended towards from the keyboard.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BlockSelectionDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl BlockSelectionDirection {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// Lines and columns moved in this direction.
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+            Self::Right => (0, 1),
+        }
+    }
+}
+
+/// Rectangular selection spanning the same columns in consecutive lines.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BlockSelection {
+    /// Line and column where the selection started.
+    pub anchor: (usize, usize),
+    /// Line and column where the selection ends, the cursor is placed there.
+    pub head: (usize, usize),
+}
+
+/// Chars of a line without its line break.
+fn line_len(rope: &Rope, line: usize) -> usize {
+    rope.get_line(line)
+        .map(|line| {
+            let len = line.len_chars();
+            let line_break = line
+                .chars()
+                .rev()
+                .take(2)
+                .take_while(|ch| *ch == '\n' || *ch == '\r')
+                .count();
+            len - line_break
+        })
+        .unwrap_or_default()
+}
+
+impl BlockSelection {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self {
+            anchor: (line, column),
+            head: (line, column),
+        }
+    }
+
+    pub fn lines(&self) -> RangeInclusive<usize> {
+        self.anchor.0.min(self.head.0)..=self.anchor.0.max(self.head.0)
+    }
+
+    pub fn columns(&self) -> Range<usize> {
+        self.anchor.1.min(self.head.1)..self.anchor.1.max(self.head.1)
+    }
+
+    /// Columns selected in a line, shorter lines are only selected up to their end.
+    pub fn line_columns(&self, rope: &Rope, line: usize) -> Option<Range<usize>> {
+        if !self.lines().contains(&line) || line >= rope.len_lines() {
+            return None;
+        }
+
+        let len = line_len(rope, line);
+        let columns = self.columns();
+        Some(columns.start.min(len)..columns.end.min(len))
+    }
+
+    /// Chars range selected in every line, from top to bottom.
+    pub fn ranges(&self, rope: &Rope) -> Vec<Range<usize>> {
+        self.lines()
+            .filter_map(|line| {
+                let columns = self.line_columns(rope, line)?;
+                let line_start = rope.line_to_char(line);
+                Some(line_start + columns.start..line_start + columns.end)
+            })
+            .collect()
+    }
+
+    /// Same lines, with every line selected at the given column and nothing else.
+    pub fn collapse_to(&self, column: usize) -> Self {
+        Self {
+            anchor: (self.anchor.0, column),
+            head: (self.head.0, column),
+        }
+    }
+
+    /// Position of the cursor, clamped to the length of its line.
+    pub fn cursor_pos(&self, rope: &Rope) -> usize {
+        let line = self.head.0.min(rope.len_lines() - 1);
+        rope.line_to_char(line) + self.head.1.min(line_len(rope, line))
+    }
+}
@@ -0,0 +1,338 @@
+use std::{collections::HashMap, ops::Range};
+
+use freya::hooks::TextEditor;
+use freya::prelude::{spawn, Rope};
+use lsp_types::{
+    CodeActionContext, CodeActionOrCommand, CodeActionParams, Command, DocumentChangeOperation,
+    DocumentChanges, ExecuteCommandParams, OneOf, PartialResultParams, Position,
+    TextDocumentIdentifier, TextEdit, Url, WorkDoneProgressParams, WorkspaceEdit,
+};
+
+use crate::{
+    lsp::{LSPClient, LspConfig},
+    notifications::notify,
+    search::read_text_file,
+    state::{AppState, Channel, NotificationLevel, RadioAppState},
+    utils::is_read_only,
+};
+
+use super::{AppStateEditorUtils, EditorTab};
+
+/// Code actions offered by the language server for the cursor (or selection) of an editor.
+#[derive(Clone, PartialEq)]
+pub struct CodeActionsMenu {
+    /// Line the menu is shown below.
+    pub line: usize,
+    pub actions: Vec<CodeActionOrCommand>,
+    pub selected: usize,
+}
+
+impl CodeActionsMenu {
+    pub fn select_previous(&mut self) {
+        self.selected = self
+            .selected
+            .checked_sub(1)
+            .unwrap_or(self.actions.len() - 1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.actions.len();
+    }
+
+    pub fn selected_action(&self) -> Option<&CodeActionOrCommand> {
+        self.actions.get(self.selected)
+    }
+}
+
+pub fn code_action_title(action: &CodeActionOrCommand) -> &str {
+    match action {
+        CodeActionOrCommand::Command(command) => &command.title,
+        CodeActionOrCommand::CodeAction(action) => &action.title,
+    }
+}
+
+/// Char index of an LSP position, whose column is counted in UTF-16 code units.
+fn lsp_position_to_char(rope: &Rope, position: Position) -> usize {
+    let line = (position.line as usize).min(rope.len_lines() - 1);
+    let line_slice = rope.line(line);
+    let column = (position.character as usize).min(line_slice.len_utf16_cu());
+    rope.line_to_char(line) + line_slice.utf16_cu_to_char(column)
+}
+
+pub(crate) fn char_to_lsp_position(rope: &Rope, pos: usize) -> Position {
+    let line = rope.char_to_line(pos);
+    let column = rope
+        .line(line)
+        .char_to_utf16_cu(pos - rope.line_to_char(line));
+    Position::new(line as u32, column as u32)
+}
+
+/// Char ranges of some edits, sorted so they can be applied.
+fn text_edits_to_ranges(rope: &Rope, edits: Vec<TextEdit>) -> Vec<(Range<usize>, String)> {
+    let mut edits = edits
+        .into_iter()
+        .map(|edit| {
+            let start = lsp_position_to_char(rope, edit.range.start);
+            let end = lsp_position_to_char(rope, edit.range.end);
+            (start..end.max(start), edit.new_text)
+        })
+        .collect::<Vec<(Range<usize>, String)>>();
+    edits.sort_by_key(|(range, _)| range.start);
+    edits
+}
+
+/// Edits of every document changed by a workspace edit.
+fn workspace_edit_changes(edit: WorkspaceEdit) -> HashMap<Url, Vec<TextEdit>> {
+    let mut changes = edit.changes.unwrap_or_default();
+
+    let document_edits = match edit.document_changes {
+        Some(DocumentChanges::Edits(edits)) => edits,
+        Some(DocumentChanges::Operations(operations)) => operations
+            .into_iter()
+            .filter_map(|operation| match operation {
+                DocumentChangeOperation::Edit(edit) => Some(edit),
+                // Creating, renaming and deleting files is not supported
+                DocumentChangeOperation::Op(_) => None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    for document_edit in document_edits {
+        changes
+            .entry(document_edit.text_document.uri)
+            .or_default()
+            .extend(document_edit.edits.into_iter().map(|edit| match edit {
+                OneOf::Left(edit) => edit,
+                OneOf::Right(annotated) => annotated.text_edit,
+            }));
+    }
+
+    changes
+}
+
+/// Language server of an editor tab, unless it was turned off for it.
+fn editor_lsp_client(app_state: &AppState, editor_tab: &EditorTab) -> Option<LSPClient> {
+    if editor_tab.is_lsp_disabled(app_state) {
+        return None;
+    }
+    let lsp_config = LspConfig::new(editor_tab.editor.editor_type.clone())?;
+    app_state.lsp(&lsp_config).cloned()
+}
+
+/// Ask the language server for the code actions at the cursor (or selection) of an editor.
+pub fn request_code_actions(
+    mut radio_app_state: RadioAppState,
+    panel_index: usize,
+    tab_index: usize,
+) {
+    let request = {
+        let app_state = radio_app_state.read();
+        let editor_tab = app_state.editor_tab(panel_index, tab_index);
+        let editor = &editor_tab.editor;
+        let cursor = editor.cursor_pos();
+        let (start, end) = editor
+            .selected
+            .map(|(from, to)| (from.min(to), from.max(to)))
+            .unwrap_or((cursor, cursor));
+        let range = lsp_types::Range::new(
+            char_to_lsp_position(&editor.rope, start),
+            char_to_lsp_position(&editor.rope, end),
+        );
+        let line = editor.rope.char_to_line(cursor);
+        editor_lsp_client(&app_state, editor_tab)
+            .zip(editor.uri())
+            .zip(editor.path().cloned())
+            .map(|((client, uri), path)| (client, uri, path, editor.text(), range, line))
+    };
+    let Some((mut client, uri, path, text, range, line)) = request else {
+        notify(
+            radio_app_state,
+            NotificationLevel::Warning,
+            "Code actions are only available with a running language server",
+            None,
+        );
+        return;
+    };
+
+    spawn(async move {
+        // The language server only knows about the saved text otherwise
+        client.change_file(uri.clone(), text);
+
+        let response = client
+            .code_actions(CodeActionParams {
+                text_document: TextDocumentIdentifier { uri },
+                range,
+                context: CodeActionContext::default(),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            })
+            .await;
+
+        let actions = match response {
+            Ok(Some(actions)) if !actions.is_empty() => actions,
+            Ok(_) => {
+                notify(
+                    radio_app_state,
+                    NotificationLevel::Info,
+                    "No code actions available",
+                    None,
+                );
+                return;
+            }
+            Err(err) => {
+                notify(
+                    radio_app_state,
+                    NotificationLevel::Error,
+                    format!("Failed to get the code actions: {err}"),
+                    None,
+                );
+                return;
+            }
+        };
+
+        // The tab might have been moved or closed in the meantime
+        let opened_tab = radio_app_state.read().find_editor_tab(&path);
+        if let Some((panel_index, tab_index)) = opened_tab {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel_index, tab_index));
+            app_state
+                .editor_tab_mut(panel_index, tab_index)
+                .code_actions = Some(CodeActionsMenu {
+                line,
+                actions,
+                selected: 0,
+            });
+        }
+    });
+}
+
+/// Apply the selected code action of an editor and close its menu.
+pub fn apply_selected_code_action(
+    mut radio_app_state: RadioAppState,
+    panel_index: usize,
+    tab_index: usize,
+) {
+    let client = {
+        let app_state = radio_app_state.read();
+        editor_lsp_client(&app_state, app_state.editor_tab(panel_index, tab_index))
+    };
+    let action = radio_app_state
+        .write_channel(Channel::follow_tab(panel_index, tab_index))
+        .editor_tab_mut(panel_index, tab_index)
+        .code_actions
+        .take()
+        .and_then(|menu| menu.selected_action().cloned());
+    let (Some(action), Some(client)) = (action, client) else {
+        return;
+    };
+
+    spawn(async move {
+        match action {
+            CodeActionOrCommand::CodeAction(action) => {
+                if let Some(edit) = action.edit {
+                    apply_workspace_edit(radio_app_state, edit).await;
+                }
+                if let Some(command) = action.command {
+                    execute_command(radio_app_state, client, command).await;
+                }
+            }
+            CodeActionOrCommand::Command(command) => {
+                execute_command(radio_app_state, client, command).await;
+            }
+        }
+    });
+}
+
+/// Run a command in the language server, applying the edits it asks for.
+async fn execute_command(radio_app_state: RadioAppState, mut client: LSPClient, command: Command) {
+    let response = client
+        .execute_command(ExecuteCommandParams {
+            command: command.command,
+            arguments: command.arguments.unwrap_or_default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await;
+    if let Err(err) = response {
+        notify(
+            radio_app_state,
+            NotificationLevel::Error,
+            format!("Failed to run the command {}: {err}", command.title),
+            None,
+        );
+    }
+
+    for edit in client.take_applied_edits() {
+        apply_workspace_edit(radio_app_state, edit).await;
+    }
+}
+
+/// Apply the edits to the opened editors, or to the files directly if they are not opened.
+pub async fn apply_workspace_edit(mut radio_app_state: RadioAppState, edit: WorkspaceEdit) {
+    for (uri, edits) in workspace_edit_changes(edit) {
+        let Ok(path) = uri.to_file_path() else {
+            continue;
+        };
+
+        let opened_tab = radio_app_state.read().find_editor_tab(&path);
+        if let Some((panel_index, tab_index)) = opened_tab {
+            let client = {
+                let app_state = radio_app_state.read();
+                editor_lsp_client(&app_state, app_state.editor_tab(panel_index, tab_index))
+            };
+            let text = {
+                let mut app_state =
+                    radio_app_state.write_channel(Channel::follow_tab(panel_index, tab_index));
+                let editor = &mut app_state.editor_tab_mut(panel_index, tab_index).editor;
+                let edits = text_edits_to_ranges(&editor.rope, edits);
+                editor.apply_edits(&edits);
+                editor.text()
+            };
+
+            // Keep the language server in sync with the edited buffer
+            if let Some(mut client) = client {
+                client.change_file(uri, text);
+            }
+        } else {
+            let (transport, atomic_save) = {
+                let app_state = radio_app_state.read();
+                (
+                    app_state.transport(&path),
+                    app_state.settings.files.atomic_save,
+                )
+            };
+            if is_read_only(&transport, &path).await {
+                continue;
+            }
+            let Some((mut rope, format)) = read_text_file(&path, &transport).await else {
+                notify(
+                    radio_app_state,
+                    NotificationLevel::Error,
+                    format!("Failed to read {} to apply a code action", path.display()),
+                    None,
+                );
+                continue;
+            };
+
+            for (range, text) in text_edits_to_ranges(&rope, edits).into_iter().rev() {
+                rope.remove(range.clone());
+                rope.insert(range.start, &text);
+            }
+
+            let content = format.encode(&rope.to_string());
+            let result = if atomic_save {
+                transport.write_atomic(&path, &content).await
+            } else {
+                transport.write(&path, &content).await
+            };
+            if let Err(err) = result {
+                notify(
+                    radio_app_state,
+                    NotificationLevel::Error,
+                    format!("Failed to apply a code action to {}: {err}", path.display()),
+                    None,
+                );
+            }
+        }
+    }
+}
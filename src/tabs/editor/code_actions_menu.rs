@@ -0,0 +1,54 @@
+use freya::prelude::*;
+
+use super::{code_action_title, CodeActionsMenu};
+
+/// List of the code actions, shown below the line they were requested for.
+#[allow(non_snake_case)]
+#[component]
+pub fn CodeActionsMenuView(
+    menu: CodeActionsMenu,
+    offset_x: f32,
+    offset_y: f32,
+    onselect: EventHandler<usize>,
+) -> Element {
+    rsx!(
+        rect {
+            margin: "{offset_y} 0 0 {offset_x}",
+            width: "350",
+            padding: "4",
+            layer: "-50",
+            corner_radius: "6",
+            background: "rgb(45, 45, 45)",
+            border: "1 solid rgb(60, 60, 60)",
+            shadow: "0 4 15 8 rgb(0, 0, 0, 0.3)",
+            label {
+                padding: "4 6",
+                color: "rgb(135, 135, 135)",
+                "💡 Code Actions"
+            }
+            {menu.actions.iter().enumerate().map(|(i, action)| {
+                let background = if i == menu.selected {
+                    "rgb(65, 65, 65)"
+                } else {
+                    "transparent"
+                };
+                rsx!(
+                    rect {
+                        key: "{i}",
+                        width: "100%",
+                        padding: "6",
+                        corner_radius: "4",
+                        background,
+                        onclick: move |_| onselect.call(i),
+                        label {
+                            max_lines: "1",
+                            text_overflow: "ellipsis",
+                            color: "rgb(235, 235, 235)",
+                            "{code_action_title(action)}"
+                        }
+                    }
+                )
+            })}
+        }
+    )
+}
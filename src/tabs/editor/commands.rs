@@ -1,12 +1,25 @@
 use freya::prelude::spawn;
-use tokio::fs::OpenOptions;
 
 use crate::{
     constants::{BASE_FONT_SIZE, MAX_FONT_SIZE},
-    state::{AppStateUtils, Channel, EditorCommand, RadioAppState},
+    fs::{Encoding, LineEnding},
+    state::{AppStateUtils, Channel, CommandOutput, EditorCommand, RadioAppState},
+    symbols::parse_symbols,
+    utils::open_file_at,
 };
 
-use crate::tabs::editor::utils::AppStateEditorUtils;
+use crate::tabs::editor::{
+    code_actions::request_code_actions,
+    document_stats::show_document_stats,
+    export::{copy_html, export_html},
+    formatter::format_editor_tab,
+    todo::todo_text,
+    utils::{
+        compare_editor_tab_with_saved, save_editor_tab, toggle_editor_tab_lsp,
+        toggle_workspace_lsp, zoom_editor_tab, AppStateEditorUtils, TabEditorUtils,
+    },
+    BlockSelectionDirection, EditorData, Motion,
+};
 
 #[derive(Clone)]
 pub struct IncreaseFontSizeCommand(pub RadioAppState);
@@ -68,6 +81,37 @@ impl EditorCommand for DecreaseFontSizeCommand {
     }
 }
 
+#[derive(Clone)]
+pub struct ResetZoomCommand(pub RadioAppState);
+
+impl ResetZoomCommand {
+    pub fn id() -> &'static str {
+        "reset-zoom"
+    }
+}
+
+impl EditorCommand for ResetZoomCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Reset Zoom"
+    }
+
+    fn run(&self) {
+        let (panel, active_tab) = self.0.get_focused_data();
+
+        if let Some(active_tab) = active_tab {
+            zoom_editor_tab(self.0, panel, active_tab, |_| 1.0);
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SaveFileCommand(pub RadioAppState);
 
@@ -91,30 +135,1350 @@ impl EditorCommand for SaveFileCommand {
     }
 
     fn run(&self) {
-        let mut radio_app_state = self.0;
+        let (panel, active_tab) = self.0.get_focused_data();
+
+        if let Some(active_tab) = active_tab {
+            save_editor_tab(self.0, panel, active_tab);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct QuickJumpCommand(pub RadioAppState);
+
+impl QuickJumpCommand {
+    pub fn id() -> &'static str {
+        "quick-jump"
+    }
+}
+
+impl EditorCommand for QuickJumpCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Quick Jump"
+    }
+
+    fn run(&self) {
+        with_focused_editor(self.0, EditorData::start_quick_jump);
+    }
+}
+
+#[derive(Clone)]
+pub struct CompareWithSavedCommand(pub RadioAppState);
+
+impl CompareWithSavedCommand {
+    pub fn id() -> &'static str {
+        "compare-with-saved"
+    }
+}
+
+impl EditorCommand for CompareWithSavedCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Compare with Saved"
+    }
+
+    fn run(&self) {
+        let radio_app_state = self.0;
         let (panel, active_tab) = radio_app_state.get_focused_data();
 
         if let Some(active_tab) = active_tab {
-            let editor_data = {
-                let app_state = radio_app_state.read();
-                app_state.editor_tab_data(panel, active_tab)
-            };
+            compare_editor_tab_with_saved(radio_app_state, panel, active_tab);
+        }
+    }
+}
+
+/// Run an editing action in the focused editor.
+fn with_focused_editor(mut radio_app_state: RadioAppState, action: impl FnOnce(&mut EditorData)) {
+    let (panel, active_tab) = radio_app_state.get_focused_data();
+
+    if let Some(active_tab) = active_tab {
+        let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
+        if let Some(editor_tab) = app_state.try_editor_tab_mut(panel, active_tab) {
+            action(&mut editor_tab.editor);
+        }
+    }
+}
+
+/// Like [`with_focused_editor`] but for actions that modify the text,
+/// so read-only editors are skipped and preview tabs become permanent.
+fn edit_focused_editor(mut radio_app_state: RadioAppState, action: impl FnOnce(&mut EditorData)) {
+    let (panel, active_tab) = radio_app_state.get_focused_data();
+
+    if let Some(active_tab) = active_tab {
+        let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
+        if let Some(editor_tab) = app_state.try_editor_tab_mut(panel, active_tab) {
+            if editor_tab.editor.read_only {
+                return;
+            }
+
+            let history_change = editor_tab.editor.history.current_change();
+            action(&mut editor_tab.editor);
+            if editor_tab.editor.history.current_change() != history_change {
+                editor_tab.preview = false;
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DeleteLineCommand(pub RadioAppState);
+
+impl DeleteLineCommand {
+    pub fn id() -> &'static str {
+        "delete-line"
+    }
+}
+
+impl EditorCommand for DeleteLineCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Delete Line"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, EditorData::delete_lines);
+    }
+}
+
+#[derive(Clone)]
+pub struct InsertLineBelowCommand(pub RadioAppState);
+
+impl InsertLineBelowCommand {
+    pub fn id() -> &'static str {
+        "insert-line-below"
+    }
+}
+
+impl EditorCommand for InsertLineBelowCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Insert Line Below"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, EditorData::insert_line_below);
+    }
+}
+
+#[derive(Clone)]
+pub struct InsertLineAboveCommand(pub RadioAppState);
+
+impl InsertLineAboveCommand {
+    pub fn id() -> &'static str {
+        "insert-line-above"
+    }
+}
+
+impl EditorCommand for InsertLineAboveCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Insert Line Above"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, EditorData::insert_line_above);
+    }
+}
+
+#[derive(Clone)]
+pub struct MoveLineUpCommand(pub RadioAppState);
+
+impl MoveLineUpCommand {
+    pub fn id() -> &'static str {
+        "move-line-up"
+    }
+}
+
+impl EditorCommand for MoveLineUpCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Move Line Up"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, |editor| editor.move_lines(true));
+    }
+}
+
+#[derive(Clone)]
+pub struct MoveLineDownCommand(pub RadioAppState);
+
+impl MoveLineDownCommand {
+    pub fn id() -> &'static str {
+        "move-line-down"
+    }
+}
+
+impl EditorCommand for MoveLineDownCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Move Line Down"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, |editor| editor.move_lines(false));
+    }
+}
+
+#[derive(Clone)]
+pub struct DuplicateLineUpCommand(pub RadioAppState);
+
+impl DuplicateLineUpCommand {
+    pub fn id() -> &'static str {
+        "duplicate-line-up"
+    }
+}
+
+impl EditorCommand for DuplicateLineUpCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Duplicate Line Up"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, |editor| editor.duplicate_lines(false));
+    }
+}
+
+#[derive(Clone)]
+pub struct DuplicateLineDownCommand(pub RadioAppState);
+
+impl DuplicateLineDownCommand {
+    pub fn id() -> &'static str {
+        "duplicate-line-down"
+    }
+}
+
+impl EditorCommand for DuplicateLineDownCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Duplicate Line Down"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, |editor| editor.duplicate_lines(true));
+    }
+}
+
+#[derive(Clone)]
+pub struct JoinLinesCommand(pub RadioAppState);
+
+impl JoinLinesCommand {
+    pub fn id() -> &'static str {
+        "join-lines"
+    }
+}
+
+impl EditorCommand for JoinLinesCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Join Lines"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, EditorData::join_lines);
+    }
+}
 
-            if let Some((Some(file_path), rope, transport)) = editor_data {
-                spawn(async move {
-                    let writer = transport
-                        .open(&file_path, OpenOptions::new().write(true).truncate(true))
-                        .await
-                        .unwrap();
-                    let std_writer = writer.into_std().await;
-                    rope.write_to(std_writer).unwrap();
-                    let mut app_state =
-                        radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
-                    let editor_tab = app_state.try_editor_tab_mut(panel, active_tab);
-                    if let Some(editor_tab) = editor_tab {
-                        editor_tab.editor.mark_as_saved()
-                    }
-                });
+#[derive(Clone)]
+pub struct TransposeCharsCommand(pub RadioAppState);
+
+impl TransposeCharsCommand {
+    pub fn id() -> &'static str {
+        "transpose-chars"
+    }
+}
+
+impl EditorCommand for TransposeCharsCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Transpose Characters"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, EditorData::transpose_chars);
+    }
+}
+
+#[derive(Clone)]
+pub struct SwapSelectionAnchorsCommand(pub RadioAppState);
+
+impl SwapSelectionAnchorsCommand {
+    pub fn id() -> &'static str {
+        "swap-selection-anchors"
+    }
+}
+
+impl EditorCommand for SwapSelectionAnchorsCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Swap Selection Anchors"
+    }
+
+    fn run(&self) {
+        with_focused_editor(self.0, EditorData::swap_selection_anchors);
+    }
+}
+
+#[derive(Clone)]
+pub struct ExtendBlockSelectionCommand(pub RadioAppState, pub BlockSelectionDirection);
+
+impl ExtendBlockSelectionCommand {
+    pub fn id(direction: BlockSelectionDirection) -> &'static str {
+        match direction {
+            BlockSelectionDirection::Up => "extend-block-selection-up",
+            BlockSelectionDirection::Down => "extend-block-selection-down",
+            BlockSelectionDirection::Left => "extend-block-selection-left",
+            BlockSelectionDirection::Right => "extend-block-selection-right",
+        }
+    }
+}
+
+impl EditorCommand for ExtendBlockSelectionCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id(self.1)
+    }
+
+    fn text(&self) -> &str {
+        match self.1 {
+            BlockSelectionDirection::Up => "Extend Block Selection Up",
+            BlockSelectionDirection::Down => "Extend Block Selection Down",
+            BlockSelectionDirection::Left => "Extend Block Selection Left",
+            BlockSelectionDirection::Right => "Extend Block Selection Right",
+        }
+    }
+
+    fn run(&self) {
+        let (lines, columns) = self.1.offset();
+        with_focused_editor(self.0, |editor| editor.move_block_selection(lines, columns));
+    }
+}
+
+#[derive(Clone)]
+pub struct MoveCursorCommand(pub RadioAppState, pub Motion);
+
+impl MoveCursorCommand {
+    pub fn id(motion: Motion) -> &'static str {
+        match motion {
+            Motion::CharLeft => "move-cursor-char-left",
+            Motion::CharRight => "move-cursor-char-right",
+            Motion::LineUp => "move-cursor-line-up",
+            Motion::LineDown => "move-cursor-line-down",
+            Motion::WordLeft => "move-cursor-word-left",
+            Motion::WordRight => "move-cursor-word-right",
+            Motion::LineHome => "move-cursor-line-home",
+            Motion::LineEnd => "move-cursor-line-end",
+            Motion::TextStart => "move-cursor-text-start",
+            Motion::TextEnd => "move-cursor-text-end",
+            Motion::MatchingBracket => "move-cursor-matching-bracket",
+        }
+    }
+}
+
+impl EditorCommand for MoveCursorCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id(self.1)
+    }
+
+    fn text(&self) -> &str {
+        match self.1 {
+            Motion::CharLeft => "Move Cursor to Previous Character",
+            Motion::CharRight => "Move Cursor to Next Character",
+            Motion::LineUp => "Move Cursor to Previous Line",
+            Motion::LineDown => "Move Cursor to Next Line",
+            Motion::WordLeft => "Move Cursor to Previous Word",
+            Motion::WordRight => "Move Cursor to Next Word",
+            Motion::LineHome => "Move Cursor to Line Start",
+            Motion::LineEnd => "Move Cursor to Line End",
+            Motion::TextStart => "Move Cursor to File Start",
+            Motion::TextEnd => "Move Cursor to File End",
+            Motion::MatchingBracket => "Move Cursor to Matching Bracket",
+        }
+    }
+
+    fn run(&self) {
+        let motion = self.1;
+        with_focused_editor(self.0, |editor| editor.apply_motion(motion, false));
+    }
+}
+
+#[derive(Clone)]
+pub struct ExtendSelectionCommand(pub RadioAppState, pub Motion);
+
+impl ExtendSelectionCommand {
+    pub fn id(motion: Motion) -> &'static str {
+        match motion {
+            Motion::CharLeft => "extend-selection-char-left",
+            Motion::CharRight => "extend-selection-char-right",
+            Motion::LineUp => "extend-selection-line-up",
+            Motion::LineDown => "extend-selection-line-down",
+            Motion::WordLeft => "extend-selection-word-left",
+            Motion::WordRight => "extend-selection-word-right",
+            Motion::LineHome => "extend-selection-line-home",
+            Motion::LineEnd => "extend-selection-line-end",
+            Motion::TextStart => "extend-selection-text-start",
+            Motion::TextEnd => "extend-selection-text-end",
+            Motion::MatchingBracket => "extend-selection-matching-bracket",
+        }
+    }
+}
+
+impl EditorCommand for ExtendSelectionCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id(self.1)
+    }
+
+    fn text(&self) -> &str {
+        match self.1 {
+            Motion::CharLeft => "Extend Selection to Previous Character",
+            Motion::CharRight => "Extend Selection to Next Character",
+            Motion::LineUp => "Extend Selection to Previous Line",
+            Motion::LineDown => "Extend Selection to Next Line",
+            Motion::WordLeft => "Extend Selection to Previous Word",
+            Motion::WordRight => "Extend Selection to Next Word",
+            Motion::LineHome => "Extend Selection to Line Start",
+            Motion::LineEnd => "Extend Selection to Line End",
+            Motion::TextStart => "Extend Selection to File Start",
+            Motion::TextEnd => "Extend Selection to File End",
+            Motion::MatchingBracket => "Extend Selection to Matching Bracket",
+        }
+    }
+
+    fn run(&self) {
+        let motion = self.1;
+        with_focused_editor(self.0, |editor| editor.apply_motion(motion, true));
+    }
+}
+
+#[derive(Clone)]
+pub struct SelectWordCommand(pub RadioAppState);
+
+impl SelectWordCommand {
+    pub fn id() -> &'static str {
+        "select-word"
+    }
+}
+
+impl EditorCommand for SelectWordCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Select Word"
+    }
+
+    fn run(&self) {
+        with_focused_editor(self.0, |editor| editor.select_word());
+    }
+}
+
+#[derive(Clone)]
+pub struct SelectLineCommand(pub RadioAppState);
+
+impl SelectLineCommand {
+    pub fn id() -> &'static str {
+        "select-line"
+    }
+}
+
+impl EditorCommand for SelectLineCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Select Line"
+    }
+
+    fn run(&self) {
+        with_focused_editor(self.0, |editor| editor.select_lines());
+    }
+}
+
+#[derive(Clone)]
+pub struct SelectAllCommand(pub RadioAppState);
+
+impl SelectAllCommand {
+    pub fn id() -> &'static str {
+        "select-all"
+    }
+}
+
+impl EditorCommand for SelectAllCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Select All"
+    }
+
+    fn run(&self) {
+        with_focused_editor(self.0, |editor| editor.select_all());
+    }
+}
+
+#[derive(Clone)]
+pub struct IncrementNumberCommand(pub RadioAppState);
+
+impl IncrementNumberCommand {
+    pub fn id() -> &'static str {
+        "increment-number"
+    }
+}
+
+impl EditorCommand for IncrementNumberCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Increment Number"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, |editor| editor.increment_number(1));
+    }
+}
+
+#[derive(Clone)]
+pub struct DecrementNumberCommand(pub RadioAppState);
+
+impl DecrementNumberCommand {
+    pub fn id() -> &'static str {
+        "decrement-number"
+    }
+}
+
+impl EditorCommand for DecrementNumberCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Decrement Number"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, |editor| editor.increment_number(-1));
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleLineCommentCommand(pub RadioAppState);
+
+impl ToggleLineCommentCommand {
+    pub fn id() -> &'static str {
+        "toggle-line-comment"
+    }
+}
+
+impl EditorCommand for ToggleLineCommentCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Line Comment"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, EditorData::toggle_line_comment);
+    }
+}
+
+#[derive(Clone)]
+pub struct AddTodoCommand(pub RadioAppState);
+
+impl AddTodoCommand {
+    pub fn id() -> &'static str {
+        "add-todo"
+    }
+}
+
+impl EditorCommand for AddTodoCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Add TODO"
+    }
+
+    fn run(&self) {
+        let text = todo_text(&self.0.read().settings.editor.todo_template);
+        edit_focused_editor(self.0, |editor| editor.insert_todo(&text));
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleBlockCommentCommand(pub RadioAppState);
+
+impl ToggleBlockCommentCommand {
+    pub fn id() -> &'static str {
+        "toggle-block-comment"
+    }
+}
+
+impl EditorCommand for ToggleBlockCommentCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Block Comment"
+    }
+
+    fn run(&self) {
+        edit_focused_editor(self.0, EditorData::toggle_block_comment);
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleIndentWithTabsCommand(pub RadioAppState);
+
+impl ToggleIndentWithTabsCommand {
+    pub fn id() -> &'static str {
+        "toggle-indent-with-tabs"
+    }
+}
+
+impl EditorCommand for ToggleIndentWithTabsCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Indentation Using Tabs"
+    }
+
+    fn run(&self) {
+        with_focused_editor(self.0, |editor| {
+            editor.indentation.with_tabs = !editor.indentation.with_tabs
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct ChangeIndentWidthCommand(pub RadioAppState);
+
+impl ChangeIndentWidthCommand {
+    pub fn id() -> &'static str {
+        "change-indent-width"
+    }
+}
+
+impl EditorCommand for ChangeIndentWidthCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Change Indentation Width (2, 4, 8)"
+    }
+
+    fn run(&self) {
+        with_focused_editor(self.0, |editor| {
+            editor.indentation.width = match editor.indentation.width {
+                2 => 4,
+                4 => 8,
+                _ => 2,
+            }
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct SetLineEndingCommand(pub RadioAppState, pub LineEnding);
+
+impl EditorCommand for SetLineEndingCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        match self.1 {
+            LineEnding::Lf => "set-line-ending-lf",
+            LineEnding::Crlf => "set-line-ending-crlf",
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self.1 {
+            LineEnding::Lf => "Change Line Endings to LF",
+            LineEnding::Crlf => "Change Line Endings to CRLF",
+        }
+    }
+
+    fn run(&self) {
+        let line_ending = self.1;
+        edit_focused_editor(self.0, |editor| editor.format.line_ending = line_ending);
+    }
+}
+
+#[derive(Clone)]
+pub struct SetEncodingCommand(pub RadioAppState, pub Encoding);
+
+impl EditorCommand for SetEncodingCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        match self.1 {
+            Encoding::Utf8 => "set-encoding-utf8",
+            Encoding::Utf8Bom => "set-encoding-utf8-bom",
+            Encoding::Utf16Le => "set-encoding-utf16-le",
+            Encoding::Utf16Be => "set-encoding-utf16-be",
+            Encoding::Latin1 => "set-encoding-latin1",
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self.1 {
+            Encoding::Utf8 => "Change Encoding to UTF-8",
+            Encoding::Utf8Bom => "Change Encoding to UTF-8 with BOM",
+            Encoding::Utf16Le => "Change Encoding to UTF-16 LE",
+            Encoding::Utf16Be => "Change Encoding to UTF-16 BE",
+            Encoding::Latin1 => "Change Encoding to Latin-1",
+        }
+    }
+
+    fn run(&self) {
+        let encoding = self.1;
+        edit_focused_editor(self.0, |editor| editor.format.encoding = encoding);
+    }
+}
+
+#[derive(Clone)]
+pub struct GoToDefinitionCommand(pub RadioAppState);
+
+impl GoToDefinitionCommand {
+    pub fn id() -> &'static str {
+        "go-to-definition"
+    }
+}
+
+impl EditorCommand for GoToDefinitionCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Go to Definition"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+        let Some(active_tab) = active_tab else {
+            return;
+        };
+
+        let (local_definition, definition) = {
+            let app_state = radio_app_state.read();
+            let Some(editor_tab) = app_state.panel(panel).tab(active_tab).as_text_editor() else {
+                return;
+            };
+            let Some(word) = editor_tab.editor.word_at_cursor() else {
+                return;
+            };
+
+            // The opened text is parsed again as it might have unsaved changes
+            let local_definition = parse_symbols(&editor_tab.editor.rope)
+                .into_iter()
+                .find(|symbol| symbol.name == word);
+            let definition = app_state.symbol_index.definitions(&word).into_iter().next();
+            (local_definition, definition)
+        };
+
+        if let Some(symbol) = local_definition {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
+            app_state
+                .editor_tab_mut(panel, active_tab)
+                .editor
+                .select_range(symbol.range);
+        } else if let Some(definition) = definition {
+            spawn(open_file_at(
+                radio_app_state,
+                definition.path,
+                definition.root_path,
+                definition.symbol.range,
+            ));
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CompleteWordCommand(pub RadioAppState);
+
+impl CompleteWordCommand {
+    pub fn id() -> &'static str {
+        "complete-word"
+    }
+}
+
+impl EditorCommand for CompleteWordCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Complete Word"
+    }
+
+    fn run(&self) {
+        let radio_app_state = self.0;
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+        let Some(active_tab) = active_tab else {
+            return;
+        };
+
+        let symbols = {
+            let app_state = radio_app_state.read();
+            app_state
+                .panel(panel)
+                .tab(active_tab)
+                .as_text_editor()
+                .map(|editor_tab| editor_tab.editor.word_before_cursor())
+                .filter(|word| !word.is_empty())
+                .map(|word| app_state.symbol_index.completions(&word))
+                .unwrap_or_default()
+        };
+
+        edit_focused_editor(radio_app_state, |editor| editor.complete_word(symbols));
+    }
+}
+
+#[derive(Clone)]
+pub struct ShowCodeActionsCommand(pub RadioAppState);
+
+impl ShowCodeActionsCommand {
+    pub fn id() -> &'static str {
+        "show-code-actions"
+    }
+}
+
+impl EditorCommand for ShowCodeActionsCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Show Code Actions"
+    }
+
+    fn run(&self) {
+        let radio_app_state = self.0;
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+        let Some(active_tab) = active_tab else {
+            return;
+        };
+
+        let is_text_editor = radio_app_state
+            .read()
+            .panel(panel)
+            .tab(active_tab)
+            .as_text_editor()
+            .is_some();
+        if is_text_editor {
+            request_code_actions(radio_app_state, panel, active_tab);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DocumentStatsCommand(pub RadioAppState);
+
+impl DocumentStatsCommand {
+    pub fn id() -> &'static str {
+        "show-document-stats"
+    }
+}
+
+impl EditorCommand for DocumentStatsCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Document Statistics"
+    }
+
+    fn run(&self) {
+        let (panel, active_tab) = self.0.get_focused_data();
+        if let Some(active_tab) = active_tab {
+            show_document_stats(self.0, panel, active_tab);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FormatFileCommand(pub RadioAppState);
+
+impl FormatFileCommand {
+    pub fn id() -> &'static str {
+        "format-file"
+    }
+}
+
+impl EditorCommand for FormatFileCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Format File"
+    }
+
+    fn run(&self) {
+        let (panel, active_tab) = self.0.get_focused_data();
+        if let Some(active_tab) = active_tab {
+            format_editor_tab(self.0, panel, active_tab);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ExportHtmlCommand(pub RadioAppState);
+
+impl ExportHtmlCommand {
+    pub fn id() -> &'static str {
+        "export-html"
+    }
+}
+
+impl EditorCommand for ExportHtmlCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Export as HTML"
+    }
+
+    fn run(&self) {
+        let (panel, active_tab) = self.0.get_focused_data();
+        if let Some(active_tab) = active_tab {
+            export_html(self.0, panel, active_tab);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CopyHtmlCommand(pub RadioAppState);
+
+impl CopyHtmlCommand {
+    pub fn id() -> &'static str {
+        "copy-html"
+    }
+}
+
+impl EditorCommand for CopyHtmlCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Copy as HTML"
+    }
+
+    fn run(&self) {
+        let (panel, active_tab) = self.0.get_focused_data();
+        if let Some(active_tab) = active_tab {
+            copy_html(self.0, panel, active_tab);
+        }
+    }
+}
+
+/// How many matching lines are listed at most by [`GrepCommand`].
+const MAX_GREP_LINES: usize = 500;
+
+#[derive(Clone)]
+pub struct GrepCommand(pub RadioAppState);
+
+impl GrepCommand {
+    pub fn id() -> &'static str {
+        "grep"
+    }
+}
+
+impl EditorCommand for GrepCommand {
+    fn matches(&self, input: &str) -> bool {
+        let input = input.to_lowercase();
+        input.starts_with("grep ") || self.text().to_lowercase().contains(&input)
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "grep: List Matching Lines"
+    }
+
+    fn run(&self) {
+        // The lines are only listed in the Commander
+    }
+
+    fn run_with_input(&self, input: &str) -> Option<CommandOutput> {
+        let pattern = input
+            .get(..5)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("grep "))
+            .map(|_| input[5..].trim())
+            .unwrap_or_default();
+        if pattern.is_empty() {
+            return Some(CommandOutput::Text(
+                "Type what to look for after `grep`, e.g. `grep TODO`.".to_string(),
+            ));
+        }
+
+        let (panel, active_tab) = self.0.get_focused_data();
+        let app_state = self.0.read();
+        let Some(editor_tab) = active_tab
+            .and_then(|active_tab| app_state.panel(panel).tab(active_tab).as_text_editor())
+        else {
+            return Some(CommandOutput::Text("No file is focused.".to_string()));
+        };
+
+        let lines = editor_tab
+            .editor
+            .rope
+            .lines()
+            .enumerate()
+            .filter_map(|(line_index, line)| {
+                let line = line.to_string();
+                line.contains(pattern)
+                    .then(|| format!("{}: {}", line_index + 1, line.trim()))
+            })
+            .take(MAX_GREP_LINES)
+            .collect::<Vec<String>>();
+
+        if lines.is_empty() {
+            Some(CommandOutput::Text(format!(
+                "No lines contain `{pattern}`."
+            )))
+        } else {
+            Some(CommandOutput::List(lines))
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleFileLspCommand(pub RadioAppState);
+
+impl ToggleFileLspCommand {
+    pub fn id() -> &'static str {
+        "toggle-file-lsp"
+    }
+}
+
+impl EditorCommand for ToggleFileLspCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Language Server for File"
+    }
+
+    fn run(&self) {
+        let (panel, active_tab) = self.0.get_focused_data();
+
+        if let Some(active_tab) = active_tab {
+            let is_editor = self
+                .0
+                .read()
+                .panel(panel)
+                .tab(active_tab)
+                .as_text_editor()
+                .is_some();
+            if is_editor {
+                toggle_editor_tab_lsp(self.0, panel, active_tab);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleWorkspaceLspCommand(pub RadioAppState);
+
+impl ToggleWorkspaceLspCommand {
+    pub fn id() -> &'static str {
+        "toggle-workspace-lsp"
+    }
+}
+
+impl EditorCommand for ToggleWorkspaceLspCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Language Server for Workspace"
+    }
+
+    fn run(&self) {
+        let (panel, active_tab) = self.0.get_focused_data();
+
+        if let Some(active_tab) = active_tab {
+            let root_path = self
+                .0
+                .read()
+                .panel(panel)
+                .tab(active_tab)
+                .as_text_editor()
+                .and_then(|editor_tab| editor_tab.editor.editor_type.paths())
+                .map(|(_, root_path)| root_path.clone());
+            if let Some(root_path) = root_path {
+                toggle_workspace_lsp(self.0, root_path);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleSplitEditorCommand(pub RadioAppState);
+
+impl ToggleSplitEditorCommand {
+    pub fn id() -> &'static str {
+        "toggle-split-editor"
+    }
+}
+
+impl EditorCommand for ToggleSplitEditorCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Split Editor"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+
+        if let Some(active_tab) = active_tab {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
+            if let Some(editor_tab) = app_state.try_editor_tab_mut(panel, active_tab) {
+                editor_tab.toggle_split();
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleBookmarkCommand(pub RadioAppState);
+
+impl ToggleBookmarkCommand {
+    pub fn id() -> &'static str {
+        "toggle-bookmark"
+    }
+}
+
+impl EditorCommand for ToggleBookmarkCommand {
+    fn matches(&self, input: &str) -> bool {
+        self.text().to_lowercase().contains(&input.to_lowercase())
+    }
+
+    fn id(&self) -> &str {
+        Self::id()
+    }
+
+    fn text(&self) -> &str {
+        "Toggle Bookmark"
+    }
+
+    fn run(&self) {
+        let mut radio_app_state = self.0;
+        let (panel, active_tab) = radio_app_state.get_focused_data();
+
+        if let Some(active_tab) = active_tab {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
+            if let Some(editor_tab) = app_state.try_editor_tab_mut(panel, active_tab) {
+                editor_tab.editor.toggle_bookmark();
             }
         }
     }
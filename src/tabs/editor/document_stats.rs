@@ -0,0 +1,56 @@
+use freya::prelude::spawn;
+use tracing::info;
+pub use valin_core::document_stats::DocumentStats;
+
+use crate::state::{Channel, RadioAppState};
+
+use super::{AppStateEditorUtils, TabEditorUtils};
+
+/// Compute the statistics of an editor in the background and show them in its popover.
+pub fn show_document_stats(radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    let editor_data = {
+        let app_state = radio_app_state.read();
+        app_state
+            .panel(panel)
+            .tab(tab)
+            .as_text_editor()
+            .map(|editor_tab| {
+                let editor = &editor_tab.editor;
+                (
+                    editor.rope.clone(),
+                    editor.format,
+                    editor.indentation,
+                    editor.path().cloned(),
+                )
+            })
+    };
+    let Some((rope, format, indentation, path)) = editor_data else {
+        return;
+    };
+
+    spawn(async move {
+        let stats =
+            tokio::task::spawn_blocking(move || DocumentStats::new(&rope, format, indentation))
+                .await;
+        let stats = match stats {
+            Ok(stats) => stats,
+            Err(err) => {
+                info!("Failed to compute the document statistics: {err:?}");
+                return;
+            }
+        };
+
+        // The tab might have been moved or closed in the meantime
+        let mut radio_app_state = radio_app_state;
+        let opened_tab = match path {
+            Some(path) => radio_app_state.read().find_editor_tab(&path),
+            None => Some((panel, tab)),
+        };
+        if let Some((panel, tab)) = opened_tab {
+            let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+            if let Some(editor_tab) = app_state.try_editor_tab_mut(panel, tab) {
+                editor_tab.document_stats = Some(stats);
+            }
+        }
+    });
+}
@@ -0,0 +1,77 @@
+use freya::prelude::*;
+
+use crate::processes::format_bytes;
+
+use super::DocumentStats;
+
+/// Statistics of the text of an editor, shown over its top right corner.
+#[allow(non_snake_case)]
+#[component]
+pub fn DocumentStatsPopover(stats: DocumentStats, onclose: EventHandler<()>) -> Element {
+    let (longest_line, longest_line_len) = stats.longest_line;
+    let rows = [
+        ("Lines", stats.lines.to_string()),
+        ("Words", stats.words.to_string()),
+        ("Characters", stats.chars.to_string()),
+        ("Size", format_bytes(stats.bytes as u64)),
+        (
+            "Longest Line",
+            format!("{} ({longest_line_len} characters)", longest_line + 1),
+        ),
+        ("Indentation", stats.indentation.to_string()),
+    ];
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "0",
+            direction: "horizontal",
+            main_align: "end",
+            rect {
+                margin: "8 20 0 0",
+                width: "280",
+                padding: "4",
+                layer: "-50",
+                corner_radius: "6",
+                background: "rgb(45, 45, 45)",
+                border: "1 solid rgb(60, 60, 60)",
+                shadow: "0 4 15 8 rgb(0, 0, 0, 0.3)",
+                rect {
+                    width: "100%",
+                    direction: "horizontal",
+                    cross_align: "center",
+                    label {
+                        width: "fill",
+                        padding: "4 6",
+                        color: "rgb(135, 135, 135)",
+                        "📄 Document Statistics"
+                    }
+                    Button {
+                        onpress: move |_| onclose.call(()),
+                        label {
+                            "✕"
+                        }
+                    }
+                }
+                for (name, value) in rows {
+                    rect {
+                        key: "{name}",
+                        width: "100%",
+                        direction: "horizontal",
+                        padding: "4 6",
+                        label {
+                            width: "50%",
+                            color: "rgb(160, 160, 160)",
+                            "{name}"
+                        }
+                        label {
+                            width: "50%",
+                            color: "rgb(235, 235, 235)",
+                            "{value}"
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
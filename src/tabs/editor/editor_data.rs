@@ -1,13 +1,48 @@
-use std::{cmp::Ordering, fmt::Display, ops::Range, path::PathBuf};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::BTreeSet,
+    fmt::Display,
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Range,
+    path::PathBuf,
+};
 
 use dioxus_sdk::clipboard::UseClipboard;
-use freya::hooks::{EditorHistory, HistoryChange, Line, TextCursor, TextEditor};
-use freya::prelude::Rope;
+use freya::hooks::{Line, TextCursor, TextEditor};
+use freya::prelude::{keyboard::Modifiers, Code, Key, Rope};
 use freya_hooks::LinesIterator;
 use lsp_types::Url;
 use skia_safe::textlayout::FontCollection;
 
-use crate::{fs::FSTransport, lsp::LanguageId, metrics::EditorMetrics};
+use crate::{
+    fs::{FSTransport, TextFormat},
+    language::LanguageConfig,
+    lsp::LanguageId,
+    metrics::EditorMetrics,
+    parser::{SyntaxType, TextNode},
+    state::{Bookmark, NavigationLocation},
+};
+
+use super::{
+    motion::matching_bracket,
+    numbers::increment_number,
+    persisted_history::{load_history, save_history},
+    BlockSelection, EditorHistory, HistoryChange, Indentation, Motion, QuickJump, QuickJumpEvent,
+};
+
+/// Keywords of the blocks pinned by the sticky scroll.
+const SCOPE_KEYWORDS: &[&str] = &[
+    "fn",
+    "impl",
+    "struct",
+    "enum",
+    "trait",
+    "mod",
+    "function",
+    "class",
+    "interface",
+];
 
 #[derive(Clone, PartialEq)]
 pub enum EditorType {
@@ -58,8 +93,25 @@ pub struct EditorData {
     pub(crate) selected: Option<(usize, usize)>,
     pub(crate) clipboard: UseClipboard,
     pub(crate) last_saved_history_change: usize,
+    /// Digest of the text in disk when it was read or saved, to notice when something else changes it.
+    pub(crate) saved_digest: u64,
     pub(crate) transport: FSTransport,
     pub(crate) metrics: EditorMetrics,
+    pub(crate) quick_jump: Option<QuickJump>,
+    /// Files without write permission can't be edited.
+    pub(crate) read_only: bool,
+    pub(crate) indentation: Indentation,
+    /// Encoding and line ending used when saving.
+    pub(crate) format: TextFormat,
+    pub(crate) saved_format: TextFormat,
+    /// Completions of the word being completed and the index of the one in use.
+    word_completion: Option<(Vec<String>, usize)>,
+    /// Bookmarked lines, they follow their content as lines are inserted and removed above.
+    pub(crate) bookmarks: BTreeSet<usize>,
+    /// Rectangular selection, edited as a whole by typing or pasting into it.
+    pub(crate) block_selection: Option<BlockSelection>,
+    /// Comment tokens, brackets and string delimiters of the language of the text.
+    pub(crate) language: LanguageConfig,
 }
 
 impl EditorData {
@@ -70,11 +122,14 @@ impl EditorData {
         clipboard: UseClipboard,
         transport: FSTransport,
         font_size: f32,
+        font_family: &str,
         font_collection: &FontCollection,
+        language: LanguageConfig,
     ) -> Self {
         let mut metrics = EditorMetrics::new();
-        metrics.measure_longest_line(font_size, &rope, font_collection);
-        metrics.run_parser(&rope);
+        metrics.measure_longest_line(font_size, font_family, &rope, font_collection);
+        metrics.run_parser(&rope, &language);
+        let saved_digest = text_digest(&rope);
 
         Self {
             editor_type,
@@ -83,9 +138,19 @@ impl EditorData {
             selected: None,
             history: EditorHistory::new(),
             last_saved_history_change: 0,
+            saved_digest,
             clipboard,
             transport,
             metrics,
+            quick_jump: None,
+            read_only: false,
+            indentation: Indentation::default(),
+            format: TextFormat::default(),
+            saved_format: TextFormat::default(),
+            word_completion: None,
+            bookmarks: BTreeSet::new(),
+            block_selection: None,
+            language,
         }
     }
 
@@ -101,16 +166,121 @@ impl EditorData {
 
     pub fn is_edited(&self) -> bool {
         self.history.current_change() != self.last_saved_history_change
+            || self.format != self.saved_format
     }
 
     pub fn mark_as_saved(&mut self) {
-        self.last_saved_history_change = self.history.current_change();
+        self.mark_change_as_saved(self.history.current_change(), self.format);
+    }
+
+    /// Mark the text as it was at the given change of the history as the saved one,
+    /// for when it was written while the editor kept changing.
+    pub fn mark_change_as_saved(&mut self, change: usize, format: TextFormat) {
+        self.last_saved_history_change = change;
+        self.saved_format = format;
+    }
+
+    /// Replace the text with a new version of it, as a single undoable edit of the part that changed.
+    /// Read-only editors are left as they are.
+    pub fn replace_text(&mut self, text: &str) {
+        if self.read_only {
+            return;
+        }
+
+        let old_text = self.rope.to_string();
+        let old_len = old_text.chars().count();
+        let new_len = text.chars().count();
+
+        let prefix = old_text
+            .chars()
+            .zip(text.chars())
+            .take_while(|(old, new)| old == new)
+            .count();
+        let suffix = old_text
+            .chars()
+            .rev()
+            .zip(text.chars().rev())
+            .take(old_len.min(new_len) - prefix)
+            .take_while(|(old, new)| old == new)
+            .count();
+
+        let replaced = prefix..old_len - suffix;
+        let replacement = text
+            .chars()
+            .skip(prefix)
+            .take(new_len - prefix - suffix)
+            .collect::<String>();
+        if !replaced.is_empty() || !replacement.is_empty() {
+            self.apply_edits(&[(replaced, replacement)]);
+        }
+    }
+
+    /// Store the undo history in disk, up to the last save and with at most `limit` entries.
+    pub fn persist_history(&self, limit: usize) {
+        let Some(path) = self.path() else {
+            return;
+        };
+
+        // The history must match the content in disk, so unsaved changes are left to be redone
+        let mut rope = self.rope.clone();
+        let mut history = self.history.clone();
+        history.rewind_to(&mut rope, self.last_saved_history_change);
+        history.truncate_oldest(limit);
+
+        save_history(path, &rope, &history);
+    }
+
+    /// Restore the undo history previously stored in disk.
+    pub fn restore_history(&mut self) {
+        let history = self.path().and_then(|path| load_history(path, &self.rope));
+        if let Some(history) = history {
+            self.last_saved_history_change = history.current_change();
+            self.history = history;
+        }
     }
 
     pub fn path(&self) -> Option<&PathBuf> {
         self.editor_type.paths().map(|(path, _)| path)
     }
 
+    /// Where the cursor is, to navigate back to it later.
+    pub fn navigation_location(&self) -> Option<NavigationLocation> {
+        let (path, root_path) = self.editor_type.paths()?;
+        Some(NavigationLocation {
+            path: path.clone(),
+            root_path: root_path.clone(),
+            line: self.cursor_row(),
+            column: self.cursor_col(),
+        })
+    }
+
+    /// Bookmark the line of the cursor, or remove its bookmark.
+    pub fn toggle_bookmark(&mut self) {
+        let line = self.cursor_row();
+        if !self.bookmarks.remove(&line) {
+            self.bookmarks.insert(line);
+        }
+    }
+
+    pub fn is_bookmarked(&self, line: usize) -> bool {
+        self.bookmarks.contains(&line)
+    }
+
+    /// Bookmarks of the file, sorted by line.
+    pub fn bookmarks(&self) -> Vec<Bookmark> {
+        let Some((path, root_path)) = self.editor_type.paths() else {
+            return Vec::new();
+        };
+        self.bookmarks
+            .iter()
+            .map(|line| Bookmark {
+                path: path.clone(),
+                line: *line,
+                root_path: root_path.clone(),
+            })
+            .collect()
+    }
+
     pub fn cursor(&self) -> TextCursor {
         self.cursor.clone()
     }
@@ -120,17 +290,1326 @@ impl EditorData {
     }
 
     pub fn run_parser(&mut self) {
-        self.metrics.run_parser(&self.rope);
+        self.metrics.run_parser(&self.rope, &self.language);
     }
 
-    pub fn measure_longest_line(&mut self, font_size: f32, font_collection: &FontCollection) {
+    /// Rebuild the text and undo history into compact storage, returning how many bytes were reclaimed.
+    /// The syntax blocks are only trimmed if `trim_syntax_blocks` is set, visible editors would grow them back.
+    pub fn compact(&mut self, trim_syntax_blocks: bool) -> usize {
+        let size = |editor: &Self| {
+            editor.rope.capacity()
+                + editor.history.heap_size()
+                + editor.metrics.syntax_blocks.heap_size()
+        };
+        let before = size(self);
+
+        self.rope.shrink_to_fit();
+        self.history.shrink_to_fit();
+        if trim_syntax_blocks {
+            self.metrics.syntax_blocks.shrink_to_fit();
+        }
+
+        before.saturating_sub(size(self))
+    }
+
+    pub fn measure_longest_line(
+        &mut self,
+        font_size: f32,
+        font_family: &str,
+        font_collection: &FontCollection,
+    ) {
         self.metrics
-            .measure_longest_line(font_size, &self.rope, font_collection);
+            .measure_longest_line(font_size, font_family, &self.rope, font_collection);
     }
 
     pub fn editor_type(&self) -> &EditorType {
         &self.editor_type
     }
+
+    pub fn start_quick_jump(&mut self) {
+        self.quick_jump = Some(QuickJump::default());
+    }
+
+    pub fn cancel_quick_jump(&mut self) {
+        self.quick_jump = None;
+    }
+
+    /// Feed a typed character to the active quick jump, moving the cursor once a hint is matched.
+    pub fn quick_jump_input(&mut self, ch: char, visible_lines: Range<usize>) {
+        if let Some(quick_jump) = self.quick_jump.as_mut() {
+            match quick_jump.input(ch, &self.rope, visible_lines) {
+                QuickJumpEvent::Pending => {}
+                QuickJumpEvent::Jump(pos) => {
+                    self.quick_jump = None;
+                    self.selected = None;
+                    self.cursor = TextCursor::new(pos);
+                }
+                QuickJumpEvent::Cancel => {
+                    self.quick_jump = None;
+                }
+            }
+        }
+    }
+
+    /// Group the changes made in the closure so they are undone at once.
+    pub fn with_transaction(&mut self, f: impl FnOnce(&mut Self)) {
+        self.history.begin_transaction();
+        f(self);
+        self.history.end_transaction();
+    }
+
+    /// Move the cursor and the selection by the given amount of chars.
+    fn shift_cursor(&mut self, offset: isize) {
+        let shift = |pos: usize| pos.saturating_add_signed(offset);
+        self.cursor = TextCursor::new(shift(self.cursor_pos()));
+        if let Some((from, to)) = self.selected {
+            self.selected = Some((shift(from), shift(to)));
+        }
+    }
+
+    /// Content of the given lines without their line breaks,
+    /// and whether the last one has a line break.
+    fn lines_content(&self, lines: Range<usize>) -> (Vec<String>, bool) {
+        let mut has_line_break = false;
+        let content = lines
+            .map(|line_idx| {
+                let line = self.rope.line(line_idx).to_string();
+                has_line_break = line.ends_with('\n');
+                line.trim_end_matches('\n').to_owned()
+            })
+            .collect();
+        (content, has_line_break)
+    }
+
+    /// Replace the given lines with new ones.
+    fn replace_lines(&mut self, lines: Range<usize>, content: &[String], has_line_break: bool) {
+        let start = self.rope.line_to_char(lines.start);
+        let end = self.rope.line_to_char(lines.end);
+
+        let mut text = content.join("\n");
+        if has_line_break {
+            text.push('\n');
+        }
+
+        self.remove(start..end);
+        self.insert(&text, start);
+    }
+
+    /// Move the selected lines (or the cursor line) one line up or down.
+    pub fn move_lines(&mut self, up: bool) {
+        let lines = self.selected_lines();
+        let mut len_lines = self.rope.len_lines();
+
+        // The empty line after a trailing line break is not swapped with
+        if self.rope.len_chars() > 0 && self.rope.char(self.rope.len_chars() - 1) == '\n' {
+            len_lines -= 1;
+        }
+
+        let region = if up {
+            if lines.start == 0 {
+                return;
+            }
+            lines.start - 1..lines.end
+        } else {
+            if lines.end >= len_lines {
+                return;
+            }
+            lines.start..lines.end + 1
+        };
+
+        let (mut content, has_line_break) = self.lines_content(region.clone());
+        let offset = if up {
+            let previous_line = content.remove(0);
+            let offset = -(previous_line.chars().count() as isize + 1);
+            content.push(previous_line);
+            offset
+        } else {
+            let next_line = content.pop().unwrap_or_default();
+            let offset = next_line.chars().count() as isize + 1;
+            content.insert(0, next_line);
+            offset
+        };
+
+        self.with_transaction(|editor| {
+            editor.replace_lines(region, &content, has_line_break);
+        });
+        self.shift_cursor(offset);
+        self.run_parser();
+    }
+
+    /// Duplicate the selected lines (or the cursor line), keeping the cursor
+    /// in the upper copy or moving it to the lower one.
+    pub fn duplicate_lines(&mut self, down: bool) {
+        let lines = self.selected_lines();
+        let (content, has_line_break) = self.lines_content(lines.clone());
+
+        let mut text = content.join("\n");
+        let end = self.rope.line_to_char(lines.end);
+        if has_line_break {
+            text.push('\n');
+        } else {
+            text.insert(0, '\n');
+        }
+
+        self.with_transaction(|editor| {
+            editor.insert(&text, end);
+        });
+
+        if down {
+            self.shift_cursor(text.chars().count() as isize);
+        }
+        self.run_parser();
+    }
+
+    /// Join the selected lines, or the cursor line with the next one.
+    pub fn join_lines(&mut self) {
+        let mut lines = self.selected_lines();
+        if lines.len() == 1 {
+            lines.end += 1;
+        }
+        let lines = lines.start..lines.end.min(self.rope.len_lines());
+        if lines.len() < 2 {
+            return;
+        }
+
+        let (content, has_line_break) = self.lines_content(lines.clone());
+        let mut joined = String::new();
+        for (i, line) in content.iter().enumerate() {
+            if i == 0 {
+                joined.push_str(line);
+                continue;
+            }
+            let line = line.trim_start();
+            if !joined.is_empty() && !joined.ends_with(char::is_whitespace) && !line.is_empty() {
+                joined.push(' ');
+            }
+            joined.push_str(line);
+        }
+
+        let line_start = self.rope.line_to_char(lines.start);
+        self.with_transaction(|editor| {
+            editor.replace_lines(lines, &[joined.clone()], has_line_break);
+        });
+
+        self.selected = None;
+        self.cursor = TextCursor::new(line_start + joined.chars().count());
+        self.run_parser();
+    }
+
+    /// Swap the chars around the cursor, or the last two chars when at the end of a line.
+    pub fn transpose_chars(&mut self) {
+        let pos = self.cursor_pos();
+        let len_chars = self.rope.len_chars();
+        let is_line_end = pos == len_chars || self.rope.char(pos) == '\n';
+
+        let (start, cursor) = if is_line_end {
+            (pos.saturating_sub(2), pos)
+        } else {
+            (pos.saturating_sub(1), pos + 1)
+        };
+
+        if pos == 0 || start + 2 > len_chars {
+            return;
+        }
+
+        let (first, second) = (self.rope.char(start), self.rope.char(start + 1));
+        if first == '\n' || second == '\n' {
+            return;
+        }
+
+        self.with_transaction(|editor| {
+            editor.remove(start..start + 2);
+            editor.insert(&format!("{second}{first}"), start);
+        });
+
+        self.selected = None;
+        self.cursor = TextCursor::new(cursor);
+        self.run_parser();
+    }
+
+    /// Add `delta` to the number under the cursor, or to the next one in the line.
+    pub fn increment_number(&mut self, delta: i64) {
+        let line = self.cursor_row();
+        let line_start = self.rope.line_to_char(line);
+        let line_text = self.rope.line(line).to_string();
+
+        let Some((range, text)) = increment_number(&line_text, self.cursor_col(), delta) else {
+            return;
+        };
+        let start = line_start + range.start;
+
+        self.with_transaction(|editor| {
+            editor.remove(start..line_start + range.end);
+            editor.insert(&text, start);
+        });
+
+        // Place the cursor in the last digit
+        self.selected = None;
+        self.cursor = TextCursor::new(start + text.chars().count() - 1);
+        self.run_parser();
+    }
+
+    /// Move the cursor to the other end of the selection.
+    pub fn swap_selection_anchors(&mut self) {
+        if let Some((from, to)) = self.selected {
+            self.selected = Some((to, from));
+            self.cursor = TextCursor::new(from);
+        }
+    }
+
+    /// Start a block selection in the given line and column.
+    pub fn start_block_selection(&mut self, line: usize, column: usize) {
+        let line = line.min(self.rope.len_lines() - 1);
+        self.set_block_selection(BlockSelection::new(line, column));
+    }
+
+    /// Move the end of the block selection to the given line and column, starting
+    /// one from the cursor if there is none.
+    pub fn extend_block_selection(&mut self, line: usize, column: usize) {
+        let mut block_selection = self
+            .block_selection
+            .unwrap_or_else(|| BlockSelection::new(self.cursor_row(), self.cursor_col()));
+        block_selection.head = (line.min(self.rope.len_lines() - 1), column);
+        self.set_block_selection(block_selection);
+    }
+
+    /// Move the end of the block selection (or the cursor) by the given lines and columns.
+    pub fn move_block_selection(&mut self, lines: isize, columns: isize) {
+        let (line, column) = self
+            .block_selection
+            .map(|block_selection| block_selection.head)
+            .unwrap_or((self.cursor_row(), self.cursor_col()));
+        self.extend_block_selection(
+            line.saturating_add_signed(lines),
+            column.saturating_add_signed(columns),
+        );
+    }
+
+    pub fn clear_block_selection(&mut self) {
+        self.block_selection = None;
+    }
+
+    fn set_block_selection(&mut self, block_selection: BlockSelection) {
+        self.selected = None;
+        self.cursor = TextCursor::new(block_selection.cursor_pos(&self.rope));
+        self.block_selection = Some(block_selection);
+    }
+
+    /// Text of every line of the block selection, one per line.
+    pub fn block_selected_text(&self) -> Option<String> {
+        let block_selection = self.block_selection?;
+        let lines = block_selection
+            .ranges(&self.rope)
+            .into_iter()
+            .map(|range| self.rope.slice(range).to_string())
+            .collect::<Vec<String>>();
+        Some(lines.join("\n"))
+    }
+
+    /// Replace every line of the block selection with its text, leaving an empty
+    /// block selection at the given column.
+    fn replace_block_selection(&mut self, texts: Vec<String>, column: usize) {
+        let Some(block_selection) = self.block_selection else {
+            return;
+        };
+        let edits = block_selection
+            .ranges(&self.rope)
+            .into_iter()
+            .zip(texts)
+            .collect::<Vec<(Range<usize>, String)>>();
+        self.apply_edits(&edits);
+        self.set_block_selection(block_selection.collapse_to(column));
+    }
+
+    /// Type the same text in every line of the block selection.
+    pub fn insert_in_block_selection(&mut self, text: &str) {
+        let Some(block_selection) = self.block_selection else {
+            return;
+        };
+        let lines = block_selection.lines().count();
+        let column = block_selection.columns().start + text.chars().count();
+        self.replace_block_selection(vec![text.to_string(); lines], column);
+    }
+
+    /// Paste one line in every line of the block selection when there are as many of them,
+    /// or the whole text in every line otherwise.
+    pub fn paste_in_block_selection(&mut self, text: &str) {
+        let Some(block_selection) = self.block_selection else {
+            return;
+        };
+        let lines = text.lines().map(str::to_string).collect::<Vec<String>>();
+        if lines.len() != block_selection.lines().count() {
+            self.insert_in_block_selection(text);
+            return;
+        }
+
+        let column = block_selection.columns().start
+            + lines
+                .last()
+                .map(|line| line.chars().count())
+                .unwrap_or_default();
+        self.replace_block_selection(lines, column);
+    }
+
+    /// Delete the block selection, or the char before (or after) it in every line when it is empty.
+    pub fn delete_in_block_selection(&mut self, forward: bool) {
+        let Some(block_selection) = self.block_selection else {
+            return;
+        };
+        let columns = block_selection.columns();
+        if !columns.is_empty() {
+            let lines = block_selection.lines().count();
+            self.replace_block_selection(vec![String::new(); lines], columns.start);
+            return;
+        }
+
+        let edits = block_selection
+            .lines()
+            .filter_map(|line| {
+                let line_columns = block_selection.line_columns(&self.rope, line)?;
+                // Lines too short to reach the column are left alone
+                if line_columns.start < columns.start {
+                    return None;
+                }
+                let pos = self.rope.line_to_char(line) + line_columns.start;
+                let is_line_end = matches!(self.rope.get_char(pos), None | Some('\n' | '\r'));
+                if forward && !is_line_end {
+                    Some((pos..pos + 1, String::new()))
+                } else if !forward && line_columns.start > 0 {
+                    Some((pos - 1..pos, String::new()))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<(Range<usize>, String)>>();
+        if !edits.is_empty() {
+            self.apply_edits(&edits);
+        }
+
+        let column = if forward {
+            columns.start
+        } else {
+            columns.start.saturating_sub(1)
+        };
+        self.set_block_selection(block_selection.collapse_to(column));
+    }
+
+    /// Process a key pressed while there is a block selection, returns whether it was handled.
+    /// Any key that doesn't edit the block selection clears it.
+    pub fn process_block_selection_key(
+        &mut self,
+        key: &Key,
+        code: &Code,
+        modifiers: &Modifiers,
+    ) -> bool {
+        let Some(block_selection) = self.block_selection else {
+            return false;
+        };
+        let is_ctrl = modifiers.contains(Modifiers::CONTROL);
+
+        match key {
+            Key::Shift | Key::Control | Key::Alt | Key::Meta => return true,
+            _ if is_ctrl && *code == Code::KeyC => {
+                if let Some(text) = self.block_selected_text() {
+                    self.clipboard.set(text).ok();
+                }
+                return true;
+            }
+            _ if self.read_only => {}
+            _ if is_ctrl && *code == Code::KeyX => {
+                if let Some(text) = self.block_selected_text() {
+                    self.clipboard.set(text).ok();
+                }
+                if !block_selection.columns().is_empty() {
+                    self.delete_in_block_selection(false);
+                }
+                return true;
+            }
+            _ if is_ctrl && *code == Code::KeyV => {
+                if let Ok(text) = self.clipboard.get() {
+                    self.paste_in_block_selection(&text);
+                }
+                return true;
+            }
+            Key::Character(text) if !is_ctrl => {
+                self.insert_in_block_selection(text);
+                return true;
+            }
+            Key::Backspace => {
+                self.delete_in_block_selection(false);
+                return true;
+            }
+            Key::Delete => {
+                self.delete_in_block_selection(true);
+                return true;
+            }
+            _ => {}
+        }
+
+        self.block_selection = None;
+        false
+    }
+
+    /// Lines covered by the selection, or the cursor line if there is no selection.
+    pub fn selected_lines(&self) -> Range<usize> {
+        let (start, end) = self
+            .get_selection_range()
+            .unwrap_or((self.cursor_pos(), self.cursor_pos()));
+        let first_line = self.rope.char_to_line(start);
+        let mut last_line = self.rope.char_to_line(end);
+
+        // A selection ending at the start of a line doesn't cover it
+        if end > start && last_line > first_line && self.rope.line_to_char(last_line) == end {
+            last_line -= 1;
+        }
+
+        first_line..last_line + 1
+    }
+
+    /// Leading whitespace of the given line.
+    fn line_indentation(&self, line_idx: usize) -> String {
+        self.rope
+            .line(line_idx)
+            .chars()
+            .take_while(|ch| *ch == ' ' || *ch == '\t')
+            .collect()
+    }
+
+    /// Delete the selected lines, or the cursor line if there is no selection.
+    pub fn delete_lines(&mut self) {
+        let lines = self.selected_lines();
+        let col = self.cursor_col();
+        let len_lines = self.rope.len_lines();
+
+        let is_last_line = lines.end >= len_lines;
+
+        let mut start = self.rope.line_to_char(lines.start);
+        let end = if is_last_line {
+            self.rope.len_chars()
+        } else {
+            self.rope.line_to_char(lines.end)
+        };
+
+        // The last line has no line break of its own, so remove the previous one
+        if is_last_line && lines.start > 0 {
+            start -= 1;
+        }
+
+        if start == end {
+            return;
+        }
+
+        self.remove(start..end);
+
+        let line = lines.start.min(self.rope.len_lines() - 1);
+        let line_start = self.rope.line_to_char(line);
+        let line_len = self
+            .rope
+            .line(line)
+            .chars()
+            .filter(|ch| *ch != '\n')
+            .count();
+        self.selected = None;
+        self.cursor = TextCursor::new(line_start + col.min(line_len));
+        self.run_parser();
+    }
+
+    /// Insert an empty line below the cursor line, keeping its indentation.
+    pub fn insert_line_below(&mut self) {
+        let line = self.cursor_row();
+        let mut indentation = self.line_indentation(line);
+
+        let line_text = self.rope.line(line).to_string();
+        if self.language.triggers_indent(&line_text) {
+            indentation.push_str(&self.indentation.unit());
+        }
+
+        let line_end =
+            self.rope.line_to_char(line) + line_text.trim_end_matches('\n').chars().count();
+        let text = format!("\n{indentation}");
+        self.insert(&text, line_end);
+
+        self.selected = None;
+        self.cursor = TextCursor::new(line_end + text.chars().count());
+        self.run_parser();
+    }
+
+    /// Insert an empty line above the cursor line, keeping its indentation.
+    pub fn insert_line_above(&mut self) {
+        let line = self.cursor_row();
+        let indentation = self.line_indentation(line);
+
+        let line_start = self.rope.line_to_char(line);
+        self.insert(&format!("{indentation}\n"), line_start);
+
+        self.selected = None;
+        self.cursor = TextCursor::new(line_start + indentation.chars().count());
+        self.run_parser();
+    }
+
+    /// Indent the selected lines, or insert an indentation level at the cursor if
+    /// there is no selection.
+    pub fn indent(&mut self) {
+        if self.get_selection_range().is_none() {
+            let line_start = self.rope.line_to_char(self.cursor_row());
+            let before_cursor = self.rope.slice(line_start..self.cursor_pos()).to_string();
+
+            // Spaces only reach the next indentation stop
+            let text = if self.indentation.with_tabs {
+                "\t".to_string()
+            } else {
+                let width = self.indentation.width as usize;
+                " ".repeat(width - self.indentation.columns(&before_cursor) % width)
+            };
+
+            let pos = self.cursor_pos();
+            self.insert(&text, pos);
+            self.cursor = TextCursor::new(pos + text.chars().count());
+            self.run_parser();
+            return;
+        }
+
+        let unit = self.indentation.unit();
+        let edits = self
+            .selected_lines()
+            .filter(|line| !self.rope.line(*line).chars().all(char::is_whitespace))
+            .map(|line| {
+                let line_start = self.rope.line_to_char(line);
+                (line_start..line_start, unit.clone())
+            })
+            .collect::<Vec<_>>();
+
+        self.apply_edits(&edits);
+    }
+
+    /// Remove an indentation level from the selected lines, or the cursor line.
+    pub fn outdent(&mut self) {
+        let width = self.indentation.width as usize;
+        let edits = self
+            .selected_lines()
+            .filter_map(|line| {
+                let indentation = self.line_indentation(line);
+                let len = if indentation.starts_with('\t') {
+                    1
+                } else {
+                    indentation
+                        .chars()
+                        .take_while(|ch| *ch == ' ')
+                        .take(width)
+                        .count()
+                };
+                let line_start = self.rope.line_to_char(line);
+                (len > 0).then(|| (line_start..line_start + len, String::new()))
+            })
+            .collect::<Vec<_>>();
+
+        self.apply_edits(&edits);
+    }
+
+    /// Replace the given ranges with new texts, keeping the cursor and the selection
+    /// in place. The ranges must be sorted and must not overlap.
+    /// Read-only editors are left as they are.
+    pub fn apply_edits(&mut self, edits: &[(Range<usize>, String)]) {
+        if self.read_only {
+            return;
+        }
+
+        let map_pos = |pos: usize| {
+            let mut new_pos = pos as isize;
+            for (range, text) in edits {
+                let inserted = text.chars().count() as isize;
+                if pos >= range.end {
+                    new_pos += inserted - range.len() as isize;
+                } else if pos > range.start {
+                    new_pos -= (pos - range.start) as isize;
+                }
+            }
+            new_pos.max(0) as usize
+        };
+
+        let cursor = map_pos(self.cursor_pos());
+        let selected = self.selected.map(|(from, to)| (map_pos(from), map_pos(to)));
+
+        self.with_transaction(|editor| {
+            for (range, text) in edits.iter().rev() {
+                if !range.is_empty() {
+                    editor.remove(range.clone());
+                }
+                if !text.is_empty() {
+                    editor.insert(text, range.start);
+                }
+            }
+        });
+
+        self.cursor = TextCursor::new(cursor);
+        self.selected = selected;
+        self.run_parser();
+    }
+
+    /// Comment or uncomment the selected lines (or the cursor line). Mixed lines are all commented.
+    pub fn toggle_line_comment(&mut self) {
+        let language = self.language.clone();
+
+        // Languages without line comments get every line wrapped in a block comment
+        let Some((open, close)) = language.line_comment_tokens() else {
+            return;
+        };
+
+        let lines = self.selected_lines();
+        let (content, _) = self.lines_content(lines.clone());
+
+        // Blank lines are left alone
+        let code_lines = content
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (lines.start + i, line.as_str()))
+            .collect::<Vec<(usize, &str)>>();
+
+        if code_lines.is_empty() {
+            return;
+        }
+
+        let is_commented = |line: &str| {
+            let line = line.trim();
+            let close = close.unwrap_or_default();
+            line.len() >= open.len() + close.len()
+                && line.starts_with(open)
+                && line.ends_with(close)
+        };
+        let all_commented = code_lines.iter().all(|(_, line)| is_commented(line));
+
+        // Comments are aligned to the least indented line
+        let column = code_lines
+            .iter()
+            .map(|(_, line)| line.chars().take_while(|ch| ch.is_whitespace()).count())
+            .min()
+            .unwrap_or_default();
+
+        let mut edits = Vec::new();
+        for (line_idx, line) in code_lines {
+            let line_start = self.rope.line_to_char(line_idx);
+            let chars = line.chars().collect::<Vec<char>>();
+            let trailing = chars
+                .iter()
+                .rev()
+                .take_while(|ch| ch.is_whitespace())
+                .count();
+            let content_end = chars.len() - trailing;
+
+            if all_commented {
+                let indentation = chars.iter().take_while(|ch| ch.is_whitespace()).count();
+                let mut open_end = indentation + open.chars().count();
+                if chars.get(open_end) == Some(&' ') {
+                    open_end += 1;
+                }
+                edits.push((
+                    line_start + indentation..line_start + open_end,
+                    String::new(),
+                ));
+
+                if let Some(close) = close {
+                    let mut close_start = content_end - close.chars().count();
+                    if close_start > open_end && chars[close_start - 1] == ' ' {
+                        close_start -= 1;
+                    }
+                    edits.push((
+                        line_start + close_start..line_start + content_end,
+                        String::new(),
+                    ));
+                }
+            } else {
+                let pos = line_start + column;
+                edits.push((pos..pos, format!("{open} ")));
+
+                if let Some(close) = close {
+                    let pos = line_start + content_end;
+                    edits.push((pos..pos, format!(" {close}")));
+                }
+            }
+        }
+
+        self.apply_edits(&edits);
+    }
+
+    /// Insert a TODO comment with the given text at the cursor, or at the end of
+    /// the line if there is code after the cursor, leaving the cursor after it.
+    pub fn insert_todo(&mut self, text: &str) {
+        let language = self.language.clone();
+        let (open, close) = language.line_comment_tokens().unwrap_or(("", None));
+
+        let line = self.cursor_row();
+        let line_start = self.rope.line_to_char(line);
+        let line_text = self.rope.line(line).to_string();
+        let line_text = line_text.trim_end_matches(['\n', '\r']);
+        let col = self.cursor_col().min(line_text.chars().count());
+        let is_code_after = line_text.chars().skip(col).any(|ch| !ch.is_whitespace());
+        let col = if is_code_after {
+            line_text.chars().count()
+        } else {
+            col
+        };
+
+        // Keep some space from the code before it
+        let is_code_before = col > 0
+            && line_text
+                .chars()
+                .nth(col - 1)
+                .is_some_and(|ch| !ch.is_whitespace());
+        let space = if is_code_before { " " } else { "" };
+        let comment = if open.is_empty() {
+            format!("{space}{text}")
+        } else {
+            format!("{space}{open} {text}")
+        };
+        let comment_len = comment.chars().count();
+        let close = close.map(|close| format!(" {close}")).unwrap_or_default();
+
+        let pos = line_start + col;
+        self.selected = None;
+        self.cursor = TextCursor::new(pos);
+        self.apply_edits(&[(pos..pos, format!("{comment}{close}"))]);
+        self.cursor = TextCursor::new(pos + comment_len);
+    }
+
+    /// Type a character closing the bracket or string it opens, or typing over it
+    /// if it's already the next one. Returns whether it was typed.
+    pub fn auto_close_pair(&mut self, ch: char) -> bool {
+        if self.get_selection_range().is_some() {
+            return false;
+        }
+
+        let pos = self.cursor_pos();
+        let char_after = self.rope.get_char(pos);
+        let char_before = pos.checked_sub(1).and_then(|pos| self.rope.get_char(pos));
+        let is_string_delimiter = self.language.string_delimiters.contains(&ch);
+
+        // Type over the closing character
+        if char_after == Some(ch) && (is_string_delimiter || self.language.is_closing_bracket(ch)) {
+            self.selected = None;
+            self.cursor = TextCursor::new(pos + 1);
+            return true;
+        }
+
+        // Only close pairs before whitespace or closing brackets, not in the middle of a word
+        let is_free_after = char_after.map_or(true, |ch| {
+            ch.is_whitespace() || self.language.is_closing_bracket(ch)
+        });
+        let close = if let Some(&(_, close)) =
+            self.language.brackets.iter().find(|(open, _)| *open == ch)
+        {
+            close
+        } else if is_string_delimiter && !char_before.is_some_and(char::is_alphanumeric) {
+            ch
+        } else {
+            return false;
+        };
+        if !is_free_after {
+            return false;
+        }
+
+        self.apply_edits(&[(pos..pos, format!("{ch}{close}"))]);
+        self.selected = None;
+        self.cursor = TextCursor::new(pos + 1);
+        true
+    }
+
+    /// Wrap the selection (or the cursor line) in a block comment, or unwrap it if it already is one.
+    pub fn toggle_block_comment(&mut self) {
+        let Some((open, close)) = self.language.block_comment.clone() else {
+            return;
+        };
+        let (open, close) = (open.as_str(), close.as_str());
+
+        let (start, end) = self.get_selection_range().unwrap_or_else(|| {
+            let line = self.cursor_row();
+            let line_start = self.rope.line_to_char(line);
+            let line_text = self.rope.line(line).to_string();
+            let indentation = self.line_indentation(line).chars().count();
+            let len = line_text.trim_end().chars().count();
+            (line_start + indentation, line_start + len.max(indentation))
+        });
+
+        let text = self.rope.slice(start..end).to_string();
+        let leading = text.chars().take_while(|ch| ch.is_whitespace()).count();
+        let trailing = text
+            .chars()
+            .rev()
+            .take_while(|ch| ch.is_whitespace())
+            .count();
+        let trimmed = text.trim();
+        let (open_len, close_len) = (open.chars().count(), close.chars().count());
+
+        let edits = if trimmed.len() >= open.len() + close.len()
+            && trimmed.starts_with(open)
+            && trimmed.ends_with(close)
+        {
+            let chars = trimmed.chars().collect::<Vec<char>>();
+            let open_start = start + leading;
+            let mut open_end = open_start + open_len;
+            let close_end = end - trailing;
+            let mut close_start = close_end - close_len;
+            if chars.get(open_len) == Some(&' ') && open_end < close_start {
+                open_end += 1;
+            }
+            if close_start > open_end && chars[chars.len() - close_len - 1] == ' ' {
+                close_start -= 1;
+            }
+            vec![
+                (open_start..open_end, String::new()),
+                (close_start..close_end, String::new()),
+            ]
+        } else {
+            vec![
+                (start..start, format!("{open} ")),
+                (end..end, format!(" {close}")),
+            ]
+        };
+
+        self.apply_edits(&edits);
+    }
+
+    /// Replace the selection with the given text, re-indenting its lines
+    /// to the indentation of the insertion point.
+    pub fn paste_reindented(&mut self, text: &str) {
+        let (start, end) = self
+            .get_selection_range()
+            .unwrap_or((self.cursor_pos(), self.cursor_pos()));
+
+        let line = self.rope.char_to_line(start);
+        let col = start - self.rope.line_to_char(line);
+        let indentation = self
+            .line_indentation(line)
+            .chars()
+            .take(col)
+            .collect::<String>();
+
+        let text = reindent(text, &indentation);
+
+        self.with_transaction(|editor| {
+            if end > start {
+                editor.remove(start..end);
+            }
+            editor.insert(&text, start);
+        });
+
+        self.selected = None;
+        self.cursor = TextCursor::new(start + text.chars().count());
+        self.run_parser();
+    }
+
+    /// Move the cursor to the given position, extending the selection if `select` is set.
+    pub fn move_cursor_to(&mut self, pos: usize, select: bool) {
+        let pos = pos.min(self.rope.len_chars());
+        if select {
+            let cursor = self.cursor_pos();
+            match self.selected.as_mut() {
+                Some(selected) => selected.1 = pos,
+                None => self.selected = Some((cursor, pos)),
+            }
+        } else {
+            self.selected = None;
+        }
+        self.cursor = TextCursor::new(pos);
+    }
+
+    /// Position the cursor ends up in after a motion.
+    pub fn motion_position(&self, motion: Motion) -> usize {
+        let cursor = self.cursor_pos();
+        match motion {
+            Motion::CharLeft => cursor.saturating_sub(1),
+            Motion::CharRight => (cursor + 1).min(self.rope.len_chars()),
+            Motion::LineUp => self.line_offset(-1),
+            Motion::LineDown => self.line_offset(1),
+            Motion::WordLeft => self.word_boundary(false),
+            Motion::WordRight => self.word_boundary(true),
+            Motion::LineHome => self.line_home(),
+            Motion::LineEnd => self.line_end(),
+            Motion::TextStart => 0,
+            Motion::TextEnd => self.rope.len_chars(),
+            Motion::MatchingBracket => {
+                matching_bracket(&self.rope, cursor, &self.language.brackets).unwrap_or(cursor)
+            }
+        }
+    }
+
+    /// Move the cursor, extending the selection if `select` is set.
+    pub fn apply_motion(&mut self, motion: Motion, select: bool) {
+        self.move_cursor_to(self.motion_position(motion), select);
+    }
+
+    /// Select the identifier touching the cursor.
+    pub fn select_word(&mut self) {
+        let range = self.word_range_at_cursor();
+        if !range.is_empty() {
+            self.select_range(range);
+        }
+    }
+
+    /// Select the whole lines covered by the selection (or the cursor line),
+    /// the next line is added if they were already selected.
+    pub fn select_lines(&mut self) {
+        let lines = self.selected_lines();
+        let line_start = |line: usize| self.rope.line_to_char(line.min(self.rope.len_lines()));
+
+        let mut range = line_start(lines.start)..line_start(lines.end);
+        if self.get_selection_range() == Some((range.start, range.end)) {
+            range.end = line_start(lines.end + 1);
+        }
+        self.select_range(range);
+    }
+
+    /// Select the whole lines from the `anchor` line to the given one, where the cursor is placed.
+    pub fn select_line_range(&mut self, anchor: usize, line: usize) {
+        let line_start = |line: usize| self.rope.line_to_char(line.min(self.rope.len_lines()));
+        if line >= anchor {
+            self.select_range(line_start(anchor)..line_start(line + 1));
+        } else {
+            self.selected = Some((line_start(anchor + 1), line_start(line)));
+            self.cursor = TextCursor::new(line_start(line));
+        }
+    }
+
+    /// Select the whole text.
+    pub fn select_all(&mut self) {
+        self.select_range(0..self.rope.len_chars());
+    }
+
+    /// Position of the next (or previous) word boundary from the cursor.
+    pub fn word_boundary(&self, forward: bool) -> usize {
+        #[derive(Clone, Copy, PartialEq)]
+        enum CharKind {
+            Space,
+            Word,
+            Punctuation,
+        }
+
+        let kind = |ch: char| {
+            if ch.is_whitespace() {
+                CharKind::Space
+            } else if ch.is_alphanumeric() || ch == '_' {
+                CharKind::Word
+            } else {
+                CharKind::Punctuation
+            }
+        };
+
+        let mut pos = self.cursor_pos();
+        let len_chars = self.rope.len_chars();
+        let char_at = |pos: usize| {
+            if forward {
+                (pos < len_chars).then(|| self.rope.char(pos))
+            } else {
+                (pos > 0).then(|| self.rope.char(pos - 1))
+            }
+        };
+        let step = |pos: usize| if forward { pos + 1 } else { pos - 1 };
+
+        // Skip the spaces and then the whole word or punctuation run
+        while char_at(pos).map(kind) == Some(CharKind::Space) {
+            pos = step(pos);
+        }
+        if let Some(first) = char_at(pos).map(kind) {
+            while char_at(pos).map(kind) == Some(first) {
+                pos = step(pos);
+            }
+        }
+
+        pos
+    }
+
+    /// Chars range of the identifier touching the cursor.
+    fn word_range_at_cursor(&self) -> Range<usize> {
+        let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+        let pos = self.cursor_pos();
+
+        let mut start = pos;
+        while start > 0 && is_word_char(self.rope.char(start - 1)) {
+            start -= 1;
+        }
+        let mut end = pos;
+        while end < self.rope.len_chars() && is_word_char(self.rope.char(end)) {
+            end += 1;
+        }
+
+        start..end
+    }
+
+    /// Identifier touching the cursor.
+    pub fn word_at_cursor(&self) -> Option<String> {
+        let range = self.word_range_at_cursor();
+        (!range.is_empty()).then(|| self.rope.slice(range).to_string())
+    }
+
+    /// Identifier before the cursor.
+    pub fn word_before_cursor(&self) -> String {
+        let range = self.word_range_at_cursor().start..self.cursor_pos();
+        self.rope.slice(range).to_string()
+    }
+
+    /// Complete the word before the cursor with the words of this text and the given ones.
+    /// Completing again right away cycles through the other completions.
+    pub fn complete_word(&mut self, mut words: BTreeSet<String>) {
+        let range = self.word_range_at_cursor().start..self.cursor_pos();
+        let word = self.rope.slice(range.clone()).to_string();
+
+        let (completions, index) = match self.word_completion.take() {
+            Some((completions, index)) if completions.get(index) == Some(&word) => {
+                let index = (index + 1) % completions.len();
+                (completions, index)
+            }
+            _ => {
+                if word.is_empty() {
+                    return;
+                }
+                words.extend(
+                    self.rope
+                        .to_string()
+                        .split(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                        .filter(|text_word| text_word.starts_with(&word))
+                        .map(str::to_string),
+                );
+                words.retain(|completion| completion.starts_with(&word) && *completion != word);
+                (words.into_iter().collect::<Vec<String>>(), 0)
+            }
+        };
+
+        let Some(completion) = completions.get(index).cloned() else {
+            return;
+        };
+
+        self.apply_edits(&[(range.clone(), completion.clone())]);
+        self.selected = None;
+        self.cursor = TextCursor::new(range.start + completion.chars().count());
+        self.word_completion = Some((completions, index));
+    }
+
+    /// Position of the first non-whitespace char of the cursor line,
+    /// or of the line start if the cursor is already there.
+    pub fn line_home(&self) -> usize {
+        let line = self.cursor_row();
+        let line_start = self.rope.line_to_char(line);
+        let indentation = self.line_indentation(line).chars().count();
+
+        if self.cursor_col() == indentation {
+            line_start
+        } else {
+            line_start + indentation
+        }
+    }
+
+    /// Position of the end of the cursor line.
+    pub fn line_end(&self) -> usize {
+        let line = self.cursor_row();
+        let line_text = self.rope.line(line).to_string();
+        self.rope.line_to_char(line) + line_text.trim_end_matches(['\n', '\r']).chars().count()
+    }
+
+    /// Position of the same column in the line `lines` lines away from the cursor line.
+    pub fn line_offset(&self, lines: isize) -> usize {
+        let last_line = self.rope.len_lines().saturating_sub(1);
+        let line = self
+            .cursor_row()
+            .saturating_add_signed(lines)
+            .min(last_line);
+        let line_text = self.rope.line(line).to_string();
+        let line_len = line_text.trim_end_matches(['\n', '\r']).chars().count();
+        self.rope.line_to_char(line) + self.cursor_col().min(line_len)
+    }
+
+    /// Header lines of the functions, impls, structs... that enclose the given line, from the outermost one.
+    /// Blocks are matched by the braces of the syntax blocks, keeping only the innermost `limit` ones.
+    pub fn enclosing_scopes(&self, line: usize, limit: usize) -> Vec<usize> {
+        let syntax_blocks = &self.metrics.syntax_blocks;
+        let mut scopes = Vec::new();
+        // Blocks closed before the line, which can't enclose it
+        let mut closed_blocks = 0;
+        // While reading the header of an enclosing block backwards, the line of its keyword
+        // and how many brackets of the header are still open
+        let mut header: Option<(Option<usize>, usize)> = None;
+
+        for line_idx in (0..line.min(syntax_blocks.len())).rev() {
+            for (syntax_type, text_node) in syntax_blocks.get_line(line_idx).iter().rev() {
+                if matches!(syntax_type, SyntaxType::String | SyntaxType::Comment) {
+                    continue;
+                }
+                let TextNode::Range(range) = text_node else {
+                    continue;
+                };
+                let token: Cow<str> = self.rope.slice(range.clone()).into();
+                let token = token.trim();
+
+                match (token, &mut header) {
+                    (")" | "]", Some((_, brackets))) => *brackets += 1,
+                    ("(" | "[", Some((_, brackets))) => *brackets = brackets.saturating_sub(1),
+                    (";", Some((_, brackets))) if *brackets > 0 => {}
+                    ("{" | "}" | ";", _) => {
+                        // The header ends where the previous statement or block does
+                        if let Some((Some(keyword_line), _)) = header.take() {
+                            scopes.push(keyword_line);
+                            if scopes.len() == limit {
+                                scopes.reverse();
+                                return scopes;
+                            }
+                        }
+                        match token {
+                            "}" => closed_blocks += 1,
+                            "{" if closed_blocks > 0 => closed_blocks -= 1,
+                            "{" => header = Some((None, 0)),
+                            _ => {}
+                        }
+                    }
+                    (word, Some((keyword_line, _))) if SCOPE_KEYWORDS.contains(&word) => {
+                        *keyword_line = Some(line_idx);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // The outermost header might start the text
+        if let Some((Some(keyword_line), _)) = header {
+            scopes.push(keyword_line);
+        }
+
+        scopes.reverse();
+        scopes
+    }
+
+    /// Select the given range and move the cursor to the end of it.
+    pub fn select_range(&mut self, range: Range<usize>) {
+        self.selected = Some((range.start, range.end));
+        self.cursor = TextCursor::new(range.end);
+    }
+
+    /// Char index of a line and column, both clamped to the text.
+    pub fn position_to_char(&self, line: usize, column: usize) -> usize {
+        let line = line.min(self.rope.len_lines() - 1);
+        let line_start = self.rope.line_to_char(line);
+        let line_len = self
+            .rope
+            .line(line)
+            .chars()
+            .filter(|ch| *ch != '\n')
+            .count();
+        line_start + column.min(line_len)
+    }
+
+    /// Replace the given ranges, which must be sorted and must not overlap.
+    /// Returns how many were replaced, none if the editor is read-only.
+    pub fn replace_ranges(
+        &mut self,
+        ranges: &[Range<usize>],
+        replacement: impl Fn(&str) -> String,
+    ) -> usize {
+        if self.read_only {
+            return 0;
+        }
+
+        self.with_transaction(|editor| {
+            for range in ranges.iter().rev() {
+                let matched = editor.rope.slice(range.clone()).to_string();
+                editor.remove(range.clone());
+                editor.insert(&replacement(&matched), range.start);
+            }
+        });
+
+        if !ranges.is_empty() {
+            self.selected = None;
+            self.cursor = TextCursor::new(self.cursor_pos().min(self.len_chars()));
+            self.run_parser();
+        }
+        ranges.len()
+    }
+}
+
+/// Digest of a text, to tell whether two versions of it are the same.
+pub fn text_digest(rope: &Rope) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    rope.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Move the bookmarked lines along with their content, before a range of the rope is replaced with a text.
+fn shift_bookmarks(bookmarks: &mut BTreeSet<usize>, rope: &Rope, range: Range<usize>, text: &str) {
+    let start_line = rope.char_to_line(range.start);
+    let end_line = rope.char_to_line(range.end);
+    let inserted_lines = Rope::from(text).len_lines() - 1;
+    if start_line == end_line && inserted_lines == 0 {
+        return;
+    }
+
+    let start_line_char = rope.line_to_char(start_line);
+    // Inserting at the start of a line pushes the whole line down
+    let pushes_start_line = range.is_empty() && range.start == start_line_char;
+    // Removing whole lines takes their bookmarks too
+    let removes_whole_lines = !range.is_empty()
+        && range.start == start_line_char
+        && range.end == rope.line_to_char(end_line);
+
+    *bookmarks = bookmarks
+        .iter()
+        .filter_map(|&line| {
+            if line < start_line
+                || (line == start_line && !pushes_start_line && !removes_whole_lines)
+            {
+                Some(line)
+            } else if line < end_line && removes_whole_lines {
+                None
+            } else if line <= end_line && line > start_line {
+                // What is left of the last removed line is joined to the first one
+                Some(start_line)
+            } else {
+                Some(line - (end_line - start_line) + inserted_lines)
+            }
+        })
+        .collect();
+}
+
+/// Move the lines of a snippet to the given indentation, keeping their relative indentation.
+fn reindent(text: &str, indentation: &str) -> String {
+    let lines = text.split('\n').collect::<Vec<&str>>();
+    if lines.len() < 2 {
+        return text.to_owned();
+    }
+
+    let indentation_of = |line: &str| {
+        line.chars()
+            .take_while(|ch| *ch == ' ' || *ch == '\t')
+            .count()
+    };
+    let is_blank = |line: &&str| line.trim().is_empty();
+
+    // The first line might have been copied from the middle of a line,
+    // so its indentation only counts if it has any
+    let base = lines
+        .iter()
+        .enumerate()
+        .filter(|(i, line)| !is_blank(line) && (*i > 0 || indentation_of(line) > 0))
+        .map(|(_, line)| indentation_of(line))
+        .min()
+        .unwrap_or_default();
+
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if is_blank(line) {
+                return line
+                    .trim_end_matches(|ch| ch == ' ' || ch == '\t')
+                    .to_owned();
+            }
+            let skipped = indentation_of(line).min(base);
+            let line = line.chars().skip(skipped).collect::<String>();
+            // The first line is placed at the cursor, which is already indented
+            if i == 0 {
+                line
+            } else {
+                format!("{indentation}{line}")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 impl Display for EditorData {
@@ -150,6 +1629,12 @@ impl TextEditor for EditorData {
     }
 
     fn insert_char(&mut self, char: char, char_idx: usize) {
+        shift_bookmarks(
+            &mut self.bookmarks,
+            &self.rope,
+            char_idx..char_idx,
+            char.encode_utf8(&mut [0; 4]),
+        );
         self.history.push_change(HistoryChange::InsertChar {
             idx: char_idx,
             char,
@@ -158,6 +1643,7 @@ impl TextEditor for EditorData {
     }
 
     fn insert(&mut self, text: &str, idx: usize) {
+        shift_bookmarks(&mut self.bookmarks, &self.rope, idx..idx, text);
         self.history.push_change(HistoryChange::InsertText {
             idx,
             text: text.to_owned(),
@@ -166,6 +1652,7 @@ impl TextEditor for EditorData {
     }
 
     fn remove(&mut self, range: Range<usize>) {
+        shift_bookmarks(&mut self.bookmarks, &self.rope, range.clone(), "");
         let text = self.rope.slice(range.clone()).to_string();
         self.history.push_change(HistoryChange::Remove {
             idx: range.start,
@@ -296,6 +1783,8 @@ impl TextEditor for EditorData {
     fn set(&mut self, text: &str) {
         self.rope.remove(0..);
         self.rope.insert(0, text);
+        let len_lines = self.rope.len_lines();
+        self.bookmarks.retain(|line| *line < len_lines);
     }
 
     fn clear_selection(&mut self) {
@@ -346,7 +1835,9 @@ impl TextEditor for EditorData {
 
     fn redo(&mut self) -> Option<usize> {
         if self.history.can_redo() {
-            self.history.redo(&mut self.rope)
+            self.history.redo_with(&mut self.rope, |rope, range, text| {
+                shift_bookmarks(&mut self.bookmarks, rope, range, text)
+            })
         } else {
             None
         }
@@ -354,13 +1845,15 @@ impl TextEditor for EditorData {
 
     fn undo(&mut self) -> Option<usize> {
         if self.history.can_undo() {
-            self.history.undo(&mut self.rope)
+            self.history.undo_with(&mut self.rope, |rope, range, text| {
+                shift_bookmarks(&mut self.bookmarks, rope, range, text)
+            })
         } else {
             None
         }
     }
 
     fn get_identation(&self) -> u8 {
-        4
+        self.indentation.width
     }
 }
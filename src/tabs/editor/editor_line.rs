@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use dioxus::dioxus_core::AttributeValue;
 use dioxus_radio::hooks::use_radio;
 use dioxus_sdk::utils::timing::UseDebounce;
 use freya::prelude::*;
@@ -6,13 +9,16 @@ use skia_safe::textlayout::Paragraph;
 
 use crate::parser::TextNode;
 use crate::tabs::editor::hover_box::HoverBox;
-use crate::tabs::editor::AppStateEditorUtils;
+use crate::tabs::editor::{is_request_line, run_request, AppStateEditorUtils};
 use crate::{hooks::UseEdit, utils::create_paragraph};
 use crate::{
-    lsp::{HoverToText, LspAction, UseLsp},
-    state::Channel,
+    lsp::{DocumentHighlights, HoverToText, LanguageId, LspAction, UseLsp},
+    state::{Channel, CursorStyle},
 };
 
+/// How long the blinking cursor stays shown, and then hidden.
+const CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
 #[derive(Props, Clone, PartialEq)]
 pub struct BuilderArgs {
     pub(crate) panel_index: usize,
@@ -29,6 +35,7 @@ pub struct EditorLineProps {
     editable: UseEdit,
     lsp: UseLsp,
     hover_location: Signal<Option<(u32, Hover)>>,
+    document_highlights: Signal<Option<DocumentHighlights>>,
     cursor_coords: Signal<CursorPoint>,
     debouncer: UseDebounce<(CursorPoint, u32, Paragraph)>,
 }
@@ -48,6 +55,7 @@ pub fn EditorLine(
         mut editable,
         lsp,
         hover_location,
+        document_highlights,
         mut cursor_coords,
         mut debouncer,
     }: EditorLineProps,
@@ -58,6 +66,14 @@ pub fn EditorLine(
         editable.process_event(&EditableEvent::MouseDown(e.data, line_index));
     };
 
+    let ongutterdown = move |_: MouseEvent| {
+        editable.process_gutter_mousedown(line_index);
+    };
+
+    let ongutterover = move |_: MouseEvent| {
+        editable.process_gutter_mouseover(line_index);
+    };
+
     let onmouseleave = move |_| {
         if lsp.is_supported() {
             lsp.send(LspAction::Clear);
@@ -95,10 +111,27 @@ pub fn EditorLine(
     let editor = &editor_tab.editor;
     let longest_width = editor.metrics.longest_width;
     let line = editor.metrics.syntax_blocks.get_line(line_index);
-    let highlights = editable.highlights_attr(line_index);
     let gutter_width = font_size * 3.0;
+    let editor_settings = &app_state.settings.editor;
+    let rainbow_brackets = editor_settings.rainbow_brackets;
+    let font_family = editor_settings.rendered_font_family();
 
-    let is_line_selected = editor.cursor_row() == line_index;
+    // One guide per indentation level, tabs are a level each
+    let (indent_guides, indent_guide_width) = if editor_settings.indent_guides {
+        let level_chars = if editor.indentation.with_tabs {
+            1
+        } else {
+            editor.indentation.width.max(1) as usize
+        };
+        (
+            editor.metrics.syntax_blocks.get_indent(line_index) / level_chars,
+            level_chars as f32 * editor.metrics.char_width,
+        )
+    } else {
+        (0, 0.0)
+    };
+
+    let is_line_selected = editable.is_viewport_focused() && editor.cursor_row() == line_index;
 
     // Only show the cursor in the active line
     let character_index = if is_line_selected {
@@ -107,6 +140,25 @@ pub fn EditorLine(
         "none".to_string()
     };
 
+    // Cursors other than a still bar are drawn over the text instead of by the paragraph
+    let (cursor_style, cursor_blink) = (editor_settings.cursor_style, editor_settings.cursor_blink);
+    let has_custom_cursor = cursor_style != CursorStyle::Bar || cursor_blink;
+    let cursor_color = if has_custom_cursor {
+        "transparent"
+    } else {
+        "white"
+    };
+    let custom_cursor_offset = (is_line_selected && has_custom_cursor).then(|| {
+        let before_cursor = rope
+            .line(line_index)
+            .chars()
+            .take(editor.cursor_col())
+            .collect::<String>();
+        gutter_width
+            + create_paragraph(&before_cursor, font_size, radio_app_state).max_intrinsic_width()
+    });
+    let char_width = editor.metrics.char_width;
+
     // Only highlight the gutter on the active line
     let gutter_color = if is_line_selected {
         "rgb(235, 235, 235)"
@@ -114,8 +166,132 @@ pub fn EditorLine(
         "rgb(135, 135, 135)"
     };
 
+    // Bookmarked lines are marked in the gutter
+    let bookmark_color = if editor.is_bookmarked(line_index) {
+        "rgb(255, 190, 0)"
+    } else {
+        "transparent"
+    };
+
+    // Every line of an empty block selection gets a cursor
+    let block_cursor_offset = editor
+        .block_selection
+        .filter(|block_selection| {
+            block_selection.columns().is_empty() && editable.is_viewport_focused()
+        })
+        .and_then(|block_selection| block_selection.line_columns(&rope, line_index))
+        .map(|columns| gutter_width + columns.start as f32 * editor.metrics.char_width);
+
+    // Hints of the quick jump targets in this line
+    let quick_jump_hints = editor.quick_jump.as_ref().map(|quick_jump| {
+        let line_start = rope.line_to_char(line_index);
+        let line_len = rope.line(line_index).len_chars();
+        quick_jump.line_hints(line_start..line_start + line_len)
+    });
+
+    let line_text = match quick_jump_hints {
+        // Dim the line and replace the start of every target with its hint
+        Some(hints) if !hints.is_empty() => {
+            let line_str = rope.line(line_index).to_string();
+            let chars = line_str.chars().collect::<Vec<char>>();
+            let mut segments = Vec::new();
+            let mut last = 0;
+
+            for (offset, hint) in hints {
+                let hint_len = hint.chars().count().min(chars.len() - offset);
+                if offset > last {
+                    segments.push((false, chars[last..offset].iter().collect::<String>()));
+                }
+                segments.push((true, hint));
+                last = offset + hint_len;
+            }
+
+            if last < chars.len() {
+                segments.push((false, chars[last..].iter().collect::<String>()));
+            }
+
+            rsx!({
+                segments
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (is_hint, text))| {
+                        let (color, font_weight) = if is_hint {
+                            ("rgb(255, 190, 0)", "bold")
+                        } else {
+                            ("rgb(110, 110, 110)", "normal")
+                        };
+
+                        rsx!(
+                            text {
+                                key: "{i}",
+                                color: "{color}",
+                                font_weight: "{font_weight}",
+                                "{text}"
+                            }
+                        )
+                    })
+            })
+        }
+        _ => rsx!({
+            line.iter().enumerate().map(|(i, (syntax_type, text))| {
+                let text = match text {
+                    TextNode::Range(word_pos) => rope.slice(word_pos.clone()).to_string(),
+                    TextNode::LineOfChars { len, char } => format!("{char}").repeat(*len),
+                };
+
+                let color = if rainbow_brackets {
+                    syntax_type.rainbow_color()
+                } else {
+                    syntax_type.color()
+                };
+
+                rsx!(
+                    text {
+                        key: "{i}",
+                        color: "{color}",
+                        "{text}"
+                    }
+                )
+            })
+        }),
+    };
+
+    // Requests of `.http` files are sent from a lens at the end of their line
+    let request_lens_offset = if editor.editor_type.language_id() == LanguageId::Http {
+        let line_str = rope.line(line_index).to_string();
+        is_request_line(&line_str).then(|| {
+            let paragraph = create_paragraph(line_str.trim_end(), font_size, radio_app_state);
+            gutter_width + paragraph.max_intrinsic_width() + font_size
+        })
+    } else {
+        None
+    };
+
+    let send_request = move |_| run_request(radio_app_state, panel_index, tab_index, line_index);
+
+    // Occurrences of the symbol under the cursor are highlighted unless there is text selected
+    let has_any_highlight = editable.has_any_highlight();
+    let symbol_highlights = document_highlights
+        .read()
+        .as_ref()
+        .filter(|highlights| {
+            !has_any_highlight && highlights.history_change == editor.history.current_change()
+        })
+        .map(|highlights| {
+            highlights.line_highlights(line_index, rope.line(line_index).len_utf16_cu())
+        })
+        .unwrap_or_default();
+    let (highlights, highlight_color) = if symbol_highlights.is_empty() {
+        (editable.highlights_attr(line_index), "rgb(65, 65, 65)")
+    } else {
+        (
+            AttributeValue::any_value(CustomAttributeValues::TextHighlights(symbol_highlights)),
+            "rgb(55, 70, 95)",
+        )
+    };
+
     // Only highlight the active line when there is no text selected
-    let line_background = if is_line_selected && !editable.has_any_highlight() {
+    let line_background = if is_line_selected && !has_any_highlight {
         "rgb(70, 70, 70)"
     } else {
         ""
@@ -148,13 +324,91 @@ pub fn EditorLine(
                     }
                 }
             }
-            label {
+            if indent_guides > 0 {
+                rect {
+                    width: "0",
+                    height: "fill",
+                    rect {
+                        height: "fill",
+                        offset_x: "{gutter_width}",
+                        direction: "horizontal",
+                        for i in 0..indent_guides {
+                            rect {
+                                key: "{i}",
+                                width: "{indent_guide_width}",
+                                height: "fill",
+                                rect {
+                                    width: "1",
+                                    height: "fill",
+                                    background: "rgb(255, 255, 255, 0.08)",
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(offset_x) = block_cursor_offset {
+                rect {
+                    width: "0",
+                    height: "fill",
+                    rect {
+                        width: "2",
+                        height: "70%",
+                        offset_x: "{offset_x}",
+                        offset_y: "{line_height * 0.15}",
+                        layer: "-30",
+                        background: "white",
+                    }
+                }
+            }
+            if let Some(offset_x) = custom_cursor_offset {
+                EditorCursor {
+                    offset_x,
+                    char_width,
+                    line_height,
+                    style: cursor_style,
+                    blink: cursor_blink,
+                }
+            }
+            if let Some(offset_x) = request_lens_offset {
+                rect {
+                    width: "0",
+                    height: "fill",
+                    rect {
+                        height: "fill",
+                        offset_x: "{offset_x}",
+                        main_align: "center",
+                        layer: "-30",
+                        label {
+                            onclick: send_request,
+                            font_size: "{font_size * 0.85}",
+                            color: "rgb(120, 170, 230)",
+                            "▶ Send"
+                        }
+                    }
+                }
+            }
+            rect {
+                onmousedown: ongutterdown,
+                onmouseover: ongutterover,
                 width: "{gutter_width}",
-                text_align: "center",
-                font_size: "{font_size}",
-                color: "{gutter_color}",
-                main_align: "center",
-                "{line_index + 1} "
+                height: "fill",
+                direction: "horizontal",
+                cross_align: "center",
+                rect {
+                    width: "3",
+                    height: "70%",
+                    corner_radius: "2",
+                    background: "{bookmark_color}",
+                }
+                label {
+                    width: "fill",
+                    text_align: "center",
+                    font_size: "{font_size}",
+                    color: "{gutter_color}",
+                    main_align: "center",
+                    "{line_index + 1} "
+                }
             }
             paragraph {
                 onmousedown,
@@ -165,33 +419,87 @@ pub fn EditorLine(
                 height: "fill",
                 main_align: "center",
                 cursor_index: "{character_index}",
-                cursor_color: "white",
+                cursor_color: "{cursor_color}",
                 max_lines: "1",
                 cursor_mode: "editable",
                 cursor_id: "{line_index}",
                 highlights,
-                highlight_color: "rgb(65, 65, 65)",
+                highlight_color: "{highlight_color}",
                 highlight_mode: "expanded",
                 font_size: "{font_size}",
-                font_family: "Jetbrains Mono",
-                {line.iter().enumerate().map(|(i, (syntax_type, text))| {
-                    let text = match text {
-                        TextNode::Range(word_pos) => {
-                            rope.slice(word_pos.clone()).to_string()
-                        },
-                        TextNode::LineOfChars { len, char } => {
-                            format!("{char}").repeat(*len)
-                        }
-                    };
+                font_family: "{font_family}",
+                {line_text}
+            }
+        }
+    )
+}
 
-                    rsx!(
-                        text {
-                            key: "{i}",
-                            color: "{syntax_type.color()}",
-                            "{text}"
-                        }
-                    )
-                })}
+#[derive(Props, Clone, PartialEq)]
+struct EditorCursorProps {
+    offset_x: f32,
+    char_width: f32,
+    line_height: f32,
+    style: CursorStyle,
+    blink: bool,
+}
+
+/// Cursor drawn over the text of the line, in the style of the settings.
+#[allow(non_snake_case)]
+fn EditorCursor(
+    EditorCursorProps {
+        offset_x,
+        char_width,
+        line_height,
+        style,
+        blink,
+    }: EditorCursorProps,
+) -> Element {
+    let mut is_visible = use_signal(|| true);
+
+    use_hook(move || {
+        if blink {
+            spawn(async move {
+                loop {
+                    tokio::time::sleep(CURSOR_BLINK_INTERVAL).await;
+                    let was_visible = *is_visible.peek();
+                    is_visible.set(!was_visible);
+                }
+            });
+        }
+    });
+
+    // Moving the cursor shows it right away
+    use_effect(use_reactive(&offset_x, move |_| {
+        is_visible.set(true);
+    }));
+
+    let (width, height, offset_y, color) = match style {
+        CursorStyle::Bar => (2.0, line_height * 0.7, line_height * 0.15, "white"),
+        CursorStyle::Block => (
+            char_width,
+            line_height * 0.7,
+            line_height * 0.15,
+            "rgb(255, 255, 255, 0.5)",
+        ),
+        CursorStyle::Underline => (char_width, 2.0, line_height * 0.85, "white"),
+    };
+    let background = if *is_visible.read() {
+        color
+    } else {
+        "transparent"
+    };
+
+    rsx!(
+        rect {
+            width: "0",
+            height: "fill",
+            rect {
+                width: "{width}",
+                height: "{height}",
+                offset_x: "{offset_x}",
+                offset_y: "{offset_y}",
+                layer: "-30",
+                background: "{background}",
             }
         }
     )
@@ -1,8 +1,11 @@
 use std::path::PathBuf;
 
+use crate::constants::{BASE_FONT_SIZE, MAX_FONT_SIZE};
+use crate::fs::{Encoding, LineEnding, TextFormat};
+use crate::language::language_config;
 use crate::state::{
-    AppSettings, AppState, EditorCommands, KeyboardShortcuts, PanelTab, PanelTabData,
-    RadioAppState, TabProps,
+    AppSettings, AppState, AppStateUtils, Channel, EditorCommands, EditorView, KeyboardShortcuts,
+    PanelTab, PanelTabData, RadioAppState, ShortcutContext, TabProps,
 };
 
 use freya::prelude::keyboard::Key;
@@ -12,19 +15,84 @@ use freya::prelude::*;
 use skia_safe::textlayout::FontCollection;
 
 use super::{
-    commands::{DecreaseFontSizeCommand, IncreaseFontSizeCommand, SaveFileCommand},
+    backups::remove_backup,
+    code_actions::CodeActionsMenu,
+    commands::{
+        AddTodoCommand, ChangeIndentWidthCommand, CompareWithSavedCommand, CompleteWordCommand,
+        CopyHtmlCommand, DecreaseFontSizeCommand, DecrementNumberCommand, DeleteLineCommand,
+        DocumentStatsCommand, DuplicateLineDownCommand, DuplicateLineUpCommand, ExportHtmlCommand,
+        ExtendBlockSelectionCommand, ExtendSelectionCommand, FormatFileCommand,
+        GoToDefinitionCommand, GrepCommand, IncreaseFontSizeCommand, IncrementNumberCommand,
+        InsertLineAboveCommand, InsertLineBelowCommand, JoinLinesCommand, MoveCursorCommand,
+        MoveLineDownCommand, MoveLineUpCommand, QuickJumpCommand, ResetZoomCommand,
+        SaveFileCommand, SelectAllCommand, SelectLineCommand, SelectWordCommand,
+        ShowCodeActionsCommand, SwapSelectionAnchorsCommand, ToggleBlockCommentCommand,
+        ToggleBookmarkCommand, ToggleFileLspCommand, ToggleIndentWithTabsCommand,
+        ToggleLineCommentCommand, ToggleSplitEditorCommand, ToggleWorkspaceLspCommand,
+        TransposeCharsCommand,
+    },
+    document_stats::DocumentStats,
     editor_data::{EditorData, EditorType},
     editor_ui::EditorUi,
+    http_runner::HttpResponseState,
+    utils::{AppStateEditorUtils, TabEditorUtils},
+    BlockSelectionDirection, Indentation, Motion,
 };
 
 /// A tab with an embedded Editor.
 pub struct EditorTab {
     pub editor: EditorData,
+    /// Preview tabs are replaced by the next previewed file, until they are edited.
+    pub preview: bool,
+    /// Response of the last request sent from a `.http` file.
+    pub http_response: Option<HttpResponseState>,
+    /// Code actions menu opened at the cursor.
+    pub code_actions: Option<CodeActionsMenu>,
+    /// Statistics popover of the text.
+    pub document_stats: Option<DocumentStats>,
+    /// Second viewport over the same buffer, below the first one.
+    pub split: Option<EditorSplit>,
+    /// The file was changed by something else since it was read or saved,
+    /// so saving waits for the user to choose what to do.
+    pub save_conflict: bool,
+    /// The language server is turned off for this file (e.g. a huge generated one).
+    pub lsp_disabled: bool,
+    /// Zoom of this editor alone, over the font size of the settings.
+    pub zoom: f32,
+}
+
+/// State of a split editor, where each viewport has its own scroll and cursor.
+pub struct EditorSplit {
+    /// Viewport (`0` is the top one) whose cursor is the one of the editor.
+    pub focused_viewport: usize,
+    /// Cursor and selection of the other viewport.
+    cursor: usize,
+    selected: Option<(usize, usize)>,
 }
 
 impl PanelTab for EditorTab {
     fn on_close(&mut self, app_state: &mut AppState) {
-        // Notify the language server that a document was closed
+        let editor_settings = &app_state.settings.editor;
+        if editor_settings.persistent_undo {
+            self.editor
+                .persist_history(editor_settings.persistent_undo_limit);
+        }
+
+        // Keep the bookmarks for the next time the file is opened
+        if let Some(path) = self.editor.path() {
+            // Its unsaved changes, if any, are discarded
+            remove_backup(path);
+
+            app_state
+                .closed_bookmarks
+                .retain(|bookmark| &bookmark.path != path);
+            app_state.closed_bookmarks.extend(self.editor.bookmarks());
+        }
+
+        // Notify the language server that a document was closed, unless it was already
+        if self.is_lsp_disabled(app_state) {
+            return;
+        }
         let language_id = self.editor.editor_type.language_id();
         let language_server_id = language_id.language_server();
 
@@ -47,8 +115,11 @@ impl PanelTab for EditorTab {
         app_settings: &AppSettings,
         font_collection: &FontCollection,
     ) {
-        self.editor
-            .measure_longest_line(app_settings.editor.font_size, font_collection);
+        self.editor.measure_longest_line(
+            self.font_size(app_settings.editor.font_size),
+            app_settings.editor.rendered_font_family(),
+            font_collection,
+        );
     }
 
     fn get_data(&self) -> PanelTabData {
@@ -57,6 +128,8 @@ impl PanelTab for EditorTab {
             id,
             title,
             edited: self.editor.is_edited(),
+            preview: self.preview,
+            read_only: self.editor.read_only,
         }
     }
     fn render(&self) -> fn(TabProps) -> Element {
@@ -74,18 +147,181 @@ impl PanelTab for EditorTab {
 
 impl EditorTab {
     /// Open an EditorTab in the focused panel.
-    pub fn open_with(app_state: &mut AppState, path: PathBuf, root_path: PathBuf, content: String) {
-        let data = EditorData::new(
+    pub fn open_with(
+        app_state: &mut AppState,
+        path: PathBuf,
+        root_path: PathBuf,
+        content: String,
+        format: TextFormat,
+        read_only: bool,
+    ) {
+        let tab = Self::new(
+            app_state, path, root_path, content, format, read_only, false,
+        );
+        app_state.push_tab(tab, app_state.focused_panel, true);
+    }
+
+    /// Open an EditorTab in the focused panel as a preview, replacing the previous preview tab.
+    pub fn open_as_preview(
+        app_state: &mut AppState,
+        path: PathBuf,
+        root_path: PathBuf,
+        content: String,
+        format: TextFormat,
+        read_only: bool,
+    ) {
+        let panel = app_state.focused_panel;
+        let is_opened = app_state
+            .find_editor_tab(&path)
+            .map(|(opened_panel, _)| opened_panel)
+            == Some(panel);
+        let preview_tab = app_state
+            .panel(panel)
+            .tabs()
+            .iter()
+            .position(|tab| tab.get_data().preview);
+
+        let tab = Self::new(app_state, path, root_path, content, format, read_only, true);
+        match preview_tab {
+            Some(preview_tab) if !is_opened => app_state.replace_tab(panel, preview_tab, tab),
+            _ => app_state.push_tab(tab, panel, true),
+        }
+    }
+
+    fn new(
+        app_state: &AppState,
+        path: PathBuf,
+        root_path: PathBuf,
+        content: String,
+        format: TextFormat,
+        read_only: bool,
+        preview: bool,
+    ) -> Self {
+        let transport = app_state.transport(&path);
+        let language = language_config(&path, &app_state.settings.languages);
+        let mut data = EditorData::new(
             EditorType::FS { path, root_path },
             Rope::from(content),
             0,
             app_state.clipboard,
-            app_state.default_transport.clone(),
+            transport,
             app_state.settings.editor.font_size,
+            app_state.settings.editor.rendered_font_family(),
             &app_state.font_collection.clone(),
+            language,
+        );
+        data.read_only = read_only;
+        data.format = format;
+        data.saved_format = format;
+
+        let editor_settings = &app_state.settings.editor;
+        data.indentation = Indentation::detect(
+            &data.rope,
+            Indentation {
+                with_tabs: editor_settings.indent_with_tabs,
+                width: editor_settings.indent_width.max(1),
+            },
         );
 
-        app_state.push_tab(Self { editor: data }, app_state.focused_panel, true);
+        if app_state.settings.editor.persistent_undo {
+            data.restore_history();
+        }
+
+        // Bookmarks from the last time the file was opened
+        let len_lines = data.rope.len_lines();
+        let bookmarks = app_state
+            .closed_bookmarks
+            .iter()
+            .filter(|bookmark| Some(&bookmark.path) == data.path() && bookmark.line < len_lines)
+            .map(|bookmark| bookmark.line)
+            .collect();
+        data.bookmarks = bookmarks;
+
+        Self {
+            editor: data,
+            preview,
+            http_response: None,
+            code_actions: None,
+            document_stats: None,
+            split: None,
+            save_conflict: false,
+            lsp_disabled: false,
+            zoom: 1.0,
+        }
+    }
+
+    /// Font size of this editor, the one of the settings with the zoom of the editor.
+    pub fn font_size(&self, base_font_size: f32) -> f32 {
+        (base_font_size * self.zoom).clamp(BASE_FONT_SIZE, MAX_FONT_SIZE)
+    }
+
+    /// Zoom this editor alone, keeping its font size in the allowed range.
+    pub fn set_zoom(
+        &mut self,
+        zoom: f32,
+        base_font_size: f32,
+        font_family: &str,
+        font_collection: &FontCollection,
+    ) {
+        self.zoom = (base_font_size * zoom).clamp(BASE_FONT_SIZE, MAX_FONT_SIZE) / base_font_size;
+        self.editor.measure_longest_line(
+            self.font_size(base_font_size),
+            font_family,
+            font_collection,
+        );
+    }
+
+    /// The language server is turned off for this file or for its workspace.
+    pub fn is_lsp_disabled(&self, app_state: &AppState) -> bool {
+        self.lsp_disabled
+            || self
+                .editor
+                .editor_type
+                .paths()
+                .is_some_and(|(_, root_path)| app_state.lsp_disabled_workspaces.contains(root_path))
+    }
+
+    /// Split the editor in two viewports, or go back to a single one.
+    pub fn toggle_split(&mut self) {
+        self.split = match self.split {
+            Some(_) => None,
+            None => Some(EditorSplit {
+                focused_viewport: 0,
+                cursor: self.editor.cursor_pos(),
+                selected: None,
+            }),
+        };
+    }
+
+    pub fn is_viewport_focused(&self, viewport: usize) -> bool {
+        self.split
+            .as_ref()
+            .map(|split| split.focused_viewport == viewport)
+            .unwrap_or(true)
+    }
+
+    /// Give the editor cursor to another viewport, keeping the one of the previous viewport.
+    pub fn focus_viewport(&mut self, viewport: usize) {
+        let Some(split) = &mut self.split else {
+            return;
+        };
+        if split.focused_viewport == viewport {
+            return;
+        }
+
+        // The text might have been edited from the other viewport in the meantime
+        let len_chars = self.editor.len_chars();
+        let cursor = split.cursor.min(len_chars);
+        let selected = split
+            .selected
+            .map(|(from, to)| (from.min(len_chars), to.min(len_chars)));
+
+        split.focused_viewport = viewport;
+        split.cursor = self.editor.cursor_pos();
+        split.selected = self.editor.selected;
+        self.editor.cursor = TextCursor::new(cursor);
+        self.editor.selected = selected;
+        self.code_actions = None;
     }
 
     /// Initialize the EditorTab module.
@@ -97,16 +333,106 @@ impl EditorTab {
         // Register Commands
         commands.register(IncreaseFontSizeCommand(radio_app_state));
         commands.register(DecreaseFontSizeCommand(radio_app_state));
+        commands.register(ResetZoomCommand(radio_app_state));
         commands.register(SaveFileCommand(radio_app_state));
+        commands.register(QuickJumpCommand(radio_app_state));
+        commands.register(CompareWithSavedCommand(radio_app_state));
+        commands.register(DeleteLineCommand(radio_app_state));
+        commands.register(InsertLineBelowCommand(radio_app_state));
+        commands.register(InsertLineAboveCommand(radio_app_state));
+        commands.register(MoveLineUpCommand(radio_app_state));
+        commands.register(MoveLineDownCommand(radio_app_state));
+        commands.register(DuplicateLineUpCommand(radio_app_state));
+        commands.register(DuplicateLineDownCommand(radio_app_state));
+        commands.register(JoinLinesCommand(radio_app_state));
+        commands.register(TransposeCharsCommand(radio_app_state));
+        commands.register(SwapSelectionAnchorsCommand(radio_app_state));
+        commands.register(IncrementNumberCommand(radio_app_state));
+        commands.register(DecrementNumberCommand(radio_app_state));
+        commands.register(ToggleLineCommentCommand(radio_app_state));
+        commands.register(ToggleBlockCommentCommand(radio_app_state));
+        commands.register(AddTodoCommand(radio_app_state));
+        commands.register(GoToDefinitionCommand(radio_app_state));
+        commands.register(ShowCodeActionsCommand(radio_app_state));
+        commands.register(DocumentStatsCommand(radio_app_state));
+        commands.register(GrepCommand(radio_app_state));
+        commands.register(FormatFileCommand(radio_app_state));
+        commands.register(ExportHtmlCommand(radio_app_state));
+        commands.register(CopyHtmlCommand(radio_app_state));
+        commands.register(ToggleSplitEditorCommand(radio_app_state));
+        commands.register(ToggleFileLspCommand(radio_app_state));
+        commands.register(ToggleWorkspaceLspCommand(radio_app_state));
+        commands.register(ToggleBookmarkCommand(radio_app_state));
+        commands.register(CompleteWordCommand(radio_app_state));
+        commands.register(ToggleIndentWithTabsCommand(radio_app_state));
+        commands.register(ChangeIndentWidthCommand(radio_app_state));
+        commands.register(SetLineEndingCommand(radio_app_state, LineEnding::Lf));
+        commands.register(SetLineEndingCommand(radio_app_state, LineEnding::Crlf));
+        for encoding in Encoding::ALL {
+            commands.register(SetEncodingCommand(radio_app_state, encoding));
+        }
+        for direction in BlockSelectionDirection::ALL {
+            commands.register(ExtendBlockSelectionCommand(radio_app_state, direction));
+        }
+        for motion in Motion::ALL {
+            commands.register(MoveCursorCommand(radio_app_state, motion));
+            commands.register(ExtendSelectionCommand(radio_app_state, motion));
+        }
+        commands.register(SelectWordCommand(radio_app_state));
+        commands.register(SelectLineCommand(radio_app_state));
+        commands.register(SelectAllCommand(radio_app_state));
 
-        // Register Shortcuts
+        // Register Shortcuts, only while editing so they don't fire while typing somewhere else
         keyboard_shorcuts.register(
+            ShortcutContext::View(EditorView::Panels),
             |data: &KeyboardData,
              commands: &mut EditorCommands,
-             _radio_app_state: RadioAppState| {
+             mut radio_app_state: RadioAppState| {
                 let is_pressing_alt = data.modifiers == Modifiers::ALT;
                 let is_pressing_ctrl = data.modifiers == Modifiers::CONTROL;
+                let is_pressing_ctrl_shift =
+                    data.modifiers == Modifiers::CONTROL | Modifiers::SHIFT;
+                let is_pressing_alt_shift = data.modifiers == Modifiers::ALT | Modifiers::SHIFT;
+                let is_pressing_ctrl_alt = data.modifiers == Modifiers::CONTROL | Modifiers::ALT;
                 match data.code {
+                    // Pressing `Esc` to cancel a quick jump, block selection, code actions menu or document statistics
+                    Code::Escape => {
+                        let (panel, active_tab) = radio_app_state.get_focused_data();
+                        let Some(active_tab) = active_tab else {
+                            return false;
+                        };
+
+                        let is_cancellable = {
+                            let app_state = radio_app_state.read();
+                            app_state
+                                .panel(panel)
+                                .tab(active_tab)
+                                .as_text_editor()
+                                .map(|editor_tab| {
+                                    editor_tab.editor.quick_jump.is_some()
+                                        || editor_tab.editor.block_selection.is_some()
+                                        || editor_tab.code_actions.is_some()
+                                        || editor_tab.document_stats.is_some()
+                                })
+                                .unwrap_or_default()
+                        };
+
+                        if !is_cancellable {
+                            return false;
+                        }
+
+                        let mut app_state =
+                            radio_app_state.write_channel(Channel::follow_tab(panel, active_tab));
+                        let editor_tab = app_state.editor_tab_mut(panel, active_tab);
+                        editor_tab.editor.cancel_quick_jump();
+                        editor_tab.editor.clear_block_selection();
+                        editor_tab.code_actions = None;
+                        editor_tab.document_stats = None;
+                    }
+                    // Pressing `Alt J`
+                    Code::KeyJ if is_pressing_alt => {
+                        commands.trigger(QuickJumpCommand::id());
+                    }
                     // Pressing `Alt +`
                     _ if is_pressing_alt && data.key == Key::Character("+".to_string()) => {
                         commands.trigger(IncreaseFontSizeCommand::id());
@@ -119,6 +445,105 @@ impl EditorTab {
                     Code::KeyS if is_pressing_ctrl => {
                         commands.trigger(SaveFileCommand::id());
                     }
+                    // Pressing `Ctrl Shift K`
+                    Code::KeyK if is_pressing_ctrl_shift => {
+                        commands.trigger(DeleteLineCommand::id());
+                    }
+                    // Pressing `Ctrl Enter`
+                    Code::Enter if is_pressing_ctrl => {
+                        commands.trigger(InsertLineBelowCommand::id());
+                    }
+                    // Pressing `Ctrl Shift Enter`
+                    Code::Enter if is_pressing_ctrl_shift => {
+                        commands.trigger(InsertLineAboveCommand::id());
+                    }
+                    // Pressing `Alt ArrowUp`
+                    Code::ArrowUp if is_pressing_alt => {
+                        commands.trigger(MoveLineUpCommand::id());
+                    }
+                    // Pressing `Alt ArrowDown`
+                    Code::ArrowDown if is_pressing_alt => {
+                        commands.trigger(MoveLineDownCommand::id());
+                    }
+                    // Pressing `Shift Alt ArrowUp`
+                    Code::ArrowUp if is_pressing_alt_shift => {
+                        commands.trigger(DuplicateLineUpCommand::id());
+                    }
+                    // Pressing `Shift Alt ArrowDown`
+                    Code::ArrowDown if is_pressing_alt_shift => {
+                        commands.trigger(DuplicateLineDownCommand::id());
+                    }
+                    // Pressing `Ctrl Alt ArrowUp`
+                    Code::ArrowUp if is_pressing_ctrl_alt => {
+                        commands
+                            .trigger(ExtendBlockSelectionCommand::id(BlockSelectionDirection::Up));
+                    }
+                    // Pressing `Ctrl Alt ArrowDown`
+                    Code::ArrowDown if is_pressing_ctrl_alt => {
+                        commands.trigger(ExtendBlockSelectionCommand::id(
+                            BlockSelectionDirection::Down,
+                        ));
+                    }
+                    // Pressing `Ctrl Alt ArrowLeft`
+                    Code::ArrowLeft if is_pressing_ctrl_alt => {
+                        commands.trigger(ExtendBlockSelectionCommand::id(
+                            BlockSelectionDirection::Left,
+                        ));
+                    }
+                    // Pressing `Ctrl Alt ArrowRight`
+                    Code::ArrowRight if is_pressing_ctrl_alt => {
+                        commands.trigger(ExtendBlockSelectionCommand::id(
+                            BlockSelectionDirection::Right,
+                        ));
+                    }
+                    // Pressing `Ctrl J`
+                    Code::KeyJ if is_pressing_ctrl => {
+                        commands.trigger(JoinLinesCommand::id());
+                    }
+                    // Pressing `Ctrl ArrowUp`
+                    Code::ArrowUp if is_pressing_ctrl => {
+                        commands.trigger(IncrementNumberCommand::id());
+                    }
+                    // Pressing `Ctrl ArrowDown`
+                    Code::ArrowDown if is_pressing_ctrl => {
+                        commands.trigger(DecrementNumberCommand::id());
+                    }
+                    // Pressing `Ctrl L`
+                    Code::KeyL if is_pressing_ctrl => {
+                        commands.trigger(SelectLineCommand::id());
+                    }
+                    // Pressing `Ctrl Shift \`
+                    Code::Backslash if is_pressing_ctrl_shift => {
+                        commands.trigger(MoveCursorCommand::id(Motion::MatchingBracket));
+                    }
+                    // Pressing `Ctrl T`
+                    Code::KeyT if is_pressing_ctrl => {
+                        commands.trigger(TransposeCharsCommand::id());
+                    }
+                    // Pressing `Ctrl /`
+                    Code::Slash if is_pressing_ctrl => {
+                        commands.trigger(ToggleLineCommentCommand::id());
+                    }
+                    // Pressing `Shift Alt A`
+                    Code::KeyA if is_pressing_alt_shift => {
+                        commands.trigger(ToggleBlockCommentCommand::id());
+                    }
+                    // Pressing `F12`
+                    Code::F12 => {
+                        commands.trigger(GoToDefinitionCommand::id());
+                    }
+                    // Pressing `Ctrl .`
+                    Code::Period if is_pressing_ctrl => {
+                        commands.trigger(ShowCodeActionsCommand::id());
+                    }
+                    // Pressing `Ctrl Space`
+                    Code::Space if is_pressing_ctrl => {
+                        commands.trigger(CompleteWordCommand::id());
+                    }
+                    // Pressing `Ctrl F2`
+                    Code::F2 if is_pressing_ctrl => {
+                        commands.trigger(ToggleBookmarkCommand::id());
+                    }
                     _ => return false,
                 }
 
@@ -1,26 +1,32 @@
 use std::{ffi::OsStr, path::PathBuf, time::Duration};
 
 use crate::hooks::*;
-use crate::lsp::{use_lsp, LspAction};
-use crate::state::{EditorView, TabProps};
-use crate::tabs::editor::AppStateEditorUtils;
+use crate::lsp::{use_lsp, DocumentHighlights, LspAction, UseLsp};
+use crate::scratchpads::is_scratchpad;
+use crate::state::{AutosaveMode, EditorView, TabProps};
+use crate::tabs::editor::code_actions_menu::CodeActionsMenuView;
+use crate::tabs::editor::document_stats_popover::DocumentStatsPopover;
+use crate::tabs::editor::http_response::HttpResponseView;
 use crate::tabs::editor::BuilderArgs;
 use crate::tabs::editor::EditorLine;
+use crate::tabs::editor::{
+    apply_selected_code_action, autosave_editor_tab, char_to_lsp_position,
+    compare_editor_tab_with_saved, overwrite_editor_tab, reload_editor_tab, zoom_editor_tab,
+    AppStateEditorUtils,
+};
+use crate::tabs::editor::{StickyScroll, MAX_STICKY_LINES};
 use crate::{components::*, state::Channel};
 
 use dioxus_radio::prelude::use_radio;
-use dioxus_sdk::utils::timing::use_debounce;
+use dioxus_sdk::utils::timing::{use_debounce, UseDebounce};
 use freya::events::KeyboardEvent;
 use freya::prelude::keyboard::Key;
 use freya::prelude::keyboard::Modifiers;
 use freya::prelude::*;
-use lsp_types::Position;
+use lsp_types::{Hover, Position};
 
 use skia_safe::textlayout::Paragraph;
 
-static LINES_JUMP_ALT: usize = 5;
-static LINES_JUMP_CONTROL: usize = 3;
-
 #[allow(non_snake_case)]
 pub fn EditorUi(
     TabProps {
@@ -39,14 +45,11 @@ pub fn EditorUi(
     // What position in the text the user is hovering
     let hover_location = use_signal(|| None);
 
-    // What location is the user hovering with the mouse
-    let cursor_coords = use_signal(CursorPoint::default);
+    // Occurrences of the symbol under the cursor
+    let mut document_highlights = use_signal(|| None);
 
-    // Initialize the editable text
-    let mut editable = use_edit(&radio_app_state, panel_index, tab_index);
-
-    // The scroll positions of the editor
-    let mut scroll_offsets = use_signal(|| (0, 0));
+    // Remember where the cursor jumps from
+    use_navigation_history(radio_app_state, panel_index, tab_index);
 
     // Initialize the language server integration
     let lsp = use_lsp(
@@ -55,6 +58,7 @@ pub fn EditorUi(
         tab_index,
         radio_app_state,
         hover_location,
+        document_highlights,
     );
 
     // Send hover notifications to the LSP only every 300ms and when hovering
@@ -71,6 +75,194 @@ pub fn EditorUi(
         },
     );
 
+    // Ask for the occurrences only once the cursor rests on the same position for a while
+    let mut highlight_debouncer = use_debounce(
+        Duration::from_millis(300),
+        move |(cursor_pos, action): (usize, LspAction)| {
+            let current_cursor_pos = radio_app_state
+                .read()
+                .editor_tab(panel_index, tab_index)
+                .editor
+                .cursor_pos();
+            if current_cursor_pos == cursor_pos {
+                lsp.send(action);
+            }
+        },
+    );
+
+    let cursor_pos = editor.cursor_pos();
+    let history_change = editor.history.current_change();
+    use_effect(use_reactive(
+        &(cursor_pos, history_change),
+        move |(cursor_pos, history_change)| {
+            if !lsp.is_supported() {
+                return;
+            }
+
+            let (position, is_identifier, text) = {
+                let app_state = radio_app_state.read();
+                let rope = &app_state.editor_tab(panel_index, tab_index).editor.rope;
+                let is_identifier_char = |pos: usize| {
+                    rope.get_char(pos)
+                        .map(|ch| ch.is_alphanumeric() || ch == '_')
+                        .unwrap_or_default()
+                };
+                (
+                    char_to_lsp_position(rope, cursor_pos),
+                    is_identifier_char(cursor_pos)
+                        || (cursor_pos > 0 && is_identifier_char(cursor_pos - 1)),
+                    rope.clone(),
+                )
+            };
+
+            // Keep the highlights while the cursor stays in one of the occurrences
+            let is_highlighted = document_highlights
+                .peek()
+                .as_ref()
+                .map(|highlights: &DocumentHighlights| {
+                    highlights.history_change == history_change && highlights.contains(position)
+                })
+                .unwrap_or_default();
+            if is_highlighted {
+                return;
+            }
+
+            if document_highlights.peek().is_some() {
+                document_highlights.set(None);
+            }
+
+            if is_identifier {
+                highlight_debouncer.action((
+                    cursor_pos,
+                    LspAction::DocumentHighlight {
+                        position,
+                        text,
+                        history_change,
+                    },
+                ));
+            }
+        },
+    ));
+
+    let http_response = editor_tab.http_response.clone();
+    let is_split = editor_tab.split.is_some();
+    let save_conflict = editor_tab.save_conflict;
+
+    let onclose = move |_| {
+        let mut app_state = radio_app_state.write();
+        app_state
+            .editor_tab_mut(panel_index, tab_index)
+            .http_response = None;
+    };
+
+    // The first viewport keeps its scroll when the editor is split
+    let first_viewport_height = if is_split { "50%" } else { "100%" };
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            background: "rgb(40, 40, 40)",
+            if let Some((path, root_path)) = paths {
+                FilePath {
+                    path: path.clone(),
+                    root_path: root_path.clone(),
+                }
+            }
+            if save_conflict {
+                SaveConflictBar {
+                    panel_index,
+                    tab_index
+                }
+            }
+            rect {
+                width: "100%",
+                height: "fill",
+                direction: "horizontal",
+                rect {
+                    width: "fill",
+                    height: "100%",
+                    rect {
+                        width: "100%",
+                        height: "{first_viewport_height}",
+                        EditorViewport {
+                            panel_index,
+                            tab_index,
+                            viewport: 0,
+                            lsp,
+                            hover_location,
+                            document_highlights,
+                            debouncer,
+                        }
+                    }
+                    if is_split {
+                        rect {
+                            width: "100%",
+                            height: "1",
+                            background: "rgb(60, 60, 60)",
+                        }
+                        rect {
+                            width: "100%",
+                            height: "fill",
+                            EditorViewport {
+                                panel_index,
+                                tab_index,
+                                viewport: 1,
+                                lsp,
+                                hover_location,
+                                document_highlights,
+                                debouncer,
+                            }
+                        }
+                    }
+                }
+                if let Some(response) = http_response {
+                    HttpResponseView {
+                        response,
+                        onclose
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// Scrollable view of the lines of an editor, split editors have two of them.
+#[allow(non_snake_case)]
+#[component]
+fn EditorViewport(
+    panel_index: usize,
+    tab_index: usize,
+    viewport: usize,
+    lsp: UseLsp,
+    hover_location: Signal<Option<(u32, Hover)>>,
+    document_highlights: Signal<Option<DocumentHighlights>>,
+    debouncer: UseDebounce<(CursorPoint, u32, Paragraph)>,
+) -> Element {
+    let mut radio_app_state = use_radio(Channel::follow_tab(panel_index, tab_index));
+
+    let app_state = radio_app_state.read();
+    let editor_tab = app_state.editor_tab(panel_index, tab_index);
+    let editor = &editor_tab.editor;
+
+    // What location is the user hovering with the mouse
+    let cursor_coords = use_signal(CursorPoint::default);
+
+    // Initialize the editable text
+    let mut editable = use_edit(&radio_app_state, panel_index, tab_index, viewport);
+
+    // The scroll positions of the editor
+    let mut scroll_offsets = use_signal(|| (0, 0));
+
+    // The size of the editor viewport
+    let (viewport_ref, viewport_size) = use_node();
+
+    // Save the file after a while without typing, if enabled
+    let mut autosave_debouncer = use_debounce(
+        Duration::from_millis(app_state.settings.editor.autosave_delay),
+        move |path: PathBuf| autosave_editor_tab(radio_app_state, &path),
+    );
+
     let onscroll = move |(axis, scroll): (Axis, i32)| match axis {
         Axis::X => {
             if scroll_offsets.read().0 != scroll {
@@ -93,13 +285,20 @@ pub fn EditorUi(
     };
 
     let onclick = move |_: MouseEvent| {
-        let (is_code_editor_view_focused, is_editor_focused) = {
+        let (is_code_editor_view_focused, is_editor_focused, is_viewport_focused) = {
             let app_state = radio_app_state.read();
             let panel = app_state.panel(panel_index);
             let is_code_editor_view_focused = *app_state.focused_view() == EditorView::Panels;
             let is_editor_focused =
                 app_state.focused_panel() == panel_index && panel.active_tab() == Some(tab_index);
-            (is_code_editor_view_focused, is_editor_focused)
+            let is_viewport_focused = app_state
+                .editor_tab(panel_index, tab_index)
+                .is_viewport_focused(viewport);
+            (
+                is_code_editor_view_focused,
+                is_editor_focused,
+                is_viewport_focused,
+            )
         };
 
         if !is_code_editor_view_focused {
@@ -112,14 +311,139 @@ pub fn EditorUi(
             app_state.set_focused_panel(panel_index);
             app_state.panel_mut(panel_index).set_active_tab(tab_index);
         }
+
+        if !is_viewport_focused {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel_index, tab_index));
+            app_state
+                .editor_tab_mut(panel_index, tab_index)
+                .focus_viewport(viewport);
+        }
     };
 
     let cursor_reference = editable.cursor_attr();
     let line_height = app_state.line_height();
-    let font_size = app_state.font_size();
+    let font_size = editor_tab.font_size(app_state.font_size());
 
     let manual_line_height = (font_size * line_height).floor();
     let syntax_blocks_len = editor.metrics.syntax_blocks.len();
+    let viewport_height = viewport_size.area.height();
+    let viewport_width = viewport_size.area.width();
+
+    // Scroll to keep the cursor visible when it moves, with some room around it
+    let cursor_row = editor.cursor_row();
+    let cursor_col = editor.cursor_col();
+    let scroll_margin = app_state.settings.editor.scroll_margin;
+    let char_width = editor.metrics.char_width;
+    use_effect(use_reactive(
+        &(
+            (cursor_row, cursor_col),
+            (viewport_width, viewport_height),
+            (manual_line_height, char_width, font_size),
+            scroll_margin,
+        ),
+        move |(
+            (cursor_row, cursor_col),
+            (viewport_width, viewport_height),
+            (manual_line_height, char_width, font_size),
+            scroll_margin,
+        )| {
+            let is_viewport_focused = radio_app_state
+                .read()
+                .editor_tab(panel_index, tab_index)
+                .is_viewport_focused(viewport);
+            if !is_viewport_focused || viewport_height <= 0.0 {
+                return;
+            }
+
+            let (scroll_x, scroll_y) = *scroll_offsets.peek();
+            let scroll_y = scroll_to_show(
+                -scroll_y as f32,
+                cursor_row as f32 * manual_line_height,
+                manual_line_height,
+                (scroll_margin as f32 * manual_line_height)
+                    .min((viewport_height - manual_line_height) / 2.0),
+                viewport_height,
+            );
+            // The gutter scrolls along with the text
+            let gutter_width = font_size * 3.0;
+            let scroll_x = scroll_to_show(
+                -scroll_x as f32,
+                cursor_col as f32 * char_width,
+                char_width,
+                (scroll_margin as f32 * char_width)
+                    .min((viewport_width - gutter_width - char_width) / 2.0),
+                viewport_width - gutter_width,
+            );
+
+            let scroll = (-scroll_x as i32, -scroll_y as i32);
+            if *scroll_offsets.peek() != scroll {
+                scroll_offsets.set(scroll);
+            }
+        },
+    ));
+
+    // Headers of the scopes around the lines covered by the sticky scroll
+    let sticky_lines = if app_state.settings.editor.sticky_scroll {
+        let first_line = (-scroll_offsets.read().1 as f32 / manual_line_height).max(0.0) as usize;
+        let covered_lines = editor.enclosing_scopes(first_line, MAX_STICKY_LINES).len();
+        editor.enclosing_scopes(first_line + covered_lines, MAX_STICKY_LINES)
+    } else {
+        Vec::new()
+    };
+
+    // The code actions menu is shown in the viewport that requested it
+    let code_actions = editor_tab
+        .code_actions
+        .clone()
+        .filter(|_| editor_tab.is_viewport_focused(viewport));
+    let code_actions_offset_y = code_actions
+        .as_ref()
+        .map(|menu| (menu.line + 1) as f32 * manual_line_height + scroll_offsets.read().1 as f32)
+        .unwrap_or_default();
+    let gutter_width = font_size * 3.0;
+    let document_stats = editor_tab
+        .document_stats
+        .clone()
+        .filter(|_| editor_tab.is_viewport_focused(viewport));
+
+    let onselect_code_action = move |action: usize| {
+        {
+            let mut app_state = radio_app_state.write();
+            if let Some(menu) = &mut app_state
+                .editor_tab_mut(panel_index, tab_index)
+                .code_actions
+            {
+                menu.selected = action;
+            }
+        }
+        apply_selected_code_action(radio_app_state, panel_index, tab_index);
+    };
+
+    let onclose_document_stats = move |_| {
+        radio_app_state
+            .write_channel(Channel::follow_tab(panel_index, tab_index))
+            .editor_tab_mut(panel_index, tab_index)
+            .document_stats = None;
+    };
+
+    let onjump = move |line_index: usize| {
+        let mut app_state = radio_app_state.write();
+        let editor_tab = app_state.editor_tab_mut(panel_index, tab_index);
+        editor_tab.focus_viewport(viewport);
+        let editor = &mut editor_tab.editor;
+        let pos = editor.line_to_char(line_index);
+        editor.move_cursor_to(pos, false);
+        scroll_offsets.write().1 = -(line_index as f32 * manual_line_height) as i32;
+    };
+
+    // Zoom smoothly, following the wheel movement
+    let onzoom = move |wheel: f32| {
+        let step = (wheel / 500.0).clamp(-0.25, 0.25);
+        zoom_editor_tab(radio_app_state, panel_index, tab_index, |zoom| {
+            zoom * (1.0 + step)
+        });
+    };
 
     let onkeyup = move |e: KeyboardEvent| {
         let (is_panel_focused, is_editor_focused) = {
@@ -127,7 +451,10 @@ pub fn EditorUi(
             let panel = app_state.panel(panel_index);
             let is_panel_focused = app_state.focused_panel() == panel_index;
             let is_editor_focused = *app_state.focused_view() == EditorView::Panels
-                && panel.active_tab() == Some(tab_index);
+                && panel.active_tab() == Some(tab_index)
+                && app_state
+                    .editor_tab(panel_index, tab_index)
+                    .is_viewport_focused(viewport);
             (is_panel_focused, is_editor_focused)
         };
 
@@ -142,42 +469,130 @@ pub fn EditorUi(
             let panel = app_state.panel(panel_index);
             let is_panel_focused = app_state.focused_panel() == panel_index;
             let is_editor_focused = *app_state.focused_view() == EditorView::Panels
-                && panel.active_tab() == Some(tab_index);
+                && panel.active_tab() == Some(tab_index)
+                && app_state
+                    .editor_tab(panel_index, tab_index)
+                    .is_viewport_focused(viewport);
             (is_panel_focused, is_editor_focused)
         };
 
         if is_panel_focused && is_editor_focused {
-            let current_scroll = scroll_offsets.read().1;
-            let lines_jump = (manual_line_height * LINES_JUMP_ALT as f32).ceil() as i32;
-            let min_height = -(syntax_blocks_len as f32 * manual_line_height) as i32;
-            let max_height = 0; // TODO, this should be the height of the viewport
-
-            let events = match &e.key {
-                Key::ArrowUp if e.modifiers.contains(Modifiers::ALT) => {
-                    let jump = (current_scroll + lines_jump).clamp(min_height, max_height);
-                    scroll_offsets.write().1 = jump;
-                    (0..LINES_JUMP_ALT)
-                        .map(|_| EditableEvent::KeyDown(e.data.clone()))
-                        .collect::<Vec<EditableEvent>>()
+            let has_code_actions = radio_app_state
+                .read()
+                .editor_tab(panel_index, tab_index)
+                .code_actions
+                .is_some();
+
+            if has_code_actions {
+                match e.code {
+                    Code::ArrowUp | Code::ArrowDown => {
+                        let mut app_state = radio_app_state.write();
+                        let editor_tab = app_state.editor_tab_mut(panel_index, tab_index);
+                        if let Some(menu) = &mut editor_tab.code_actions {
+                            if e.code == Code::ArrowUp {
+                                menu.select_previous();
+                            } else {
+                                menu.select_next();
+                            }
+                        }
+                        return;
+                    }
+                    Code::Enter => {
+                        apply_selected_code_action(radio_app_state, panel_index, tab_index);
+                        return;
+                    }
+                    // Closed by the keyboard shortcuts handler
+                    Code::Escape => return,
+                    Code::ShiftLeft
+                    | Code::ShiftRight
+                    | Code::ControlLeft
+                    | Code::ControlRight
+                    | Code::AltLeft
+                    | Code::AltRight => {}
+                    // Typing closes the menu
+                    _ => {
+                        let mut app_state = radio_app_state.write();
+                        app_state
+                            .editor_tab_mut(panel_index, tab_index)
+                            .code_actions = None;
+                    }
                 }
-                Key::ArrowDown if e.modifiers.contains(Modifiers::ALT) => {
-                    let jump = (current_scroll - lines_jump).clamp(min_height, max_height);
-                    scroll_offsets.write().1 = jump;
-                    (0..LINES_JUMP_ALT)
-                        .map(|_| EditableEvent::KeyDown(e.data.clone()))
-                        .collect::<Vec<EditableEvent>>()
+            }
+
+            let is_quick_jumping = radio_app_state
+                .read()
+                .editor_tab(panel_index, tab_index)
+                .editor
+                .quick_jump
+                .is_some();
+
+            if is_quick_jumping {
+                // Shortcuts are left for the keyboard shortcuts handlers
+                if e.modifiers
+                    .intersects(Modifiers::ALT | Modifiers::CONTROL | Modifiers::META)
+                {
+                    return;
                 }
-                Key::ArrowDown | Key::ArrowUp if e.modifiers.contains(Modifiers::CONTROL) => (0
-                    ..LINES_JUMP_CONTROL)
-                    .map(|_| EditableEvent::KeyDown(e.data.clone()))
-                    .collect::<Vec<EditableEvent>>(),
-                _ => {
-                    vec![EditableEvent::KeyDown(e.data.clone())]
+
+                let first_line =
+                    (-scroll_offsets.peek().1 as f32 / manual_line_height).max(0.0) as usize;
+                let visible_lines_len = (viewport_height / manual_line_height).ceil() as usize + 1;
+                let visible_lines = first_line..first_line + visible_lines_len;
+
+                match &e.key {
+                    Key::Character(ch) => {
+                        if let Some(ch) = ch.chars().next() {
+                            let mut app_state = radio_app_state.write();
+                            let editor_tab = app_state.editor_tab_mut(panel_index, tab_index);
+                            editor_tab.editor.quick_jump_input(ch, visible_lines);
+                        }
+                    }
+                    Key::Shift | Key::Escape => {}
+                    _ => {
+                        let mut app_state = radio_app_state.write();
+                        let editor_tab = app_state.editor_tab_mut(panel_index, tab_index);
+                        editor_tab.editor.cancel_quick_jump();
+                    }
                 }
-            };
 
-            for event in events {
-                editable.process_event(&event);
+                return;
+            }
+
+            // Move a viewport's worth of lines
+            if matches!(e.code, Code::PageUp | Code::PageDown) {
+                let page_lines = (viewport_height / manual_line_height).floor().max(1.0) as isize;
+                let page_lines = if e.code == Code::PageUp {
+                    -page_lines
+                } else {
+                    page_lines
+                };
+
+                let mut app_state = radio_app_state.write();
+                let editor = &mut app_state.editor_tab_mut(panel_index, tab_index).editor;
+                let pos = editor.line_offset(page_lines);
+                editor.move_cursor_to(pos, e.modifiers.contains(Modifiers::SHIFT));
+
+                let max_scroll = (syntax_blocks_len as f32 * manual_line_height - viewport_height)
+                    .max(0.0) as i32;
+                let scroll =
+                    scroll_offsets.peek().1 - page_lines as i32 * manual_line_height as i32;
+                scroll_offsets.write().1 = scroll.clamp(-max_scroll, 0);
+
+                return;
+            }
+
+            editable.process_event(&EditableEvent::KeyDown(e.data));
+
+            let (autosave, path) = {
+                let app_state = radio_app_state.read();
+                let editor = &app_state.editor_tab(panel_index, tab_index).editor;
+                (app_state.settings.editor.autosave, editor.path().cloned())
+            };
+            if let Some(path) = path {
+                // Scratchpads are always saved while typing
+                if autosave == AutosaveMode::AfterDelay || is_scratchpad(&path) {
+                    autosave_debouncer.action(path);
+                }
             }
         }
     };
@@ -186,50 +601,93 @@ pub fn EditorUi(
         rect {
             width: "100%",
             height: "100%",
-            background: "rgb(40, 40, 40)",
-            if let Some((path, root_path)) = paths {
-                FilePath {
-                    path: path.clone(),
-                    root_path: root_path.clone(),
-                }
-            }
-            rect {
-                onkeydown,
-                onkeyup,
-                onglobalclick,
-                onclick,
-                cursor_reference,
-                EditorScrollView {
-                    offset_x: scroll_offsets.read().0,
-                    offset_y: scroll_offsets.read().1,
-                    onscroll,
-                    length: syntax_blocks_len,
-                    item_size: manual_line_height,
-                    builder_args: BuilderArgs {
+            reference: viewport_ref,
+            onkeydown,
+            onkeyup,
+            onglobalclick,
+            onclick,
+            cursor_reference,
+            if !sticky_lines.is_empty() {
+                rect {
+                    width: "0",
+                    height: "0",
+                    StickyScroll {
                         panel_index,
                         tab_index,
+                        lines: sticky_lines,
                         font_size,
                         line_height: manual_line_height,
-                        rope: editor.rope().clone(),
-                    },
-                    builder: move |i: usize, builder_args: &BuilderArgs| rsx!(
-                        EditorLine {
-                            key: "{i}",
-                            line_index: i,
-                            builder_args: builder_args.clone(),
-                            editable,
-                            hover_location,
-                            debouncer,
-                            lsp,
-                            cursor_coords,
-                        }
-                    )
+                        width: viewport_width,
+                        onjump
+                    }
                 }
             }
+            if let Some(menu) = code_actions {
+                rect {
+                    width: "0",
+                    height: "0",
+                    CodeActionsMenuView {
+                        menu,
+                        offset_x: gutter_width,
+                        offset_y: code_actions_offset_y,
+                        onselect: onselect_code_action
+                    }
+                }
+            }
+            if let Some(stats) = document_stats {
+                DocumentStatsPopover {
+                    stats,
+                    onclose: onclose_document_stats
+                }
+            }
+            EditorScrollView {
+                offset_x: scroll_offsets.read().0,
+                offset_y: scroll_offsets.read().1,
+                onscroll,
+                onzoom,
+                length: syntax_blocks_len,
+                item_size: manual_line_height,
+                builder_args: BuilderArgs {
+                    panel_index,
+                    tab_index,
+                    font_size,
+                    line_height: manual_line_height,
+                    rope: editor.rope().clone(),
+                },
+                builder: move |i: usize, builder_args: &BuilderArgs| rsx!(
+                    EditorLine {
+                        key: "{i}",
+                        line_index: i,
+                        builder_args: builder_args.clone(),
+                        editable,
+                        hover_location,
+                        document_highlights,
+                        debouncer,
+                        lsp,
+                        cursor_coords,
+                    }
+                )
+            }
         }
     )
 }
 
+/// Scroll position showing the item at `position`, with `margin` around it when possible.
+/// The current scroll is kept if the item is already visible.
+fn scroll_to_show(scroll: f32, position: f32, size: f32, margin: f32, viewport: f32) -> f32 {
+    let margin = margin.max(0.0);
+    let first_visible = position - margin;
+    let last_visible = position + size + margin - viewport;
+
+    if scroll > first_visible {
+        first_visible.max(0.0)
+    } else if scroll < last_visible {
+        last_visible
+    } else {
+        scroll
+    }
+}
+
 #[allow(non_snake_case)]
 #[component]
 fn FilePath(path: PathBuf, root_path: PathBuf) -> Element {
@@ -276,3 +734,68 @@ fn FilePath(path: PathBuf, root_path: PathBuf) -> Element {
         }
     )
 }
+
+/// Choices offered when saving a file that something else changed in the meantime.
+#[allow(non_snake_case)]
+#[component]
+fn SaveConflictBar(panel_index: usize, tab_index: usize) -> Element {
+    let mut radio_app_state = use_radio(Channel::follow_tab(panel_index, tab_index));
+
+    let oncompare = move |_| {
+        compare_editor_tab_with_saved(radio_app_state, panel_index, tab_index);
+    };
+
+    let onoverwrite = move |_| {
+        overwrite_editor_tab(radio_app_state, panel_index, tab_index);
+    };
+
+    let onreload = move |_| {
+        reload_editor_tab(radio_app_state, panel_index, tab_index);
+    };
+
+    let ondismiss = move |_| {
+        radio_app_state
+            .write_channel(Channel::follow_tab(panel_index, tab_index))
+            .editor_tab_mut(panel_index, tab_index)
+            .save_conflict = false;
+    };
+
+    rsx!(
+        rect {
+            width: "100%",
+            direction: "horizontal",
+            cross_align: "center",
+            padding: "4 10",
+            background: "rgb(85, 60, 25)",
+            label {
+                width: "fill",
+                color: "rgb(240, 220, 190)",
+                "The file was changed by something else, saving would overwrite those changes."
+            }
+            Button {
+                onpress: oncompare,
+                label {
+                    "Compare"
+                }
+            }
+            Button {
+                onpress: onoverwrite,
+                label {
+                    "Overwrite"
+                }
+            }
+            Button {
+                onpress: onreload,
+                label {
+                    "Reload"
+                }
+            }
+            Button {
+                onpress: ondismiss,
+                label {
+                    "✕"
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,170 @@
+use std::{fmt::Write, ops::Range};
+
+use freya::{hooks::TextEditor, prelude::spawn};
+
+use crate::{
+    notifications::notify,
+    parser::{SyntaxType, TextNode},
+    state::{NotificationLevel, RadioAppState},
+};
+
+use super::{EditorData, TabEditorUtils};
+
+/// Background of the exported code, the same one of the editor.
+const BACKGROUND_COLOR: &str = "rgb(40, 40, 40)";
+
+/// The selected lines, or all of them if there is no selection.
+fn exported_lines(editor: &EditorData) -> Range<usize> {
+    if editor.get_selection_range().is_some() {
+        editor.selected_lines()
+    } else {
+        0..editor.metrics.syntax_blocks.len()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Lines of code as a `<pre>` element colored like the editor, with inline styles
+/// so it keeps its colors wherever it's pasted.
+pub fn highlighted_html(
+    editor: &EditorData,
+    lines: Range<usize>,
+    font_size: f32,
+    rainbow_brackets: bool,
+) -> String {
+    let syntax_blocks = &editor.metrics.syntax_blocks;
+    let mut html = format!(
+        "<pre style=\"margin: 0; padding: 16px; background: {BACKGROUND_COLOR}; color: {}; font-family: 'Jetbrains Mono', monospace; font-size: {font_size}px; white-space: pre-wrap;\">",
+        SyntaxType::Unknown.color()
+    );
+
+    for line in lines.start..lines.end.min(syntax_blocks.len()) {
+        if line > lines.start {
+            html.push('\n');
+        }
+
+        for (syntax_type, text_node) in syntax_blocks.get_line(line) {
+            let text = match text_node {
+                TextNode::Range(range) => editor.rope.slice(range.clone()).to_string(),
+                // The whitespace marks are only drawn in the editor
+                TextNode::LineOfChars { len, .. } => " ".repeat(*len),
+            };
+            let text = text.trim_end_matches(['\n', '\r']);
+
+            if text.trim().is_empty() {
+                html.push_str(text);
+                continue;
+            }
+
+            let color = if rainbow_brackets {
+                syntax_type.rainbow_color()
+            } else {
+                syntax_type.color()
+            };
+            write!(
+                html,
+                "<span style=\"color: {color};\">{}</span>",
+                escape_html(text)
+            )
+            .ok();
+        }
+    }
+
+    html.push_str("</pre>");
+    html
+}
+
+/// Standalone HTML document with the given highlighted code, keeping its background when printed.
+pub fn html_document(title: &str, code: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<style>@media print {{ body {{ print-color-adjust: exact; -webkit-print-color-adjust: exact; }} }}</style>
+</head>
+<body style=\"margin: 0; background: {BACKGROUND_COLOR};\">
+{code}
+</body>
+</html>
+",
+        escape_html(title)
+    )
+}
+
+/// Title and highlighted code of the exported lines of an editor.
+fn editor_html(
+    radio_app_state: RadioAppState,
+    panel: usize,
+    tab: usize,
+) -> Option<(String, String)> {
+    let app_state = radio_app_state.read();
+    let editor = &app_state.panel(panel).tab(tab).as_text_editor()?.editor;
+    let (title, _) = editor.editor_type.title_and_id();
+    let settings = &app_state.settings.editor;
+    let html = highlighted_html(
+        editor,
+        exported_lines(editor),
+        settings.font_size,
+        settings.rainbow_brackets,
+    );
+    Some((title, html))
+}
+
+/// Save the highlighted code of an editor (the selected lines, or all of them)
+/// as a standalone HTML file, asking where.
+pub fn export_html(radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    let Some((title, html)) = editor_html(radio_app_state, panel, tab) else {
+        return;
+    };
+
+    spawn(async move {
+        let file = rfd::AsyncFileDialog::new()
+            .set_file_name(&format!("{title}.html"))
+            .add_filter("HTML", &["html"])
+            .save_file()
+            .await;
+        let Some(file) = file else {
+            return;
+        };
+
+        let path = file.path().to_owned();
+        match tokio::fs::write(&path, html_document(&title, &html)).await {
+            Ok(()) => notify(
+                radio_app_state,
+                NotificationLevel::Info,
+                format!("Exported {title} to {}", path.display()),
+                None,
+            ),
+            Err(err) => notify(
+                radio_app_state,
+                NotificationLevel::Error,
+                format!("Failed to export {title}: {err}"),
+                None,
+            ),
+        }
+    });
+}
+
+/// Copy the highlighted code of an editor (the selected lines, or all of them) as HTML.
+pub fn copy_html(radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    let Some((title, html)) = editor_html(radio_app_state, panel, tab) else {
+        return;
+    };
+
+    let mut clipboard = radio_app_state.read().clipboard;
+    if clipboard.set(html).is_ok() {
+        notify(
+            radio_app_state,
+            NotificationLevel::Info,
+            format!("Copied {title} as HTML"),
+            None,
+        );
+    }
+}
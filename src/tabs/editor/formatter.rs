@@ -0,0 +1,249 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use freya::prelude::spawn;
+use ropey::Rope;
+use tokio::{io::AsyncWriteExt, process::Command};
+
+use crate::{
+    environment::workspace_environment,
+    notifications::notify,
+    processes::ProcessKind,
+    state::{Channel, FormatterSettings, NotificationLevel, RadioAppState},
+};
+
+use super::{text_digest, AppStateEditorUtils, TabEditorUtils};
+
+/// Formatters taking longer than this are killed, so saving doesn't hang forever.
+const FORMATTER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Run an external formatter over the text, returning the formatted text.
+/// What it prints to stderr is notified, and the text is left as it is if it fails.
+async fn run_formatter(
+    mut radio_app_state: RadioAppState,
+    formatter: &FormatterSettings,
+    root_path: &Path,
+    text: String,
+) -> Option<String> {
+    let env = {
+        let app_state = radio_app_state.read();
+        workspace_environment(&app_state.settings, root_path)
+    };
+
+    let child = Command::new(&formatter.command)
+        .args(&formatter.args)
+        .current_dir(root_path)
+        .envs(env.iter().map(|variable| (&variable.name, &variable.value)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(err) => {
+            notify(
+                radio_app_state,
+                NotificationLevel::Error,
+                format!("Failed to run {}: {err}", formatter.command),
+                None,
+            );
+            return None;
+        }
+    };
+
+    let pid = child.id();
+    if let Some(pid) = pid {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .processes
+            .register(pid, formatter.command.clone(), ProcessKind::Formatter);
+    }
+
+    // Write while the output is read, big texts don't fit in the pipes
+    let is_empty = text.is_empty();
+    let stdin = child.stdin.take();
+    let writer = tokio::spawn(async move {
+        if let Some(mut stdin) = stdin {
+            stdin.write_all(text.as_bytes()).await.ok();
+        }
+    });
+    // The child is killed when dropped by the timeout
+    let output = tokio::time::timeout(FORMATTER_TIMEOUT, child.wait_with_output()).await;
+    writer.await.ok();
+
+    if let Some(pid) = pid {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .processes
+            .unregister(pid);
+    }
+
+    let output = match output {
+        Ok(Ok(output)) => output,
+        Err(_) => {
+            notify(
+                radio_app_state,
+                NotificationLevel::Error,
+                format!(
+                    "{} took more than {} seconds and was stopped",
+                    formatter.command,
+                    FORMATTER_TIMEOUT.as_secs()
+                ),
+                None,
+            );
+            return None;
+        }
+        Ok(Err(err)) => {
+            notify(
+                radio_app_state,
+                NotificationLevel::Error,
+                format!("Failed to run {}: {err}", formatter.command),
+                None,
+            );
+            return None;
+        }
+    };
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() {
+        let reason = if stderr.is_empty() {
+            output.status.to_string()
+        } else {
+            stderr
+        };
+        notify(
+            radio_app_state,
+            NotificationLevel::Error,
+            format!("{} failed: {reason}", formatter.command),
+            None,
+        );
+        return None;
+    }
+    if !stderr.is_empty() {
+        notify(
+            radio_app_state,
+            NotificationLevel::Warning,
+            format!("{}: {stderr}", formatter.command),
+            None,
+        );
+    }
+
+    match String::from_utf8(output.stdout) {
+        // Nothing printed is more likely a misconfigured formatter than an emptied file
+        Ok(formatted) if formatted.is_empty() && !is_empty => {
+            notify(
+                radio_app_state,
+                NotificationLevel::Warning,
+                format!("{} printed no text", formatter.command),
+                None,
+            );
+            None
+        }
+        Ok(formatted) => Some(formatted),
+        Err(_) => {
+            notify(
+                radio_app_state,
+                NotificationLevel::Error,
+                format!("{} printed invalid UTF-8", formatter.command),
+                None,
+            );
+            None
+        }
+    }
+}
+
+/// Replace the text of an editor tab with its formatted version, as an undoable edit.
+/// Returns the new text and its change in the history,
+/// unless nothing changed or the text was edited while formatting.
+async fn format_text(
+    mut radio_app_state: RadioAppState,
+    path: PathBuf,
+    formatter: FormatterSettings,
+    root_path: PathBuf,
+    rope: Rope,
+) -> Option<(Rope, usize)> {
+    let text = rope.to_string();
+    let formatted = run_formatter(radio_app_state, &formatter, &root_path, text.clone()).await?;
+    if formatted == text {
+        return None;
+    }
+
+    // The tab might have been moved or closed in the meantime
+    let (panel, tab) = radio_app_state.read().find_editor_tab(&path)?;
+    let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+    let editor_tab = app_state.editor_tab_mut(panel, tab);
+    if text_digest(&editor_tab.editor.rope) != text_digest(&rope) {
+        return None;
+    }
+    editor_tab.editor.replace_text(&formatted);
+    Some((
+        editor_tab.editor.rope.clone(),
+        editor_tab.editor.history.current_change(),
+    ))
+}
+
+/// Formatter and workspace of an editor tab, for the formatters enabled on save if `on_save` is set.
+pub fn editor_tab_formatter(
+    radio_app_state: RadioAppState,
+    panel: usize,
+    tab: usize,
+    on_save: bool,
+) -> Option<(FormatterSettings, PathBuf)> {
+    let app_state = radio_app_state.read();
+    let editor_tab = app_state.panel(panel).tab(tab).as_text_editor()?;
+    let (path, root_path) = editor_tab.editor.editor_type.paths()?;
+    let formatter = FormatterSettings::find(&app_state.settings.formatters, path)
+        .filter(|formatter| formatter.on_save || !on_save)?;
+    Some((formatter.clone(), root_path.clone()))
+}
+
+/// Format the text of an editor tab before saving it, returning the text to save
+/// and its change in the history, which are the given ones if it wasn't formatted.
+pub async fn format_before_saving(
+    radio_app_state: RadioAppState,
+    path: PathBuf,
+    formatter: FormatterSettings,
+    root_path: PathBuf,
+    rope: Rope,
+    change: usize,
+) -> (Rope, usize) {
+    format_text(radio_app_state, path, formatter, root_path, rope.clone())
+        .await
+        .unwrap_or((rope, change))
+}
+
+/// Format the text of an editor tab with the formatter of its language.
+pub fn format_editor_tab(radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    let (path, rope) = {
+        let app_state = radio_app_state.read();
+        let Some(editor_tab) = app_state.panel(panel).tab(tab).as_text_editor() else {
+            return;
+        };
+        let Some(path) = editor_tab.editor.path() else {
+            return;
+        };
+        if editor_tab.editor.read_only {
+            return;
+        }
+        (path.clone(), editor_tab.editor.rope.clone())
+    };
+
+    let Some((formatter, root_path)) = editor_tab_formatter(radio_app_state, panel, tab, false)
+    else {
+        notify(
+            radio_app_state,
+            NotificationLevel::Warning,
+            "There is no formatter for this file, add one to the `formatters` setting",
+            None,
+        );
+        return;
+    };
+
+    spawn(async move {
+        format_text(radio_app_state, path, formatter, root_path, rope).await;
+    });
+}
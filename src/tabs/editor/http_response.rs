@@ -0,0 +1,98 @@
+use freya::prelude::*;
+
+use super::HttpResponseState;
+
+#[allow(non_snake_case)]
+#[component]
+pub fn HttpResponseView(response: HttpResponseState, onclose: EventHandler<()>) -> Element {
+    let (title, status, status_color) = match &response {
+        HttpResponseState::Sending(request) => (
+            request.clone(),
+            "Sending...".to_string(),
+            "rgb(200, 200, 200)",
+        ),
+        HttpResponseState::Received(response) => {
+            let color = if response.status.starts_with(['4', '5']) {
+                "rgb(240, 110, 110)"
+            } else {
+                "rgb(120, 200, 120)"
+            };
+            (
+                response.request.clone(),
+                format!("{} · {}ms", response.status, response.elapsed.as_millis()),
+                color,
+            )
+        }
+        HttpResponseState::Failed(request, err) => {
+            (request.clone(), err.clone(), "rgb(240, 110, 110)")
+        }
+    };
+
+    rsx!(
+        rect {
+            width: "50%",
+            height: "100%",
+            background: "rgb(35, 35, 35)",
+            border: "1 solid rgb(50, 50, 50)",
+            rect {
+                width: "100%",
+                direction: "horizontal",
+                cross_align: "center",
+                padding: "8 10",
+                rect {
+                    width: "fill",
+                    label {
+                        color: "rgb(245, 245, 245)",
+                        max_lines: "1",
+                        text_overflow: "ellipsis",
+                        "{title}"
+                    }
+                    label {
+                        color: "{status_color}",
+                        "{status}"
+                    }
+                }
+                Button {
+                    onpress: move |_| onclose.call(()),
+                    label {
+                        "Close"
+                    }
+                }
+            }
+            if let HttpResponseState::Received(response) = response {
+                ScrollView {
+                    rect {
+                        width: "100%",
+                        padding: "0 10 10 10",
+                        for (i, (name, value)) in response.headers.iter().enumerate() {
+                            paragraph {
+                                key: "{i}",
+                                width: "100%",
+                                max_lines: "1",
+                                text_overflow: "ellipsis",
+                                font_family: "Jetbrains Mono",
+                                text {
+                                    color: "rgb(150, 180, 220)",
+                                    "{name}: "
+                                }
+                                text {
+                                    color: "rgb(190, 190, 190)",
+                                    "{value}"
+                                }
+                            }
+                        }
+                        rect {
+                            height: "10"
+                        }
+                        label {
+                            width: "100%",
+                            color: "rgb(245, 245, 245)",
+                            font_family: "Jetbrains Mono",
+                            "{response.body}"
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
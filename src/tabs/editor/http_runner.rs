@@ -0,0 +1,195 @@
+use std::time::{Duration, Instant};
+
+use freya::prelude::*;
+
+use crate::state::{Channel, RadioAppState};
+
+use super::AppStateEditorUtils;
+
+const METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// A request of a `.http` or `.rest` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequest {
+    /// Line of the method and URL.
+    pub line: usize,
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpRequest {
+    pub fn title(&self) -> String {
+        format!("{} {}", self.method, self.url)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub request: String,
+    pub status: String,
+    pub headers: Vec<(String, String)>,
+    /// Body of the response, pretty printed if it's JSON.
+    pub body: String,
+    pub elapsed: Duration,
+}
+
+/// Last request sent from a `.http` or `.rest` file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HttpResponseState {
+    Sending(String),
+    Received(HttpResponse),
+    Failed(String, String),
+}
+
+/// Whether a line starts a request, e.g `POST https://example.com/users HTTP/1.1`.
+pub fn is_request_line(line: &str) -> bool {
+    let mut words = line.split_whitespace();
+    words.next().is_some_and(|method| METHODS.contains(&method)) && words.next().is_some()
+}
+
+/// Find the requests of a text, separated by `###` lines.
+///
+/// Every request has a method line, then the headers until an empty line and then the body.
+pub fn parse_requests(rope: &Rope) -> Vec<HttpRequest> {
+    let mut requests = Vec::new();
+    let mut current: Option<HttpRequest> = None;
+    let mut in_body = false;
+
+    for (line_idx, line) in rope.lines().enumerate() {
+        let line = line.to_string();
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("###") {
+            requests.extend(current.take());
+            continue;
+        }
+
+        match &mut current {
+            None => {
+                if is_request_line(trimmed) {
+                    let mut words = trimmed.split_whitespace();
+                    current = Some(HttpRequest {
+                        line: line_idx,
+                        method: words.next().unwrap().to_string(),
+                        url: words.next().unwrap().to_string(),
+                        headers: Vec::new(),
+                        body: String::new(),
+                    });
+                    in_body = false;
+                }
+            }
+            Some(request) if !in_body => {
+                if trimmed.is_empty() {
+                    in_body = true;
+                } else if trimmed.starts_with('#') || trimmed.starts_with("//") {
+                    // Comments between the headers
+                } else if let Some((name, value)) = trimmed.split_once(':') {
+                    request
+                        .headers
+                        .push((name.trim().to_string(), value.trim().to_string()));
+                }
+            }
+            Some(request) => request.body.push_str(&line),
+        }
+    }
+
+    requests.extend(current);
+
+    for request in &mut requests {
+        request.body = request.body.trim_end().to_string();
+    }
+
+    requests
+}
+
+/// Pretty print JSON bodies, anything else is kept as it is.
+fn pretty_body(body: String) -> String {
+    serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or(body)
+}
+
+pub async fn send_request(request: HttpRequest) -> Result<HttpResponse, String> {
+    let method =
+        reqwest::Method::from_bytes(request.method.as_bytes()).map_err(|err| err.to_string())?;
+
+    let mut builder = reqwest::Client::new().request(method, &request.url);
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    if !request.body.is_empty() {
+        builder = builder.body(request.body.clone());
+    }
+
+    let start = Instant::now();
+    let response = builder.send().await.map_err(|err| err.to_string())?;
+
+    let status = response.status().to_string();
+    let headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect();
+    let body = response.text().await.map_err(|err| err.to_string())?;
+
+    Ok(HttpResponse {
+        request: request.title(),
+        status,
+        headers,
+        body: pretty_body(body),
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Send the request that starts at the given line of an editor, its response is shown next to the editor.
+pub fn run_request(
+    mut radio_app_state: RadioAppState,
+    panel_index: usize,
+    tab_index: usize,
+    line: usize,
+) {
+    let request = {
+        let app_state = radio_app_state.read();
+        let editor = &app_state.editor_tab(panel_index, tab_index).editor;
+        parse_requests(&editor.rope)
+            .into_iter()
+            .find(|request| request.line == line)
+            .zip(editor.path().cloned())
+    };
+    let Some((request, path)) = request else {
+        return;
+    };
+
+    radio_app_state
+        .write_channel(Channel::follow_tab(panel_index, tab_index))
+        .editor_tab_mut(panel_index, tab_index)
+        .http_response = Some(HttpResponseState::Sending(request.title()));
+
+    spawn(async move {
+        let title = request.title();
+        let response = match send_request(request).await {
+            Ok(response) => HttpResponseState::Received(response),
+            Err(err) => HttpResponseState::Failed(title, err),
+        };
+
+        // The tab might have been moved or closed in the meantime
+        let opened_tab = radio_app_state.read().find_editor_tab(&path);
+        if let Some((panel_index, tab_index)) = opened_tab {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel_index, tab_index));
+            app_state
+                .editor_tab_mut(panel_index, tab_index)
+                .http_response = Some(response);
+        }
+    });
+}
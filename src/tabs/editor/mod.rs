@@ -1,12 +1,37 @@
+mod backups;
+mod block_selection;
+mod code_actions;
+mod code_actions_menu;
 mod commands;
+mod document_stats;
+mod document_stats_popover;
 mod editor_data;
 mod editor_line;
 mod editor_tab;
 mod editor_ui;
+mod export;
+mod formatter;
 mod hover_box;
+mod http_response;
+mod http_runner;
+mod motion;
+mod numbers;
+mod persisted_history;
+mod quick_jump;
+mod sticky_scroll;
+mod todo;
 mod utils;
 
+pub use backups::*;
+pub use block_selection::*;
+pub use code_actions::*;
+pub use document_stats::*;
 pub use editor_data::*;
 pub use editor_line::*;
 pub use editor_tab::*;
+pub use http_runner::*;
+pub use motion::*;
+pub use quick_jump::*;
+pub use sticky_scroll::*;
 pub use utils::*;
+pub use valin_core::{history::*, indentation::*};
@@ -0,0 +1,76 @@
+use ropey::Rope;
+
+/// Cursor movement, used by the editor keys and available as commands
+/// so they can be bound to other keys.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Motion {
+    CharLeft,
+    CharRight,
+    LineUp,
+    LineDown,
+    WordLeft,
+    WordRight,
+    LineHome,
+    LineEnd,
+    TextStart,
+    TextEnd,
+    MatchingBracket,
+}
+
+impl Motion {
+    pub const ALL: [Self; 11] = [
+        Self::CharLeft,
+        Self::CharRight,
+        Self::LineUp,
+        Self::LineDown,
+        Self::WordLeft,
+        Self::WordRight,
+        Self::LineHome,
+        Self::LineEnd,
+        Self::TextStart,
+        Self::TextEnd,
+        Self::MatchingBracket,
+    ];
+}
+
+/// Position of the bracket pairing the one around the given position.
+/// Before an opening bracket it's the position after its closing bracket, and the other way around.
+pub fn matching_bracket(rope: &Rope, pos: usize, brackets: &[(char, char)]) -> Option<usize> {
+    let char_after = (pos < rope.len_chars()).then(|| rope.char(pos));
+    let char_before = (pos > 0).then(|| rope.char(pos - 1));
+
+    if let Some(&(open, close)) =
+        char_after.and_then(|ch| brackets.iter().find(|(open, _)| *open == ch))
+    {
+        let mut depth = 0;
+        for (idx, ch) in rope.chars_at(pos).enumerate() {
+            if ch == open {
+                depth += 1;
+            } else if ch == close {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(pos + idx + 1);
+                }
+            }
+        }
+    } else if let Some(&(open, close)) =
+        char_before.and_then(|ch| brackets.iter().find(|(_, close)| *close == ch))
+    {
+        let mut depth = 0;
+        let mut chars = rope.chars_at(pos);
+        let mut idx = pos;
+        while let Some(ch) = chars.prev() {
+            idx -= 1;
+            if ch == close {
+                depth += 1;
+            } else if ch == open {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+        }
+    }
+
+    None
+}
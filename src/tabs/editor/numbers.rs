@@ -0,0 +1,133 @@
+use std::ops::Range;
+
+/// A number literal found in a line.
+struct NumberLiteral {
+    /// Chars range of the literal, including its sign and prefix.
+    range: Range<usize>,
+    radix: u32,
+    /// Prefix of non-decimal literals as it was typed, e.g `0x`.
+    prefix: String,
+    digits: String,
+    negative: bool,
+}
+
+fn is_digit(ch: char, radix: u32) -> bool {
+    ch.is_digit(radix) || ch == '_'
+}
+
+/// Find the number literals of a line.
+fn find_literals(chars: &[char]) -> Vec<NumberLiteral> {
+    let mut literals = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        // Numbers are not part of identifiers, e.g `u8`
+        let is_word_start = i == 0 || !(chars[i - 1].is_alphanumeric() || chars[i - 1] == '_');
+        if !chars[i].is_ascii_digit() || !is_word_start {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        // A minus is a sign only if it isn't a subtraction, e.g `x-1`
+        let negative = start > 0
+            && chars[start - 1] == '-'
+            && (start == 1 || !chars[start - 2].is_alphanumeric());
+
+        let radix = match (chars[i], chars.get(i + 1)) {
+            ('0', Some('x' | 'X')) => 16,
+            ('0', Some('b' | 'B')) => 2,
+            ('0', Some('o' | 'O')) => 8,
+            _ => 10,
+        };
+
+        let prefix = if radix != 10 {
+            i += 2;
+            chars[start..i].iter().collect()
+        } else {
+            String::new()
+        };
+
+        let digits_start = i;
+        while i < chars.len() && is_digit(chars[i], radix) {
+            i += 1;
+        }
+
+        // Decimals
+        if radix == 10
+            && chars.get(i) == Some(&'.')
+            && chars.get(i + 1).map(char::is_ascii_digit) == Some(true)
+        {
+            i += 1;
+            while i < chars.len() && is_digit(chars[i], 10) {
+                i += 1;
+            }
+        }
+
+        if i == digits_start {
+            continue;
+        }
+
+        literals.push(NumberLiteral {
+            range: if negative { start - 1 } else { start }..i,
+            radix,
+            prefix,
+            digits: chars[digits_start..i].iter().collect(),
+            negative,
+        });
+    }
+
+    literals
+}
+
+impl NumberLiteral {
+    fn add(&self, delta: i64) -> Option<String> {
+        let digits = self.digits.replace('_', "");
+
+        if self.radix == 10 && digits.contains('.') {
+            let decimals = digits.len() - digits.find('.')? - 1;
+            let mut value = digits.parse::<f64>().ok()?;
+            if self.negative {
+                value = -value;
+            }
+            return Some(format!("{:.*}", decimals, value + delta as f64));
+        }
+
+        let mut value = i128::from_str_radix(&digits, self.radix).ok()?;
+        if self.negative {
+            value = -value;
+        }
+        let value = value + delta as i128;
+
+        if self.radix == 10 {
+            return Some(value.to_string());
+        }
+
+        // Keep the width and the letters case of the literal
+        let sign = if value < 0 { "-" } else { "" };
+        let width = digits.len();
+        let abs = value.unsigned_abs();
+        let mut formatted = match self.radix {
+            16 => format!("{abs:0width$x}"),
+            8 => format!("{abs:0width$o}"),
+            _ => format!("{abs:0width$b}"),
+        };
+        if digits.chars().any(|ch| ch.is_ascii_uppercase()) {
+            formatted = formatted.to_uppercase();
+        }
+
+        Some(format!("{sign}{}{formatted}", self.prefix))
+    }
+}
+
+/// Add `delta` to the number under the column, or to the next one in the line.
+/// Returns the chars range of the number in the line and its new text.
+pub fn increment_number(line: &str, col: usize, delta: i64) -> Option<(Range<usize>, String)> {
+    let chars = line.chars().collect::<Vec<char>>();
+    let literal = find_literals(&chars)
+        .into_iter()
+        .find(|literal| literal.range.end > col)?;
+
+    let text = literal.add(delta)?;
+    Some((literal.range, text))
+}
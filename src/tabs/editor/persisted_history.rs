@@ -0,0 +1,62 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::{Path, PathBuf},
+};
+
+use ropey::Rope;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{settings::data_path, utils::stable_hash};
+
+use super::EditorHistory;
+
+/// An undo history stored on disk, only valid for the content it was stored with.
+#[derive(Serialize, Deserialize)]
+struct PersistedHistory {
+    content_hash: u64,
+    history: EditorHistory,
+}
+
+/// Hash of a path, the same across builds so it can name the files stored for it.
+pub(super) fn path_hash(path: &Path) -> u64 {
+    stable_hash([path.as_os_str().as_encoded_bytes()])
+}
+
+fn content_hash(rope: &Rope) -> u64 {
+    stable_hash(rope.chunks().map(str::as_bytes))
+}
+
+fn history_path(file_path: &Path) -> Option<PathBuf> {
+    let history_path = data_path()?
+        .join("undo")
+        .join(format!("{:x}.json", path_hash(file_path)));
+
+    Some(history_path)
+}
+
+/// Store the undo history of a file, `rope` must be the content of the file in disk.
+pub fn save_history(file_path: &Path, rope: &Rope, history: &EditorHistory) -> Option<()> {
+    let history_path = history_path(file_path)?;
+    create_dir_all(history_path.parent()?).ok()?;
+
+    let persisted_history = PersistedHistory {
+        content_hash: content_hash(rope),
+        history: history.clone(),
+    };
+    let content = serde_json::to_string(&persisted_history).ok()?;
+    write(history_path, content).ok()
+}
+
+/// Load the undo history of a file, unless the file was modified by something else since then.
+pub fn load_history(file_path: &Path, rope: &Rope) -> Option<EditorHistory> {
+    let content = read_to_string(history_path(file_path)?).ok()?;
+    let persisted_history: PersistedHistory = serde_json::from_str(&content).ok()?;
+
+    if persisted_history.content_hash != content_hash(rope) {
+        info!("Discarded the undo history of {file_path:?} as the file changed.");
+        return None;
+    }
+
+    Some(persisted_history.history)
+}
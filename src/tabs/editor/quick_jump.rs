@@ -0,0 +1,128 @@
+use std::ops::Range;
+
+use ropey::Rope;
+
+/// Characters used to build the hints, sorted by how easy they are to type.
+const HINT_CHARS: &[char] = &[
+    'a', 's', 'd', 'f', 'j', 'k', 'l', 'g', 'h', 'q', 'w', 'e', 'r', 'u', 'i', 'o', 'p', 'z', 'x',
+    'c', 'v', 'b', 'n', 'm', 't', 'y',
+];
+
+/// How many characters must be typed before the occurrences are labeled.
+const PATTERN_LEN: usize = 2;
+
+pub enum QuickJumpEvent {
+    Pending,
+    Jump(usize),
+    Cancel,
+}
+
+/// Jump to a visible occurrence of a characters pair by typing its hint.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct QuickJump {
+    pattern: Vec<char>,
+    typed_hint: String,
+    /// Chars position of every labeled occurrence along with its hint.
+    targets: Vec<(usize, String)>,
+}
+
+fn hint_label(index: usize, len: usize) -> String {
+    if len == 1 {
+        HINT_CHARS[index].to_string()
+    } else {
+        let n = HINT_CHARS.len();
+        format!("{}{}", HINT_CHARS[index / n], HINT_CHARS[index % n])
+    }
+}
+
+fn lowercase_char(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+impl QuickJump {
+    pub fn pattern(&self) -> String {
+        self.pattern.iter().collect()
+    }
+
+    /// Process a typed character.
+    pub fn input(&mut self, ch: char, rope: &Rope, visible_lines: Range<usize>) -> QuickJumpEvent {
+        if self.pattern.len() < PATTERN_LEN {
+            self.pattern.push(lowercase_char(ch));
+
+            if self.pattern.len() == PATTERN_LEN {
+                self.find_targets(rope, visible_lines);
+
+                return match self.targets.len() {
+                    0 => QuickJumpEvent::Cancel,
+                    1 => QuickJumpEvent::Jump(self.targets[0].0),
+                    _ => QuickJumpEvent::Pending,
+                };
+            }
+
+            return QuickJumpEvent::Pending;
+        }
+
+        self.typed_hint.push(lowercase_char(ch));
+
+        let mut candidates = self
+            .targets
+            .iter()
+            .filter(|(_, hint)| hint.starts_with(&self.typed_hint));
+
+        match (candidates.next(), candidates.next()) {
+            (None, _) => QuickJumpEvent::Cancel,
+            (Some((pos, hint)), None) if *hint == self.typed_hint => QuickJumpEvent::Jump(*pos),
+            _ => QuickJumpEvent::Pending,
+        }
+    }
+
+    fn find_targets(&mut self, rope: &Rope, visible_lines: Range<usize>) {
+        let mut positions = Vec::new();
+
+        for line_index in visible_lines {
+            let Some(line) = rope.get_line(line_index) else {
+                break;
+            };
+            let line_start = rope.line_to_char(line_index);
+            let chars = line.chars().map(lowercase_char).collect::<Vec<char>>();
+
+            let mut i = 0;
+            while i + PATTERN_LEN <= chars.len() {
+                if chars[i..i + PATTERN_LEN] == self.pattern[..] {
+                    positions.push(line_start + i);
+                    i += PATTERN_LEN;
+                } else {
+                    i += 1;
+                }
+            }
+        }
+
+        positions.truncate(HINT_CHARS.len().pow(2));
+
+        let hint_len = if positions.len() <= HINT_CHARS.len() {
+            1
+        } else {
+            2
+        };
+
+        self.targets = positions
+            .into_iter()
+            .enumerate()
+            .map(|(i, pos)| (pos, hint_label(i, hint_len)))
+            .collect();
+    }
+
+    /// Get the hints (offset in the line and the part left to type) that should be shown in a line.
+    pub fn line_hints(&self, line_chars: Range<usize>) -> Vec<(usize, String)> {
+        self.targets
+            .iter()
+            .filter(|(pos, hint)| line_chars.contains(pos) && hint.starts_with(&self.typed_hint))
+            .map(|(pos, hint)| {
+                (
+                    pos - line_chars.start,
+                    hint[self.typed_hint.len()..].to_owned(),
+                )
+            })
+            .collect()
+    }
+}
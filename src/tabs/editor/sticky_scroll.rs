@@ -0,0 +1,78 @@
+use dioxus_radio::hooks::use_radio;
+use freya::prelude::*;
+
+use crate::parser::TextNode;
+use crate::state::Channel;
+use crate::tabs::editor::AppStateEditorUtils;
+
+/// How many scope headers can be pinned at once.
+pub const MAX_STICKY_LINES: usize = 5;
+
+/// Headers of the scopes enclosing the first visible line, pinned over the editor.
+#[allow(non_snake_case)]
+#[component]
+pub fn StickyScroll(
+    panel_index: usize,
+    tab_index: usize,
+    lines: Vec<usize>,
+    font_size: f32,
+    line_height: f32,
+    width: f32,
+    onjump: EventHandler<usize>,
+) -> Element {
+    let radio_app_state = use_radio(Channel::follow_tab(panel_index, tab_index));
+    let app_state = radio_app_state.read();
+    let editor = &app_state.editor_tab(panel_index, tab_index).editor;
+    let rope = editor.rope();
+    let font_family = app_state.settings.editor.rendered_font_family();
+    let gutter_width = font_size * 3.0;
+
+    rsx!(
+        rect {
+            width: "{width}",
+            background: "rgb(40, 40, 40)",
+            layer: "-40",
+            shadow: "0 4 6 0 rgb(0, 0, 0, 60)",
+            {lines.into_iter().map(|line_index| {
+                let line = editor.metrics.syntax_blocks.get_line(line_index);
+                rsx!(
+                    rect {
+                        key: "{line_index}",
+                        width: "100%",
+                        height: "{line_height}",
+                        direction: "horizontal",
+                        cross_align: "center",
+                        onclick: move |_| onjump.call(line_index),
+                        label {
+                            width: "{gutter_width}",
+                            text_align: "center",
+                            font_size: "{font_size}",
+                            color: "rgb(135, 135, 135)",
+                            "{line_index + 1} "
+                        }
+                        paragraph {
+                            width: "fill",
+                            max_lines: "1",
+                            font_size: "{font_size}",
+                            font_family: "{font_family}",
+                            {line.iter().enumerate().map(|(i, (syntax_type, text))| {
+                                let text = match text {
+                                    TextNode::Range(word_pos) => rope.slice(word_pos.clone()).to_string(),
+                                    TextNode::LineOfChars { len, char } => format!("{char}").repeat(*len),
+                                };
+
+                                rsx!(
+                                    text {
+                                        key: "{i}",
+                                        color: "{syntax_type.color()}",
+                                        "{text}"
+                                    }
+                                )
+                            })}
+                        }
+                    }
+                )
+            })}
+        }
+    )
+}
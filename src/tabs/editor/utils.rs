@@ -1,13 +1,21 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use freya::prelude::spawn;
 use ropey::Rope;
+use tracing::info;
 
 use crate::{
-    fs::FSTransport,
-    state::{AppState, Panel, PanelTab},
+    fs::{FSTransport, TextFormat},
+    lsp::LspConfig,
+    notifications::notify,
+    state::{AppState, Channel, NotificationLevel, Panel, PanelTab, RadioAppState},
+    tabs::diff::DiffTab,
 };
 
-use super::EditorTab;
+use super::{
+    formatter::{editor_tab_formatter, format_before_saving},
+    remove_backup, text_digest, EditorTab,
+};
 
 pub trait AppStateEditorUtils {
     fn editor_tab(&self, panel: usize, editor_id: usize) -> &EditorTab;
@@ -16,6 +24,8 @@ pub trait AppStateEditorUtils {
 
     fn try_editor_tab_mut(&mut self, panel: usize, editor_id: usize) -> Option<&mut EditorTab>;
 
+    fn find_editor_tab(&self, path: &Path) -> Option<(usize, usize)>;
+
     fn editor_tab_data(
         &self,
         panel: usize,
@@ -41,6 +51,23 @@ impl AppStateEditorUtils for AppState {
             .as_text_editor_mut()
     }
 
+    fn find_editor_tab(&self, path: &Path) -> Option<(usize, usize)> {
+        self.panels()
+            .iter()
+            .enumerate()
+            .find_map(|(panel_index, panel)| {
+                panel
+                    .tabs()
+                    .iter()
+                    .enumerate()
+                    .find_map(|(tab_index, tab)| {
+                        let editor_tab = tab.as_text_editor()?;
+                        (editor_tab.editor.path().map(|p| p.as_path()) == Some(path))
+                            .then_some((panel_index, tab_index))
+                    })
+            })
+    }
+
     fn editor_tab_data(
         &self,
         panel: usize,
@@ -48,7 +75,7 @@ impl AppStateEditorUtils for AppState {
     ) -> Option<(Option<PathBuf>, Rope, FSTransport)> {
         let panel: &Panel = self.panel(panel);
         let editor = panel.tab(editor_id).as_text_editor();
-        editor.map(|EditorTab { editor: data }| {
+        editor.map(|EditorTab { editor: data, .. }| {
             (
                 data.path().cloned(),
                 data.rope.clone(),
@@ -73,3 +100,314 @@ impl TabEditorUtils for Box<dyn PanelTab> {
         self.as_any_mut().downcast_mut()
     }
 }
+
+/// Decode the content of a file like the editors do, binary files are decoded lossily.
+fn decode_text(bytes: &[u8]) -> (String, TextFormat) {
+    TextFormat::decode(bytes).unwrap_or_else(|| {
+        (
+            String::from_utf8_lossy(bytes).into_owned(),
+            TextFormat::default(),
+        )
+    })
+}
+
+/// Write the content of an editor tab to its file, unless something else changed
+/// the file since it was read or saved, in which case the editor asks what to do.
+pub fn save_editor_tab(radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    write_editor_tab(radio_app_state, panel, tab, false);
+}
+
+/// Write the content of an editor tab to its file, even if something else changed it.
+pub fn overwrite_editor_tab(radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    write_editor_tab(radio_app_state, panel, tab, true);
+}
+
+fn write_editor_tab(mut radio_app_state: RadioAppState, panel: usize, tab: usize, overwrite: bool) {
+    // What is saved is the text at this change, it can keep being edited in the meantime
+    let (editor_data, change, format, saved_digest, atomic_save) = {
+        let app_state = radio_app_state.read();
+        let Some(editor_tab) = app_state.panel(panel).tab(tab).as_text_editor() else {
+            return;
+        };
+        if editor_tab.editor.read_only {
+            return;
+        }
+        (
+            app_state.editor_tab_data(panel, tab),
+            editor_tab.editor.history.current_change(),
+            editor_tab.editor.format,
+            editor_tab.editor.saved_digest,
+            app_state.settings.files.atomic_save,
+        )
+    };
+    let formatter = editor_tab_formatter(radio_app_state, panel, tab, true);
+
+    if let Some((Some(file_path), rope, transport)) = editor_data {
+        spawn(async move {
+            if !overwrite {
+                // Files removed in the meantime are simply created again
+                let disk_digest = transport
+                    .read(&file_path)
+                    .await
+                    .ok()
+                    .map(|bytes| text_digest(&Rope::from(decode_text(&bytes).0)));
+                if disk_digest.is_some_and(|disk_digest| disk_digest != saved_digest) {
+                    info!("Not saving {file_path:?} as it was changed by something else.");
+                    // The tab might have been moved or closed in the meantime
+                    let opened_tab = radio_app_state.read().find_editor_tab(&file_path);
+                    if let Some((panel, tab)) = opened_tab {
+                        let mut app_state =
+                            radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+                        app_state.editor_tab_mut(panel, tab).save_conflict = true;
+                    }
+                    return;
+                }
+            }
+
+            // The formatted text is the one saved
+            let (rope, change) = match formatter {
+                Some((formatter, root_path)) => {
+                    format_before_saving(
+                        radio_app_state,
+                        file_path.clone(),
+                        formatter,
+                        root_path,
+                        rope,
+                        change,
+                    )
+                    .await
+                }
+                None => (rope, change),
+            };
+
+            let content = format.encode(&rope.to_string());
+            let result = if atomic_save {
+                transport.write_atomic(&file_path, &content).await
+            } else {
+                transport.write(&file_path, &content).await
+            };
+            if let Err(err) = result {
+                let file_name = file_path.file_name().unwrap().to_string_lossy();
+                notify(
+                    radio_app_state,
+                    NotificationLevel::Error,
+                    format!("Failed to save {file_name}: {err}"),
+                    None,
+                );
+                return;
+            }
+
+            // The tab might have been moved or closed in the meantime
+            let opened_tab = radio_app_state.read().find_editor_tab(&file_path);
+            let Some((panel, tab)) = opened_tab else {
+                return;
+            };
+            let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+            let persistent_undo = app_state
+                .settings
+                .editor
+                .persistent_undo
+                .then_some(app_state.settings.editor.persistent_undo_limit);
+            let editor_tab = app_state.editor_tab_mut(panel, tab);
+            editor_tab.editor.mark_change_as_saved(change, format);
+            editor_tab.editor.saved_digest = text_digest(&rope);
+            editor_tab.save_conflict = false;
+            remove_backup(&file_path);
+
+            if let Some(limit) = persistent_undo {
+                editor_tab.editor.persist_history(limit);
+            }
+
+            // Keep the symbols of the saved file up to date, if it's from an opened folder
+            let root_path = editor_tab
+                .editor
+                .editor_type
+                .paths()
+                .map(|(_, root_path)| root_path.clone())
+                .filter(|root_path| {
+                    app_state
+                        .file_explorer_folders
+                        .iter()
+                        .any(|folder| folder.path() == root_path)
+                });
+            if let Some(root_path) = root_path {
+                app_state
+                    .symbol_index
+                    .update_file(file_path, root_path, &rope);
+            }
+        });
+    }
+}
+
+/// Replace the content of an editor tab with the one of its file, as an undoable edit.
+pub fn reload_editor_tab(mut radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    let editor_data = radio_app_state.read().editor_tab_data(panel, tab);
+
+    if let Some((Some(file_path), _, transport)) = editor_data {
+        spawn(async move {
+            let bytes = match transport.read(&file_path).await {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    let file_name = file_path.file_name().unwrap().to_string_lossy();
+                    notify(
+                        radio_app_state,
+                        NotificationLevel::Error,
+                        format!("Failed to reload {file_name}: {err}"),
+                        None,
+                    );
+                    return;
+                }
+            };
+            let (text, format) = decode_text(&bytes);
+
+            // The tab might have been moved or closed in the meantime
+            let opened_tab = radio_app_state.read().find_editor_tab(&file_path);
+            let Some((panel, tab)) = opened_tab else {
+                return;
+            };
+            let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+            let editor_tab = app_state.editor_tab_mut(panel, tab);
+            let editor = &mut editor_tab.editor;
+            editor.replace_text(&text);
+            editor.format = format;
+            editor.mark_as_saved();
+            editor.saved_digest = text_digest(&editor.rope);
+            editor_tab.save_conflict = false;
+        });
+    }
+}
+
+/// Open a diff between the content of an editor tab and the one of its file.
+pub fn compare_editor_tab_with_saved(mut radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    let (editor_data, format) = {
+        let app_state = radio_app_state.read();
+        (
+            app_state.editor_tab_data(panel, tab),
+            app_state.editor_tab(panel, tab).editor.format,
+        )
+    };
+
+    if let Some((Some(file_path), rope, transport)) = editor_data {
+        spawn(async move {
+            let saved = match transport.read(&file_path).await {
+                Ok(saved) => saved,
+                Err(err) => {
+                    let file_name = file_path.file_name().unwrap().to_string_lossy();
+                    notify(
+                        radio_app_state,
+                        NotificationLevel::Error,
+                        format!("Failed to read {file_name} to compare it: {err}"),
+                        None,
+                    );
+                    return;
+                }
+            };
+            // Both texts use their own line endings again, so the diff can tell them apart
+            let (saved, saved_format) = decode_text(&saved);
+            let saved = saved_format.line_ending.apply(&saved);
+            let unsaved = rope.to_string();
+            let unsaved = format.line_ending.apply(&unsaved);
+            let file_name = file_path.file_name().unwrap().to_string_lossy();
+            let mut app_state = radio_app_state.write_channel(Channel::Global);
+            DiffTab::open_with(
+                &mut app_state,
+                format!("diff-saved:{}", file_path.display()),
+                format!("{file_name} ↔ Saved"),
+                (format!("{file_name} (Saved)"), &saved),
+                (format!("{file_name} (Unsaved)"), &unsaved),
+            );
+        });
+    }
+}
+
+/// Save the editor tab of the given file, if it has unsaved changes.
+pub fn autosave_editor_tab(radio_app_state: RadioAppState, path: &Path) {
+    let editor_tab = {
+        let app_state = radio_app_state.read();
+        app_state
+            .find_editor_tab(path)
+            .filter(|(panel, tab)| app_state.editor_tab(*panel, *tab).editor.is_edited())
+    };
+
+    if let Some((panel, tab)) = editor_tab {
+        save_editor_tab(radio_app_state, panel, tab);
+    }
+}
+
+/// Zoom an editor tab alone, `zoom` gets its current zoom and returns the new one.
+pub fn zoom_editor_tab(
+    mut radio_app_state: RadioAppState,
+    panel: usize,
+    tab: usize,
+    zoom: impl FnOnce(f32) -> f32,
+) {
+    let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+    let font_size = app_state.font_size();
+    let font_family = app_state.settings.editor.rendered_font_family().to_string();
+    let font_collection = app_state.font_collection.clone();
+    if let Some(editor_tab) = app_state.try_editor_tab_mut(panel, tab) {
+        let zoom = zoom(editor_tab.zoom);
+        editor_tab.set_zoom(zoom, font_size, &font_family, &font_collection);
+    }
+}
+
+/// Turn the language server off (or on again) for an editor tab.
+pub fn toggle_editor_tab_lsp(mut radio_app_state: RadioAppState, panel: usize, tab: usize) {
+    let mut app_state = radio_app_state.write_channel(Channel::Global);
+    update_lsp_documents(&mut app_state, |app_state| {
+        let editor_tab = app_state.editor_tab_mut(panel, tab);
+        editor_tab.lsp_disabled = !editor_tab.lsp_disabled;
+    });
+}
+
+/// Turn the language server off (or on again) for every file of a workspace.
+pub fn toggle_workspace_lsp(mut radio_app_state: RadioAppState, root_path: PathBuf) {
+    let mut app_state = radio_app_state.write_channel(Channel::Global);
+    update_lsp_documents(&mut app_state, |app_state| {
+        if !app_state.lsp_disabled_workspaces.remove(&root_path) {
+            app_state.lsp_disabled_workspaces.insert(root_path);
+        }
+    });
+}
+
+/// Close the documents of the editor tabs whose language server was turned off by `toggle`,
+/// and open again the ones turned on, so it only hears about the files that use it.
+fn update_lsp_documents(app_state: &mut AppState, toggle: impl FnOnce(&mut AppState)) {
+    let lsp_disabled_tabs = |app_state: &AppState| {
+        app_state
+            .panels()
+            .iter()
+            .flat_map(|panel| panel.tabs())
+            .map(|tab| {
+                tab.as_text_editor()
+                    .map(|editor_tab| editor_tab.is_lsp_disabled(app_state))
+            })
+            .collect::<Vec<Option<bool>>>()
+    };
+
+    let was_lsp_disabled = lsp_disabled_tabs(app_state);
+    toggle(app_state);
+
+    let tabs = app_state.panels().iter().flat_map(|panel| panel.tabs());
+    for (tab, was_lsp_disabled) in tabs.zip(was_lsp_disabled) {
+        let Some(editor_tab) = tab.as_text_editor() else {
+            continue;
+        };
+        let is_lsp_disabled = editor_tab.is_lsp_disabled(app_state);
+        if Some(is_lsp_disabled) == was_lsp_disabled {
+            continue;
+        }
+
+        let client = LspConfig::new(editor_tab.editor.editor_type.clone())
+            .and_then(|lsp_config| app_state.lsp(&lsp_config).cloned());
+        let (Some(mut client), Some(uri)) = (client, editor_tab.editor.uri()) else {
+            continue;
+        };
+
+        if is_lsp_disabled {
+            client.close_file(uri);
+        } else {
+            client.open_file(uri, editor_tab.editor.text());
+        }
+    }
+}
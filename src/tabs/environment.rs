@@ -0,0 +1,130 @@
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::{
+    environment::workspace_environment,
+    state::{AppState, Channel, PanelTab, PanelTabData, TabProps},
+};
+
+/// Lists the variables of the env files applied to the processes of every opened folder.
+pub struct EnvironmentTab;
+
+impl PanelTab for EnvironmentTab {
+    fn get_data(&self) -> PanelTabData {
+        PanelTabData {
+            id: "environment".to_string(),
+            title: "Environment".to_string(),
+            edited: false,
+            preview: false,
+            read_only: true,
+        }
+    }
+
+    fn render(&self) -> fn(TabProps) -> Element {
+        EnvironmentVariables
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl EnvironmentTab {
+    pub fn open_with(app_state: &mut AppState) {
+        app_state.push_tab(Self, app_state.focused_panel, true);
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn EnvironmentVariables(_: TabProps) -> Element {
+    // Variables change with the settings and the opened folders
+    let radio_app_state = use_radio::<AppState, Channel>(Channel::Global);
+
+    let app_state = radio_app_state.read();
+    let settings = &app_state.settings;
+
+    let workspaces = app_state
+        .file_explorer_folders
+        .iter()
+        .map(|folder| {
+            let root_path = folder.path();
+            (
+                root_path.to_string_lossy().to_string(),
+                workspace_environment(settings, root_path)
+                    .into_iter()
+                    .map(|variable| {
+                        let file = variable.path.to_string_lossy().to_string();
+                        (variable.name, variable.value, file)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let env_files = settings.environment.env_files.join(", ");
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            background: "rgb(35, 35, 35)",
+            padding: "20",
+            if !settings.environment.load_env_files {
+                label {
+                    color: "rgb(200, 200, 200)",
+                    "Env files are not loaded, enable `environment.load_env_files` in the settings."
+                }
+            } else if workspaces.is_empty() {
+                label {
+                    color: "rgb(200, 200, 200)",
+                    "Open a folder to load its env files ({env_files})."
+                }
+            } else {
+                ScrollView {
+                    for (root_path, variables) in workspaces {
+                        rect {
+                            key: "{root_path}",
+                            width: "100%",
+                            padding: "0 0 16 0",
+                            label {
+                                font_weight: "bold",
+                                "{root_path}"
+                            }
+                            if variables.is_empty() {
+                                label {
+                                    color: "rgb(150, 150, 150)",
+                                    "No variables found in {env_files}"
+                                }
+                            }
+                            for (name, value, file) in variables {
+                                paragraph {
+                                    key: "{name}",
+                                    width: "100%",
+                                    max_lines: "1",
+                                    text_overflow: "ellipsis",
+                                    font_family: "Jetbrains Mono",
+                                    text {
+                                        color: "rgb(150, 180, 220)",
+                                        "{name}"
+                                    }
+                                    text {
+                                        color: "rgb(235, 235, 235)",
+                                        "={value}"
+                                    }
+                                    text {
+                                        color: "rgb(120, 120, 120)",
+                                        "  ({file})"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::state::{AppState, Channel, PanelTab, PanelTabData, RadioAppState, TabProps};
+
+static BYTES_PER_ROW: usize = 16;
+
+/// A read-only tab that shows a hex dump of a binary file.
+pub struct HexViewerTab {
+    pub(crate) path: PathBuf,
+    pub(crate) bytes: Vec<u8>,
+}
+
+impl PanelTab for HexViewerTab {
+    fn get_data(&self) -> PanelTabData {
+        PanelTabData {
+            id: self.path.to_str().unwrap().to_owned(),
+            title: self.path.file_name().unwrap().to_str().unwrap().to_owned(),
+            edited: false,
+            preview: false,
+            read_only: false,
+        }
+    }
+
+    fn render(&self) -> fn(TabProps) -> Element {
+        HexViewer
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl HexViewerTab {
+    /// Open a HexViewerTab in the focused panel.
+    pub fn open_with(app_state: &mut AppState, path: PathBuf, bytes: Vec<u8>) {
+        app_state.push_tab(Self { path, bytes }, app_state.focused_panel, true);
+    }
+}
+
+fn hex_viewer_tab(app_state: &AppState, panel_index: usize, tab_index: usize) -> &HexViewerTab {
+    app_state
+        .panel(panel_index)
+        .tab(tab_index)
+        .as_any()
+        .downcast_ref::<HexViewerTab>()
+        .unwrap()
+}
+
+#[allow(non_snake_case)]
+pub fn HexViewer(
+    TabProps {
+        panel_index,
+        tab_index,
+    }: TabProps,
+) -> Element {
+    let radio_app_state =
+        use_radio::<AppState, Channel>(Channel::follow_tab(panel_index, tab_index));
+
+    let app_state = radio_app_state.read();
+    let tab = hex_viewer_tab(&app_state, panel_index, tab_index);
+    let bytes_len = tab.bytes.len();
+    let rows = bytes_len.div_ceil(BYTES_PER_ROW);
+    let font_size = app_state.font_size();
+    let line_height = (font_size * app_state.line_height()).floor();
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            background: "rgb(40, 40, 40)",
+            label {
+                height: "28",
+                padding: "6 10",
+                color: "rgb(215, 215, 215)",
+                "Binary file · {bytes_len} bytes · read-only"
+            }
+            VirtualScrollView {
+                theme: theme_with!(ScrollViewTheme {
+                    width: "100%".into(),
+                    height: "fill".into(),
+                }),
+                length: rows,
+                item_size: line_height,
+                builder_args: (radio_app_state, panel_index, tab_index, font_size, line_height),
+                direction: "vertical",
+                builder: hex_row_builder
+            }
+        }
+    )
+}
+
+type HexRowBuilderOptions = (RadioAppState, usize, usize, f32, f32);
+
+fn hex_row_builder(row: usize, values: &Option<HexRowBuilderOptions>) -> Element {
+    let (radio_app_state, panel_index, tab_index, font_size, line_height) =
+        values.as_ref().unwrap();
+    let app_state = radio_app_state.read();
+    let tab = hex_viewer_tab(&app_state, *panel_index, *tab_index);
+
+    let start = row * BYTES_PER_ROW;
+    let end = (start + BYTES_PER_ROW).min(tab.bytes.len());
+    let chunk = &tab.bytes[start..end];
+
+    let hex = chunk
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let ascii = chunk
+        .iter()
+        .map(|byte| {
+            if byte.is_ascii_graphic() || *byte == b' ' {
+                *byte as char
+            } else {
+                '.'
+            }
+        })
+        .collect::<String>();
+    // Keep the ASCII column aligned on the last row
+    let hex_width = BYTES_PER_ROW * 3 - 1;
+
+    rsx!(
+        rect {
+            key: "{row}",
+            height: "{line_height}",
+            direction: "horizontal",
+            cross_align: "center",
+            padding: "0 10",
+            font_size: "{font_size}",
+            font_family: "Jetbrains Mono",
+            label {
+                color: "rgb(135, 135, 135)",
+                "{start:08x}  "
+            }
+            label {
+                color: "rgb(223, 191, 142)",
+                "{hex:<hex_width$}  "
+            }
+            label {
+                color: "rgb(200, 200, 200)",
+                "{ascii}"
+            }
+        }
+    )
+}
@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+use skia_safe::{Data, Image};
+
+use crate::{
+    fs::ImageKind,
+    state::{AppState, Channel, PanelTab, PanelTabData, TabProps},
+};
+
+static ZOOM_STEP: f32 = 0.25;
+static MIN_ZOOM: f32 = 0.1;
+static MAX_ZOOM: f32 = 10.0;
+
+/// Size used when the intrinsic size of an image is unknown, e.g. SVGs.
+static FALLBACK_SIZE: (f32, f32) = (512.0, 512.0);
+
+/// A read-only tab that displays an image.
+pub struct ImageViewerTab {
+    pub(crate) path: PathBuf,
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) kind: ImageKind,
+    pub(crate) size: (f32, f32),
+}
+
+impl PanelTab for ImageViewerTab {
+    fn get_data(&self) -> PanelTabData {
+        PanelTabData {
+            id: self.path.to_str().unwrap().to_owned(),
+            title: self.path.file_name().unwrap().to_str().unwrap().to_owned(),
+            edited: false,
+            preview: false,
+            read_only: false,
+        }
+    }
+
+    fn render(&self) -> fn(TabProps) -> Element {
+        ImageViewer
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ImageViewerTab {
+    /// Open an ImageViewerTab in the focused panel.
+    pub fn open_with(app_state: &mut AppState, path: PathBuf, bytes: Vec<u8>, kind: ImageKind) {
+        let size = match kind {
+            ImageKind::Raster => Image::from_encoded(Data::new_copy(&bytes))
+                .map(|image| (image.width() as f32, image.height() as f32)),
+            ImageKind::Svg => None,
+        }
+        .unwrap_or(FALLBACK_SIZE);
+
+        app_state.push_tab(
+            Self {
+                path,
+                bytes,
+                kind,
+                size,
+            },
+            app_state.focused_panel,
+            true,
+        );
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn ImageViewer(
+    TabProps {
+        panel_index,
+        tab_index,
+    }: TabProps,
+) -> Element {
+    let radio_app_state =
+        use_radio::<AppState, Channel>(Channel::follow_tab(panel_index, tab_index));
+    // `None` means the image is scaled to fit in the viewport
+    let mut zoom = use_signal::<Option<f32>>(|| None);
+    let (node_ref, viewport) = use_node();
+
+    let app_state = radio_app_state.read();
+    let tab = app_state
+        .panel(panel_index)
+        .tab(tab_index)
+        .as_any()
+        .downcast_ref::<ImageViewerTab>()
+        .unwrap();
+
+    let (width, height) = tab.size;
+    let fit_zoom = (viewport.area.width() / width)
+        .min(viewport.area.height() / height)
+        .min(1.0);
+    let current_zoom = zoom().unwrap_or(fit_zoom);
+
+    let image_width = (width * current_zoom).floor();
+    let image_height = (height * current_zoom).floor();
+    let content_width = image_width.max(viewport.area.width());
+    let content_height = image_height.max(viewport.area.height());
+    let percentage = (current_zoom * 100.0).round();
+
+    let fit = move |_| zoom.set(None);
+    let original_size = move |_| zoom.set(Some(1.0));
+    let zoom_out = move |_| zoom.set(Some((current_zoom - ZOOM_STEP).max(MIN_ZOOM)));
+    let zoom_in = move |_| zoom.set(Some((current_zoom + ZOOM_STEP).min(MAX_ZOOM)));
+
+    let image_element = match tab.kind {
+        ImageKind::Raster => rsx!(image {
+            image_data: dynamic_bytes(tab.bytes.clone()),
+            width: "{image_width}",
+            height: "{image_height}",
+        }),
+        ImageKind::Svg => rsx!(svg {
+            svg_content: String::from_utf8_lossy(&tab.bytes).to_string(),
+            width: "{image_width}",
+            height: "{image_height}",
+        }),
+    };
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            background: "rgb(35, 35, 35)",
+            rect {
+                width: "100%",
+                height: "34",
+                direction: "horizontal",
+                cross_align: "center",
+                padding: "0 6",
+                Button {
+                    onpress: fit,
+                    label {
+                        "Fit"
+                    }
+                }
+                Button {
+                    onpress: original_size,
+                    label {
+                        "100%"
+                    }
+                }
+                Button {
+                    onpress: zoom_out,
+                    label {
+                        "-"
+                    }
+                }
+                Button {
+                    onpress: zoom_in,
+                    label {
+                        "+"
+                    }
+                }
+                label {
+                    margin: "0 8",
+                    color: "rgb(200, 200, 200)",
+                    "{percentage}% · {width}x{height}"
+                }
+            }
+            rect {
+                width: "100%",
+                height: "fill",
+                reference: node_ref,
+                ScrollView {
+                    theme: theme_with!(ScrollViewTheme {
+                        width: "100%".into(),
+                        height: "100%".into(),
+                    }),
+                    rect {
+                        width: "{content_width}",
+                        height: "{content_height}",
+                        main_align: "center",
+                        cross_align: "center",
+                        {image_element}
+                    }
+                }
+            }
+        }
+    )
+}
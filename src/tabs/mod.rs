@@ -1,3 +1,8 @@
+pub mod diff;
 pub mod editor;
+pub mod environment;
+pub mod hex_viewer;
+pub mod image_viewer;
+pub mod project_stats;
 pub mod settings;
 pub mod welcome;
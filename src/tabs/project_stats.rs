@@ -0,0 +1,231 @@
+use dioxus_radio::prelude::use_radio;
+use freya::prelude::*;
+
+use crate::{
+    project_stats::{compute_project_stats, ProjectStats},
+    state::{AppState, Channel, PanelTab, PanelTabData, RadioAppState, TabProps},
+};
+
+const PROJECT_STATS_TAB_ID: &str = "project-stats";
+
+/// Colors of the language bars, reused in order.
+const BAR_COLORS: [&str; 6] = [
+    "rgb(90, 140, 215)",
+    "rgb(110, 190, 110)",
+    "rgb(215, 185, 90)",
+    "rgb(205, 80, 80)",
+    "rgb(185, 110, 200)",
+    "rgb(80, 180, 190)",
+];
+
+/// Overview of the files and lines of the opened folders, by language.
+pub struct ProjectStatsTab {
+    /// Not computed yet.
+    stats: Option<ProjectStats>,
+}
+
+impl PanelTab for ProjectStatsTab {
+    fn get_data(&self) -> PanelTabData {
+        PanelTabData {
+            id: PROJECT_STATS_TAB_ID.to_string(),
+            title: "Project Stats".to_string(),
+            edited: false,
+            preview: false,
+            read_only: true,
+        }
+    }
+
+    fn render(&self) -> fn(TabProps) -> Element {
+        ProjectStatsView
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ProjectStatsTab {
+    pub fn open_with(app_state: &mut AppState) {
+        app_state.push_tab(Self { stats: None }, app_state.focused_panel, true);
+    }
+}
+
+/// Compute the stats in the background and show them in the tab, wherever it is now.
+fn refresh_project_stats(mut radio_app_state: RadioAppState) {
+    spawn(async move {
+        let stats = compute_project_stats(radio_app_state).await;
+
+        let opened_tab =
+            radio_app_state
+                .read()
+                .panels()
+                .iter()
+                .enumerate()
+                .find_map(|(panel_index, panel)| {
+                    let tab_index = panel
+                        .tabs()
+                        .iter()
+                        .position(|tab| tab.get_data().id == PROJECT_STATS_TAB_ID)?;
+                    Some((panel_index, tab_index))
+                });
+
+        if let Some((panel_index, tab_index)) = opened_tab {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel_index, tab_index));
+            if let Some(tab) = app_state
+                .panel_mut(panel_index)
+                .tab_mut(tab_index)
+                .as_any_mut()
+                .downcast_mut::<ProjectStatsTab>()
+            {
+                tab.stats = Some(stats);
+            }
+        }
+    });
+}
+
+#[allow(non_snake_case)]
+pub fn ProjectStatsView(
+    TabProps {
+        panel_index,
+        tab_index,
+    }: TabProps,
+) -> Element {
+    let mut radio_app_state =
+        use_radio::<AppState, Channel>(Channel::follow_tab(panel_index, tab_index));
+
+    let app_state = radio_app_state.read();
+    let stats = app_state
+        .panel(panel_index)
+        .tab(tab_index)
+        .as_any()
+        .downcast_ref::<ProjectStatsTab>()
+        .and_then(|tab| tab.stats.clone());
+    let is_computing = stats.is_none();
+
+    use_hook(move || {
+        if is_computing {
+            refresh_project_stats(radio_app_state);
+        }
+    });
+
+    let onrefresh = move |_| {
+        {
+            let mut app_state =
+                radio_app_state.write_channel(Channel::follow_tab(panel_index, tab_index));
+            if let Some(tab) = app_state
+                .panel_mut(panel_index)
+                .tab_mut(tab_index)
+                .as_any_mut()
+                .downcast_mut::<ProjectStatsTab>()
+            {
+                tab.stats = None;
+            }
+        }
+        refresh_project_stats(radio_app_state);
+    };
+
+    rsx!(
+        rect {
+            width: "100%",
+            height: "100%",
+            background: "rgb(35, 35, 35)",
+            padding: "20",
+            rect {
+                width: "100%",
+                direction: "horizontal",
+                cross_align: "center",
+                padding: "0 0 16 0",
+                label {
+                    width: "fill",
+                    font_size: "20",
+                    "Project Stats"
+                }
+                Button {
+                    onpress: onrefresh,
+                    label {
+                        "Refresh"
+                    }
+                }
+            }
+            if let Some(stats) = stats {
+                ProjectStatsSummary {
+                    stats
+                }
+            } else {
+                label {
+                    color: "rgb(200, 200, 200)",
+                    "Counting the files of the opened folders..."
+                }
+            }
+        }
+    )
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn ProjectStatsSummary(stats: ProjectStats) -> Element {
+    let max_lines = stats
+        .languages
+        .first()
+        .map(|language| language.lines)
+        .unwrap_or_default()
+        .max(1);
+
+    rsx!(
+        label {
+            color: "rgb(200, 200, 200)",
+            "{stats.files} files · {stats.lines} lines · {stats.languages.len()} languages"
+        }
+        if stats.skipped_files > 0 {
+            label {
+                color: "rgb(135, 135, 135)",
+                "{stats.skipped_files} binary or too big files skipped"
+            }
+        }
+        ScrollView {
+            theme: theme_with!(ScrollViewTheme {
+                width: "100%".into(),
+                height: "fill".into(),
+            }),
+            for (i, language) in stats.languages.iter().enumerate() {
+                {
+                    let bar_width = language.lines as f32 / max_lines as f32 * 100.0;
+                    let bar_color = BAR_COLORS[i % BAR_COLORS.len()];
+                    rsx!(
+                        rect {
+                            key: "{language.language}",
+                            width: "100%",
+                            padding: "8 0",
+                            rect {
+                                width: "100%",
+                                direction: "horizontal",
+                                label {
+                                    width: "fill",
+                                    color: "rgb(235, 235, 235)",
+                                    "{language.language}"
+                                }
+                                label {
+                                    color: "rgb(135, 135, 135)",
+                                    "{language.files} files · {language.lines} lines ({language.blank_lines} blank)"
+                                }
+                            }
+                            rect {
+                                width: "{bar_width}%",
+                                min_width: "2",
+                                height: "6",
+                                margin: "4 0 0 0",
+                                corner_radius: "3",
+                                background: "{bar_color}",
+                            }
+                        }
+                    )
+                }
+            }
+        }
+    )
+}
@@ -1,14 +1,12 @@
 #[allow(non_snake_case)]
 pub mod Settings {
-    use crate::{settings::settings_path, state::AppState, tabs::editor::EditorTab};
+    use crate::{settings::settings_path, state::RadioAppState, utils::focus_file};
 
-    pub fn open_with(app_state: &mut AppState) {
-        let settings_path = settings_path().unwrap();
-        EditorTab::open_with(
-            app_state,
-            settings_path.clone(),
-            settings_path,
-            toml::to_string(&app_state.settings).unwrap(),
-        );
+    /// Open the settings file as it is on disk, so saving it doesn't look like a conflict.
+    pub async fn open(radio_app_state: RadioAppState) {
+        let Some(settings_path) = settings_path() else {
+            return;
+        };
+        focus_file(radio_app_state, settings_path.clone(), settings_path).await;
     }
 }
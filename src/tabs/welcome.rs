@@ -10,6 +10,8 @@ impl PanelTab for WelcomeTab {
             id: "welcome".to_string(),
             title: "welcome".to_string(),
             edited: false,
+            preview: false,
+            read_only: false,
         }
     }
     fn render(&self) -> fn(TabProps) -> Element {
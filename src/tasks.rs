@@ -0,0 +1,200 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::Command,
+    sync::mpsc::{unbounded_channel, UnboundedSender},
+};
+use tracing::info;
+
+use crate::{
+    environment::workspace_environment,
+    output::OutputLine,
+    processes::ProcessKind,
+    state::{Channel, RadioAppState},
+};
+
+/// Tasks of a workspace, relative to its folder.
+pub const TASKS_FILE: &str = ".valin/tasks.json";
+
+/// A command that can be run from the Commander, e.g:
+/// ```json
+/// [{ "name": "Build", "command": "cargo build", "cwd": "crates/app" }]
+/// ```
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Task {
+    pub name: String,
+    pub command: String,
+    /// Relative to the workspace folder.
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+}
+
+/// A task of one of the opened folders.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkspaceTask {
+    pub task: Task,
+    pub root_path: PathBuf,
+}
+
+impl WorkspaceTask {
+    pub fn cwd(&self) -> PathBuf {
+        match &self.task.cwd {
+            Some(cwd) => self.root_path.join(cwd),
+            None => self.root_path.clone(),
+        }
+    }
+}
+
+/// Load (or reload) the tasks of a folder.
+pub async fn load_tasks(mut radio_app_state: RadioAppState, root_path: PathBuf) {
+    let transport = radio_app_state.read().transport(&root_path);
+    let tasks = match transport.read_to_string(&root_path.join(TASKS_FILE)).await {
+        Ok(content) => serde_json::from_str::<Vec<Task>>(&content).unwrap_or_else(|err| {
+            info!("Failed to parse the tasks of {root_path:?}: {err:?}");
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+
+    let mut app_state = radio_app_state.write_channel(Channel::Global);
+    app_state.tasks.retain(|task| task.root_path != root_path);
+    app_state
+        .tasks
+        .extend(tasks.into_iter().map(|task| WorkspaceTask {
+            task,
+            root_path: root_path.clone(),
+        }));
+}
+
+/// Whether a changed path is the tasks file of a folder.
+pub fn is_tasks_file(path: &Path, root_path: &Path) -> bool {
+    path == root_path.join(TASKS_FILE)
+}
+
+/// Command running the given command line in the shell of the system.
+/// On unix it runs in its own process group, so it can be killed along with what it started.
+pub fn shell_command(command: &str) -> Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+
+        let mut shell = std::process::Command::new("sh");
+        shell.arg("-c").arg(command).process_group(0);
+        Command::from(shell)
+    }
+    #[cfg(not(unix))]
+    {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    }
+}
+
+/// Send every line of a reader until it ends.
+fn forward_lines(reader: impl AsyncRead + Unpin + Send + 'static, sender: UnboundedSender<String>) {
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Run a task and stream what it prints into the output panel.
+pub async fn run_task(mut radio_app_state: RadioAppState, task: WorkspaceTask) {
+    let cwd = task.cwd();
+    let env = {
+        let app_state = radio_app_state.read();
+        workspace_environment(&app_state.settings, &task.root_path)
+    };
+
+    radio_app_state
+        .write_channel(Channel::Global)
+        .show_output_panel();
+    radio_app_state
+        .write_channel(Channel::Output)
+        .output
+        .push(OutputLine::message(format!(
+            "> {} ({})",
+            task.task.command,
+            cwd.display()
+        )));
+
+    let child = shell_command(&task.task.command)
+        .current_dir(&cwd)
+        .envs(env.iter().map(|variable| (&variable.name, &variable.value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(err) => {
+            radio_app_state
+                .write_channel(Channel::Output)
+                .output
+                .push(OutputLine::message(format!(
+                    "Failed to run the task: {err}"
+                )));
+            return;
+        }
+    };
+
+    let pid = child.id();
+    if let Some(pid) = pid {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .processes
+            .register(pid, task.task.name.clone(), ProcessKind::Task);
+    }
+
+    let (sender, mut receiver) = unbounded_channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, sender.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, sender);
+    }
+
+    while let Some(line) = receiver.recv().await {
+        // Lines usually come in bursts
+        let mut lines = vec![line];
+        while let Ok(line) = receiver.try_recv() {
+            lines.push(line);
+        }
+
+        let mut app_state = radio_app_state.write_channel(Channel::Output);
+        for line in lines {
+            app_state
+                .output
+                .push(OutputLine::parse(&line, &cwd, &task.root_path));
+        }
+    }
+
+    let message = match child.wait().await {
+        Ok(status) => match status.code() {
+            Some(code) => format!("{} finished with exit code {code}", task.task.name),
+            None => format!("{} was stopped", task.task.name),
+        },
+        Err(err) => format!("{} failed: {err}", task.task.name),
+    };
+
+    radio_app_state
+        .write_channel(Channel::Output)
+        .output
+        .push(OutputLine::message(message));
+
+    if let Some(pid) = pid {
+        radio_app_state
+            .write_channel(Channel::Global)
+            .processes
+            .unregister(pid);
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use crate::{
+    environment::workspace_environment,
+    notifications::notify,
+    processes::{watch_process, ProcessKind},
+    scratchpads::focused_workspace,
+    state::{NotificationLevel, RadioAppState},
+    tabs::editor::TabEditorUtils,
+    tasks::shell_command,
+};
+
+/// Open the terminal of the settings in a folder, with the environment of its workspace.
+fn open_terminal(radio_app_state: RadioAppState, cwd: &Path, root_path: &Path) {
+    let (terminal, env) = {
+        let app_state = radio_app_state.read();
+        (
+            app_state.settings.environment.terminal.clone(),
+            workspace_environment(&app_state.settings, root_path),
+        )
+    };
+
+    let result = shell_command(&terminal)
+        .current_dir(cwd)
+        .envs(env.iter().map(|variable| (&variable.name, &variable.value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match result {
+        Ok(child) => watch_process(radio_app_state, child, terminal, ProcessKind::Terminal),
+        Err(err) => notify(
+            radio_app_state,
+            NotificationLevel::Error,
+            format!("Failed to open the terminal: {err}"),
+            None,
+        ),
+    }
+}
+
+/// Open a terminal in the workspace of the focused file, or in the first opened folder.
+pub fn open_workspace_terminal(radio_app_state: RadioAppState) {
+    let workspace = focused_workspace(&radio_app_state.read());
+    let Some(workspace) = workspace.or_else(home::home_dir) else {
+        return;
+    };
+    open_terminal(radio_app_state, &workspace, &workspace);
+}
+
+/// Open a terminal in the folder of the focused file.
+pub fn open_file_terminal(radio_app_state: RadioAppState) {
+    let paths = {
+        let app_state = radio_app_state.read();
+        let panel = app_state.panel(app_state.focused_panel());
+        panel
+            .active_tab()
+            .and_then(|tab| panel.tab(tab).as_text_editor())
+            .and_then(|editor_tab| editor_tab.editor.editor_type.paths())
+            .and_then(|(path, root_path)| Some((path.parent()?.to_path_buf(), root_path.clone())))
+    };
+
+    let Some((folder, root_path)) = paths else {
+        notify(
+            radio_app_state,
+            NotificationLevel::Warning,
+            "There is no focused file to open a terminal in its folder",
+            None,
+        );
+        return;
+    };
+    open_terminal(radio_app_state, &folder, &root_path);
+}
@@ -1,10 +1,23 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
 use freya::prelude::*;
 use skia_safe::{
     scalar,
     textlayout::{Paragraph, ParagraphBuilder, ParagraphStyle, TextStyle},
 };
 
-use crate::state::RadioAppState;
+use crate::{
+    fs::{FSTransport, FileContent},
+    state::{AppState, Channel, EditorView, RadioAppState},
+    tabs::{
+        editor::{AppStateEditorUtils, EditorTab},
+        hex_viewer::HexViewerTab,
+        image_viewer::ImageViewerTab,
+    },
+};
 
 #[allow(non_snake_case)]
 pub fn Divider() -> Element {
@@ -30,6 +43,7 @@ pub fn create_paragraph(text: &str, font_size: f32, radio_app_state: RadioAppSta
     let mut style = ParagraphStyle::default();
     let mut text_style = TextStyle::default();
     text_style.set_font_size(font_size);
+    text_style.set_font_families(&[app_state.settings.editor.rendered_font_family()]);
     style.set_text_style(&text_style);
 
     let mut paragraph_builder = ParagraphBuilder::new(&style, &app_state.font_collection);
@@ -42,3 +56,158 @@ pub fn create_paragraph(text: &str, font_size: f32, radio_app_state: RadioAppSta
 
     paragraph
 }
+
+/// Whether a file can't be written, as reported by its transport.
+pub async fn is_read_only(transport: &FSTransport, path: &Path) -> bool {
+    transport
+        .metadata(path)
+        .await
+        .is_ok_and(|metadata| metadata.read_only)
+}
+
+/// Open a file in the focused panel, using the kind of tab that suits its content.
+pub fn open_file(
+    app_state: &mut AppState,
+    path: PathBuf,
+    root_path: PathBuf,
+    content: Vec<u8>,
+    read_only: bool,
+) {
+    let content = FileContent::detect(&path, content);
+    open_file_content(app_state, path, root_path, content, read_only);
+}
+
+fn open_file_content(
+    app_state: &mut AppState,
+    path: PathBuf,
+    root_path: PathBuf,
+    content: FileContent,
+    read_only: bool,
+) {
+    match content {
+        FileContent::Text(text, format) => {
+            EditorTab::open_with(app_state, path, root_path, text, format, read_only)
+        }
+        FileContent::Image { bytes, kind } => {
+            ImageViewerTab::open_with(app_state, path, bytes, kind)
+        }
+        FileContent::Binary(bytes) => HexViewerTab::open_with(app_state, path, bytes),
+    }
+}
+
+/// Like [`open_file`], but text files are opened in a preview tab.
+pub fn open_file_as_preview(
+    app_state: &mut AppState,
+    path: PathBuf,
+    root_path: PathBuf,
+    content: Vec<u8>,
+    read_only: bool,
+) {
+    match FileContent::detect(&path, content) {
+        FileContent::Text(text, format) => {
+            EditorTab::open_as_preview(app_state, path, root_path, text, format, read_only)
+        }
+        content => open_file_content(app_state, path, root_path, content, read_only),
+    }
+}
+
+/// Focus the editor of a file (opening it if necessary) and select the given range.
+pub async fn open_file_at(
+    mut radio_app_state: RadioAppState,
+    path: PathBuf,
+    root_path: PathBuf,
+    range: Range<usize>,
+) {
+    let Some((panel, tab)) = focus_file(radio_app_state, path, root_path).await else {
+        return;
+    };
+
+    let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+    app_state
+        .editor_tab_mut(panel, tab)
+        .editor
+        .select_range(range);
+}
+
+/// Like [`open_file_at`], but moves the cursor to a line and column (both starting from 0).
+pub async fn open_file_at_position(
+    mut radio_app_state: RadioAppState,
+    path: PathBuf,
+    root_path: PathBuf,
+    line: usize,
+    column: usize,
+) {
+    let Some((panel, tab)) = focus_file(radio_app_state, path, root_path).await else {
+        return;
+    };
+
+    let mut app_state = radio_app_state.write_channel(Channel::follow_tab(panel, tab));
+    let editor = &mut app_state.editor_tab_mut(panel, tab).editor;
+    let pos = editor.position_to_char(line, column);
+    editor.move_cursor_to(pos, false);
+}
+
+/// Focus the editor of a file, opening it if necessary.
+pub async fn focus_file(
+    mut radio_app_state: RadioAppState,
+    path: PathBuf,
+    root_path: PathBuf,
+) -> Option<(usize, usize)> {
+    let opened_tab = radio_app_state.read().find_editor_tab(&path);
+    let opened_tab = match opened_tab {
+        Some(opened_tab) => Some(opened_tab),
+        None => {
+            let transport = radio_app_state.read().transport(&path);
+            let Ok(content) = transport.read(&path).await else {
+                return None;
+            };
+            let read_only = is_read_only(&transport, &path).await;
+            let mut app_state = radio_app_state.write_channel(Channel::Global);
+            open_file(&mut app_state, path.clone(), root_path, content, read_only);
+            app_state.find_editor_tab(&path)
+        }
+    };
+
+    let (panel, tab) = opened_tab?;
+
+    let mut app_state = radio_app_state.write_channel(Channel::Global);
+    app_state.set_focused_panel(panel);
+    app_state.panel_mut(panel).set_active_tab(tab);
+    app_state.set_focused_view(EditorView::Panels);
+
+    Some((panel, tab))
+}
+
+/// FNV-1a hash of some chunks of bytes, as if they were a single slice. Unlike the hasher
+/// of the standard library, it never changes between builds, so it can name files that outlive them.
+pub fn stable_hash<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> u64 {
+    chunks
+        .into_iter()
+        .flatten()
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+/// Check if all the chars of the query appear in order in the text, ignoring the case.
+/// Returns how many chars were skipped between the matched ones, so lower is better.
+pub fn fuzzy_match(text: &str, query: &str) -> Option<usize> {
+    let mut text_chars = text.chars().flat_map(char::to_lowercase).enumerate();
+    let mut last_match = None;
+    let mut gaps = 0;
+
+    for query_char in query
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let (i, _) = text_chars.find(|(_, ch)| *ch == query_char)?;
+        gaps += match last_match {
+            Some(last_match) => i - last_match - 1,
+            None => i,
+        };
+        last_match = Some(i);
+    }
+
+    Some(gaps)
+}
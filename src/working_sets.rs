@@ -0,0 +1,80 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    settings::data_path,
+    state::{Channel, RadioAppState},
+    tabs::editor::{AppStateEditorUtils, TabEditorUtils},
+    utils::{is_read_only, open_file},
+};
+
+/// A named group of files that can be reopened at once.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WorkingSet {
+    pub name: String,
+    /// Path and root path of every file.
+    pub files: Vec<(PathBuf, PathBuf)>,
+}
+
+fn working_sets_path() -> Option<PathBuf> {
+    Some(data_path()?.join("working_sets.json"))
+}
+
+pub fn load_working_sets() -> Vec<WorkingSet> {
+    working_sets_path()
+        .and_then(|path| read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_working_sets(working_sets: &[WorkingSet]) -> Option<()> {
+    let path = working_sets_path()?;
+    create_dir_all(path.parent()?).ok()?;
+    let content = serde_json::to_string_pretty(working_sets).ok()?;
+    write(path, content).ok()
+}
+
+/// Replace the files of the focused panel with the ones of a working set.
+/// Files with unsaved changes are kept open.
+pub async fn open_working_set(mut radio_app_state: RadioAppState, working_set: WorkingSet) {
+    {
+        let mut app_state = radio_app_state.write_channel(Channel::Global);
+        let panel = app_state.focused_panel;
+
+        for tab in (0..app_state.panel(panel).tabs().len()).rev() {
+            let keep_tab = match app_state.panel(panel).tab(tab).as_text_editor() {
+                Some(editor_tab) => {
+                    let path = editor_tab.editor.path();
+                    editor_tab.editor.is_edited()
+                        || working_set.files.iter().any(|(file, _)| Some(file) == path)
+                }
+                None => false,
+            };
+
+            if !keep_tab {
+                app_state.close_tab(panel, tab);
+            }
+        }
+    }
+
+    for (path, root_path) in working_set.files {
+        if radio_app_state.read().find_editor_tab(&path).is_some() {
+            continue;
+        }
+
+        let transport = radio_app_state.read().transport(&path);
+        match transport.read(&path).await {
+            Ok(content) => {
+                let read_only = is_read_only(&transport, &path).await;
+                let mut app_state = radio_app_state.write_channel(Channel::Global);
+                open_file(&mut app_state, path, root_path, content, read_only);
+            }
+            Err(err) => info!("Failed to open {path:?} from the working set: {err:?}"),
+        }
+    }
+}